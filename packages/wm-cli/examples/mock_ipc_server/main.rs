@@ -0,0 +1,43 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mock IPC server with a fixed container tree, for testing IPC clients
+//! (e.g. `lavawm-cli watch`) without a running WM.
+//!
+//! Usage: `cargo run -p wm-cli --example mock_ipc_server -- [port]`
+
+mod server;
+
+use tokio::net::TcpListener;
+
+/// Default port, which differs from the WM's so both can run at once.
+const DEFAULT_PORT: u32 = 6124;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+  let port = std::env::args()
+    .nth(1)
+    .map(|port| port.parse::<u32>())
+    .transpose()?
+    .unwrap_or(DEFAULT_PORT);
+
+  let listener = TcpListener::bind(format!("127.0.0.1:{port}")).await?;
+  println!("Mock IPC server listening on port {port}.");
+
+  server::serve(listener).await
+}
@@ -0,0 +1,281 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mock IPC server with a fixed container tree, for testing IPC clients
+//! (e.g. `lavawm-cli watch`) without a running WM.
+//!
+//! Responds to `query`, `command`, `sub` and `unsub` messages, and emits a
+//! `focus_changed` event to subscribers every 2 seconds.
+
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{accept_async, tungstenite::Message};
+use uuid::Uuid;
+use wm_common::{
+  AppCommand, BindingModeConfig, BindingModesData, ClientResponseData,
+  ClientResponseMessage, CommandData, CommandsData, ConfigSchemaData,
+  ContainerDto, DisplayState, EventSubscribeData,
+  EventSubscriptionMessage, FocusedData, LengthValue, MetricsData,
+  MetricsDto, MonitorDto, MonitorsData, ParsedConfig, Rect, RectDelta,
+  ServerMessage, SizeConstraints, SplitContainerDto, TilingDirection,
+  WindowDto, WindowEffectOverrides, WindowState, WindowsData, WmEvent,
+  WorkspaceDto, WorkspacesData,
+};
+
+/// Accepts connections on the given listener until it fails.
+pub async fn serve(listener: TcpListener) -> anyhow::Result<()> {
+  loop {
+    let (stream, _) = listener.accept().await?;
+
+    tokio::spawn(async move {
+      if let Err(err) = handle_connection(stream).await {
+        println!("Connection closed: {err}");
+      }
+    });
+  }
+}
+
+async fn handle_connection(stream: TcpStream) -> anyhow::Result<()> {
+  let mut stream = accept_async(stream).await?;
+  let mut subscription_ids = Vec::new();
+  let mut event_interval = tokio::time::interval(Duration::from_secs(2));
+  let mut focused_index = 0;
+
+  loop {
+    tokio::select! {
+      message = stream.next() => {
+        let message = match message.transpose()? {
+          Some(Message::Text(message)) => message,
+          Some(_) => continue,
+          None => return Ok(()),
+        };
+
+        println!("Received: {message}");
+
+        let data = response_data(&message);
+
+        if let Ok(ClientResponseData::EventSubscribe(data)) = &data {
+          subscription_ids.push(data.subscription_id);
+        }
+
+        let response = ServerMessage::ClientResponse(ClientResponseMessage {
+          client_message: message.to_string(),
+          success: data.is_ok(),
+          error: data.as_ref().err().cloned(),
+          data: data.ok(),
+        });
+
+        stream
+          .send(Message::Text(serde_json::to_string(&response)?.into()))
+          .await?;
+      }
+      _ = event_interval.tick() => {
+        // Alternate focus between the windows of the mock tree.
+        let windows = windows();
+        focused_index = (focused_index + 1) % windows.len();
+
+        for subscription_id in &subscription_ids {
+          let event =
+            ServerMessage::EventSubscription(EventSubscriptionMessage {
+              data: Some(WmEvent::FocusChanged {
+                focused_container: windows[focused_index].clone(),
+              }),
+              error: None,
+              subscription_id: *subscription_id,
+              success: true,
+            });
+
+          stream
+            .send(Message::Text(serde_json::to_string(&event)?.into()))
+            .await?;
+        }
+      }
+    }
+  }
+}
+
+fn response_data(message: &str) -> Result<ClientResponseData, String> {
+  let args = message.split_whitespace().collect::<Vec<_>>();
+
+  match args.as_slice() {
+    ["query", "monitors", ..] => {
+      Ok(ClientResponseData::Monitors(MonitorsData {
+        monitors: vec![monitor()],
+      }))
+    }
+    ["query", "commands", ..] => {
+      Ok(ClientResponseData::Commands(CommandsData {
+        commands: AppCommand::schema().subcommands,
+      }))
+    }
+    ["query", "config-schema", ..] => {
+      Ok(ClientResponseData::ConfigSchema(ConfigSchemaData {
+        config_schema: ParsedConfig::json_schema(),
+      }))
+    }
+    ["query", "focused", ..] => {
+      Ok(ClientResponseData::Focused(FocusedData {
+        focused: windows().remove(0),
+      }))
+    }
+    ["query", "metrics", ..] => {
+      Ok(ClientResponseData::Metrics(MetricsData {
+        metrics: MetricsDto {
+          uptime_seconds: 60,
+          commands: 3,
+          platform_syncs: 5,
+          windows_redrawn: 8,
+          ..MetricsDto::default()
+        },
+      }))
+    }
+    ["query", "windows", ..] => {
+      Ok(ClientResponseData::Windows(WindowsData {
+        windows: windows(),
+      }))
+    }
+    ["query", "binding-modes", ..] => {
+      Ok(ClientResponseData::BindingModes(BindingModesData {
+        binding_modes: vec![BindingModeConfig {
+          name: "resize".to_string(),
+          display_name: None,
+          keybindings: Vec::new(),
+        }],
+      }))
+    }
+    ["query", "paused", ..] => Ok(ClientResponseData::Paused(false)),
+    ["query", "workspaces", ..] => {
+      Ok(ClientResponseData::Workspaces(WorkspacesData {
+        workspaces: vec![workspace()],
+      }))
+    }
+    ["command", ..] => Ok(ClientResponseData::Command(CommandData {
+      subject_container_id: Uuid::new_v4(),
+    })),
+    ["sub", ..] => {
+      Ok(ClientResponseData::EventSubscribe(EventSubscribeData {
+        subscription_id: Uuid::new_v4(),
+      }))
+    }
+    ["unsub", ..] => Ok(ClientResponseData::EventUnsubscribe),
+    _ => Err(format!("Unsupported by mock IPC server: '{message}'.")),
+  }
+}
+
+fn monitor() -> ContainerDto {
+  ContainerDto::Monitor(MonitorDto {
+    id: Uuid::nil(),
+    parent_id: None,
+    children: vec![workspace()],
+    child_focus_order: Vec::new(),
+    has_focus: false,
+    width: 1920,
+    height: 1080,
+    x: 0,
+    y: 0,
+    dpi: 96,
+    scale_factor: 1.,
+    handle: 0,
+    device_name: "\\\\.\\DISPLAY1".to_string(),
+    device_path: None,
+    hardware_id: None,
+    working_rect: Rect::from_xy(0, 0, 1920, 1080),
+  })
+}
+
+/// Workspace containing a window and a vertical split of two windows.
+fn workspace() -> ContainerDto {
+  let mut windows = windows().into_iter();
+  let first_window = windows.next().into_iter();
+
+  let split = ContainerDto::Split(SplitContainerDto {
+    id: Uuid::nil(),
+    parent_id: None,
+    children: windows.collect(),
+    child_focus_order: Vec::new(),
+    has_focus: false,
+    tiling_size: 0.5,
+    width: 960,
+    height: 1080,
+    x: 960,
+    y: 0,
+    tiling_direction: TilingDirection::Vertical,
+  });
+
+  ContainerDto::Workspace(WorkspaceDto {
+    id: Uuid::nil(),
+    name: "1".to_string(),
+    display_name: None,
+    parent_id: None,
+    children: first_window.chain([split]).collect(),
+    child_focus_order: Vec::new(),
+    has_focus: false,
+    is_displayed: true,
+    is_urgent: false,
+    is_monocle: false,
+    width: 1920,
+    height: 1080,
+    x: 0,
+    y: 0,
+    tiling_direction: TilingDirection::Horizontal,
+  })
+}
+
+fn windows() -> Vec<ContainerDto> {
+  [
+    ("Notes", "notepad", 0.5),
+    ("Terminal", "WindowsTerminal", 0.5),
+    ("Browser", "firefox", 0.5),
+  ]
+  .into_iter()
+  .enumerate()
+  .map(|(index, (title, process_name, tiling_size))| {
+    ContainerDto::Window(WindowDto {
+      id: Uuid::from_u128(index as u128 + 1),
+      parent_id: None,
+      has_focus: index == 0,
+      tiling_size: Some(tiling_size),
+      width: 960,
+      height: 1080,
+      x: 0,
+      y: 0,
+      state: WindowState::Tiling,
+      prev_state: None,
+      display_state: DisplayState::Shown,
+      border_delta: RectDelta::new(
+        LengthValue::from_px(0),
+        LengthValue::from_px(0),
+        LengthValue::from_px(0),
+        LengthValue::from_px(0),
+      ),
+      floating_placement: Rect::from_xy(0, 0, 960, 540),
+      handle: 0,
+      title: title.to_string(),
+      class_name: "MockWindow".to_string(),
+      process_name: process_name.to_string(),
+      active_drag: None,
+      is_urgent: false,
+      sticky: None,
+      size_constraints: SizeConstraints::default(),
+      effect_overrides: WindowEffectOverrides::default(),
+    })
+  })
+  .collect()
+}
@@ -1,41 +1,195 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
-use anyhow::Context;
-use wm_common::ClientResponseData;
+mod output;
+mod table;
+mod tree;
+mod watch;
+
+use std::{io, path::Path, process::ExitCode};
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use futures_util::StreamExt;
+use serde::Serialize;
+pub use tree::render as render_tree;
+use wm_common::{AppCommand, CompletionShell, OutputArgs, OutputFormat};
 use wm_ipc_client::IpcClient;
 
-pub async fn start(args: Vec<String>) -> anyhow::Result<()> {
-  let mut client = IpcClient::connect().await?;
+use crate::output::format_message;
+
+/// Exit code when the WM responds with an error (e.g. an invalid command).
+const EXIT_RESPONSE_ERROR: u8 = 1;
+
+/// Exit code when unable to communicate with the IPC server. Exit code 2
+/// is skipped, since it's used for invalid arguments.
+const EXIT_IPC_ERROR: u8 = 3;
 
+/// Exit code when the `--field` selector doesn't match the response.
+const EXIT_FIELD_NOT_FOUND: u8 = 4;
+
+pub async fn start(args: Vec<String>) -> ExitCode {
+  let app_command = AppCommand::parse_with_default(&args);
+  let output = app_command.output().cloned().unwrap_or_default();
   let message = args[1..].join(" ");
-  client
-    .send(&message)
-    .await
-    .context("Failed to send command to IPC server.")?;
-
-  let client_response = client
-    .client_response(&message)
-    .await
-    .context("Failed to receive response from IPC server.")?;
-
-  match client_response.data {
-    // For event subscriptions, omit the initial response message and
-    // continuously output subsequent event messages.
-    Some(ClientResponseData::EventSubscribe(data)) => loop {
-      let event_subscription = client
-        .event_subscription(&data.subscription_id)
-        .await
-        .context("Failed to receive response from IPC server.")?;
-
-      println!("{}", serde_json::to_string(&event_subscription)?);
-    },
+
+  if let AppCommand::Completions { shell } = app_command {
+    print_completions(shell, &args[0]);
+    return ExitCode::SUCCESS;
+  }
+
+  if let AppCommand::Replay { .. } = app_command {
+    eprintln!(
+      "Recordings are replayed by the main executable (e.g. `lavawm replay <path>`)."
+    );
+    return ExitCode::FAILURE;
+  }
+
+  if let AppCommand::Watch { port, snapshot } = app_command {
+    return match watch::watch(port, snapshot).await {
+      Ok(()) => ExitCode::SUCCESS,
+      Err(err) => ipc_error(&err),
+    };
+  }
+
+  let client = match IpcClient::connect().await {
+    Ok(client) => client,
+    Err(err) => return ipc_error(&err),
+  };
+
+  // For event subscriptions, omit the initial response message and
+  // continuously output subsequent event messages.
+  if let AppCommand::Sub { .. } = app_command {
+    let (response, events) = match client.subscribe_message(&message).await
+    {
+      Ok(subscription) => subscription,
+      Err(err) => return ipc_error(&err),
+    };
+
+    if !response.success {
+      return exit_code(
+        print_message(&response, &output).and(Err(EXIT_RESPONSE_ERROR)),
+      );
+    }
+
+    let mut events = Box::pin(events);
+
+    while let Some(event_subscription) = events.next().await {
+      if let Err(code) = print_message(&event_subscription, &output) {
+        return ExitCode::from(code);
+      }
+    }
+
+    ExitCode::from(EXIT_IPC_ERROR)
+  } else {
     // For all other messages, output and exit when the first response
     // message is received.
-    _ => {
-      println!("{}", serde_json::to_string(&client_response)?);
-    }
+    let client_response = match client.send_message(&message).await {
+      Ok(client_response) => client_response,
+      Err(err) => return ipc_error(&err),
+    };
+
+    let res = print_message(&client_response, &output);
+
+    exit_code(if client_response.success {
+      res
+    } else {
+      res.and(Err(EXIT_RESPONSE_ERROR))
+    })
+  }
+}
+
+/// Outputs a completion script for the given shell. The binary name is
+/// taken from the invoked executable, so that completions also work for
+/// renamed binaries.
+fn print_completions(shell: CompletionShell, executable: &str) {
+  let shell = match shell {
+    CompletionShell::Bash => Shell::Bash,
+    CompletionShell::Fish => Shell::Fish,
+    CompletionShell::Powershell => Shell::PowerShell,
+    CompletionShell::Zsh => Shell::Zsh,
+  };
+
+  let bin_name = Path::new(executable)
+    .file_stem()
+    .and_then(|stem| stem.to_str())
+    .unwrap_or("lavawm-cli");
+
+  clap_complete::generate(
+    shell,
+    &mut AppCommand::command(),
+    bin_name,
+    &mut io::stdout(),
+  );
+}
+
+/// Outputs a response or event message in the given output format.
+///
+/// Unsuccessful messages are output as-is in the JSON formats, and
+/// otherwise only their error is output (to stderr).
+fn print_message(
+  message: &impl Serialize,
+  output: &OutputArgs,
+) -> Result<(), u8> {
+  let message = serde_json::to_value(message).map_err(|err| {
+    eprintln!("Invalid message from IPC server: {err}");
+    EXIT_IPC_ERROR
+  })?;
+
+  let is_success = message
+    .get("success")
+    .and_then(serde_json::Value::as_bool)
+    .unwrap_or(true);
+
+  let is_json_output = output.field.is_none()
+    && matches!(output.format, OutputFormat::Json | OutputFormat::Pretty);
+
+  if !is_success && !is_json_output {
+    let error = message
+      .get("error")
+      .and_then(serde_json::Value::as_str)
+      .unwrap_or("Unknown error.");
+
+    eprintln!("{error}");
+    return Ok(());
   }
 
+  let Some(formatted) = format_message(&message, output) else {
+    eprintln!(
+      "Field '{}' not found in response.",
+      output.field.as_deref().unwrap_or_default()
+    );
+
+    return Err(EXIT_FIELD_NOT_FOUND);
+  };
+
+  println!("{formatted}");
   Ok(())
 }
+
+fn exit_code(res: Result<(), u8>) -> ExitCode {
+  res.map_or_else(ExitCode::from, |()| ExitCode::SUCCESS)
+}
+
+fn ipc_error(err: &anyhow::Error) -> ExitCode {
+  eprintln!("{err:#}");
+  ExitCode::from(EXIT_IPC_ERROR)
+}
@@ -16,14 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{env, process::Command};
+use std::{
+  env,
+  process::{Command, ExitCode},
+};
 
 use anyhow::Context;
 use wm_cli::start;
 use wm_common::AppCommand;
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
   let args = std::env::args().collect::<Vec<_>>();
   let app_command = AppCommand::parse_with_default(&args);
 
@@ -56,8 +59,8 @@ async fn main() -> anyhow::Result<()> {
         .spawn()
         .context("Failed to start main executable.")?;
 
-      Ok(())
+      Ok(ExitCode::SUCCESS)
     }
-    _ => start(args).await,
+    _ => Ok(start(args).await),
   }
 }
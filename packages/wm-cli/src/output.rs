@@ -0,0 +1,211 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde_json::Value;
+use wm_common::{OutputArgs, OutputFormat};
+
+use crate::{table, tree};
+
+/// Formats a response or event message for output.
+///
+/// Returns `None` if the `--field` selector doesn't match the data of the
+/// message.
+pub fn format_message(
+  message: &Value,
+  output: &OutputArgs,
+) -> Option<String> {
+  let data = message.get("data").unwrap_or(&Value::Null);
+
+  let Some(field) = &output.field else {
+    return Some(match output.format {
+      OutputFormat::Json => message.to_string(),
+      OutputFormat::Pretty => format!("{message:#}"),
+      OutputFormat::Table => table::render(unwrap_data(data)),
+      OutputFormat::Tree => tree::render(unwrap_data(data)),
+    });
+  };
+
+  let selected = select_field(data, field)?;
+
+  // Scalars are output as-is, so that they can be used directly in
+  // scripts.
+  if !selected.is_object() && !selected.is_array() {
+    return Some(display_value(&selected));
+  }
+
+  Some(match output.format {
+    OutputFormat::Json => selected.to_string(),
+    OutputFormat::Pretty => format!("{selected:#}"),
+    OutputFormat::Table => table::render(&selected),
+    OutputFormat::Tree => tree::render(&selected),
+  })
+}
+
+/// Resolves a field path (e.g. `workspaces[0].name`) against a value.
+///
+/// Path segments are separated by `.`, and list items are selected with
+/// either `[0]` or `.0`. A segment applied to a list is applied to each
+/// of its items, and object keys that wrap the response data (e.g. the
+/// `focused` key of `query focused`) can be omitted.
+fn select_field(value: &Value, path: &str) -> Option<Value> {
+  let path = path.trim_start_matches('$').trim_start_matches('.');
+
+  let segments = path
+    .split('.')
+    .flat_map(|segment| segment.split('['))
+    .map(|segment| segment.trim_end_matches(']'))
+    .filter(|segment| !segment.is_empty())
+    .collect::<Vec<_>>();
+
+  resolve_segments(value, &segments)
+}
+
+fn resolve_segments(value: &Value, segments: &[&str]) -> Option<Value> {
+  let Some((segment, rest)) = segments.split_first() else {
+    return Some(value.clone());
+  };
+
+  match value {
+    Value::Array(items) => {
+      if let Ok(index) = segment.parse::<usize>() {
+        return resolve_segments(items.get(index)?, rest);
+      }
+
+      // Items without the field are skipped (e.g. splits when selecting
+      // window titles).
+      let values = items
+        .iter()
+        .filter_map(|item| resolve_segments(item, segments))
+        .collect::<Vec<_>>();
+
+      (!values.is_empty() || items.is_empty())
+        .then_some(Value::Array(values))
+    }
+    Value::Object(map) => match map.get(*segment) {
+      Some(value) => resolve_segments(value, rest),
+      None if map.len() == 1 => {
+        resolve_segments(map.values().next()?, segments)
+      }
+      None => None,
+    },
+    _ => None,
+  }
+}
+
+/// Gets the contents of response data that is wrapped in a single object
+/// key (e.g. `{ "windows": [...] }`).
+fn unwrap_data(data: &Value) -> &Value {
+  match data {
+    Value::Object(map) if map.len() == 1 => {
+      map.values().next().unwrap_or(data)
+    }
+    _ => data,
+  }
+}
+
+/// Formats a value for display, with strings output without quotes.
+pub fn display_value(value: &Value) -> String {
+  match value {
+    Value::String(string) => string.clone(),
+    _ => value.to_string(),
+  }
+}
+
+/// Whether the value is a serialized container (e.g. a `WindowDto`).
+pub fn is_container(value: &Value) -> bool {
+  value.get("id").is_some()
+    && matches!(
+      value.get("type").and_then(Value::as_str),
+      Some("root" | "monitor" | "workspace" | "split" | "window")
+    )
+}
+
+/// Human-readable details of a serialized container.
+pub struct ContainerSummary {
+  pub kind: String,
+  pub id: String,
+  pub name: String,
+  pub process: String,
+  pub state: String,
+  pub tiling_size: String,
+  pub has_focus: bool,
+}
+
+impl ContainerSummary {
+  pub fn new(container: &Value) -> Self {
+    let field = |key: &str| {
+      container
+        .get(key)
+        .filter(|value| !value.is_null())
+        .map(display_value)
+        .unwrap_or_default()
+    };
+
+    let kind = field("type");
+
+    let (name, state) = match kind.as_str() {
+      "monitor" => (
+        field("deviceName"),
+        format!("{}x{}", field("width"), field("height")),
+      ),
+      "workspace" => {
+        let name = container
+          .get("displayName")
+          .and_then(Value::as_str)
+          .map_or_else(|| field("name"), ToString::to_string);
+
+        let is_displayed = container
+          .get("isDisplayed")
+          .and_then(Value::as_bool)
+          .unwrap_or(false);
+
+        let state = if is_displayed { "displayed" } else { "hidden" };
+        (name, state.to_string())
+      }
+      "split" => (field("tilingDirection"), String::new()),
+      "window" => (
+        field("title"),
+        container
+          .get("state")
+          .and_then(|state| state.get("type"))
+          .map(display_value)
+          .unwrap_or_default(),
+      ),
+      _ => (String::new(), String::new()),
+    };
+
+    let tiling_size = container
+      .get("tilingSize")
+      .and_then(Value::as_f64)
+      .map(|size| format!("{:.0}%", size * 100.))
+      .unwrap_or_default();
+
+    Self {
+      id: field("id"),
+      process: field("processName"),
+      has_focus: container
+        .get("hasFocus")
+        .and_then(Value::as_bool)
+        .unwrap_or(false),
+      kind,
+      name,
+      state,
+      tiling_size,
+    }
+  }
+}
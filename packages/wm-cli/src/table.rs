@@ -0,0 +1,159 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde_json::Value;
+
+use crate::output::{display_value, is_container, ContainerSummary};
+
+/// Maximum number of characters shown in a table cell.
+const MAX_CELL_LENGTH: usize = 48;
+
+/// Renders a value as a table.
+///
+/// Lists are rendered with a row per item and objects with a row per
+/// field. Containers are rendered with a fixed set of columns.
+pub fn render(value: &Value) -> String {
+  match value {
+    Value::Array(items) if items.iter().all(is_container) => {
+      container_table(items)
+    }
+    Value::Array(items) if items.iter().all(Value::is_object) => {
+      object_table(items)
+    }
+    Value::Array(items) => items
+      .iter()
+      .map(display_value)
+      .collect::<Vec<_>>()
+      .join("\n"),
+    Value::Object(_) if is_container(value) => {
+      container_table(std::slice::from_ref(value))
+    }
+    Value::Object(map) => format_table(
+      &["FIELD", "VALUE"],
+      map
+        .iter()
+        .map(|(key, value)| vec![key.clone(), display_value(value)])
+        .collect(),
+    ),
+    _ => display_value(value),
+  }
+}
+
+fn container_table(containers: &[Value]) -> String {
+  let rows = containers
+    .iter()
+    .map(|container| {
+      let summary = ContainerSummary::new(container);
+
+      vec![
+        summary.kind,
+        summary.id,
+        summary.name,
+        summary.process,
+        summary.state,
+        summary.tiling_size,
+        if summary.has_focus { "*" } else { "" }.to_string(),
+      ]
+    })
+    .collect();
+
+  format_table(
+    &["TYPE", "ID", "NAME", "PROCESS", "STATE", "SIZE", "FOCUS"],
+    rows,
+  )
+}
+
+/// Renders a list of objects, with a column per field of the first
+/// object.
+fn object_table(objects: &[Value]) -> String {
+  let keys = objects
+    .first()
+    .and_then(Value::as_object)
+    .map(|map| map.keys().cloned().collect::<Vec<_>>())
+    .unwrap_or_default();
+
+  let rows = objects
+    .iter()
+    .map(|object| {
+      keys
+        .iter()
+        .map(|key| object.get(key).map(display_value).unwrap_or_default())
+        .collect()
+    })
+    .collect();
+
+  let headers = keys
+    .iter()
+    .map(|key| key.to_uppercase())
+    .collect::<Vec<_>>();
+
+  format_table(
+    &headers.iter().map(String::as_str).collect::<Vec<_>>(),
+    rows,
+  )
+}
+
+fn format_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+  let rows = rows
+    .into_iter()
+    .map(|row| row.into_iter().map(truncate).collect::<Vec<_>>())
+    .collect::<Vec<_>>();
+
+  let widths = headers
+    .iter()
+    .enumerate()
+    .map(|(index, header)| {
+      rows
+        .iter()
+        .map(|row| row[index].chars().count())
+        .chain([header.len()])
+        .max()
+        .unwrap_or(0)
+    })
+    .collect::<Vec<_>>();
+
+  let format_row = |cells: Vec<&str>| {
+    cells
+      .iter()
+      .zip(&widths)
+      .map(|(cell, &width)| format!("{cell:width$}"))
+      .collect::<Vec<_>>()
+      .join("  ")
+      .trim_end()
+      .to_string()
+  };
+
+  std::iter::once(format_row(headers.to_vec()))
+    .chain(
+      rows
+        .iter()
+        .map(|row| format_row(row.iter().map(String::as_str).collect())),
+    )
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn truncate(cell: String) -> String {
+  if cell.chars().count() <= MAX_CELL_LENGTH {
+    return cell;
+  }
+
+  let truncated =
+    cell.chars().take(MAX_CELL_LENGTH - 1).collect::<String>();
+  format!("{truncated}…")
+}
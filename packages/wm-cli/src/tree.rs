@@ -0,0 +1,159 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde_json::Value;
+
+use crate::output::{display_value, is_container, ContainerSummary};
+
+struct Node {
+  label: String,
+  children: Vec<Node>,
+}
+
+/// Renders a value as a tree.
+///
+/// Containers are shown by their hierarchy, with the tiling direction of
+/// splits and the tiling size of splits and windows. Other values are
+/// shown by their fields.
+#[must_use]
+pub fn render(value: &Value) -> String {
+  let root = build_node(None, value);
+
+  // Lists and objects without a key aren't shown as a node themselves.
+  let roots = if root.label.is_empty() {
+    root.children
+  } else {
+    vec![root]
+  };
+
+  let mut lines = Vec::new();
+
+  for node in &roots {
+    write_node(node, "", "", &mut lines);
+  }
+
+  lines.join("\n")
+}
+
+fn build_node(key: Option<&str>, value: &Value) -> Node {
+  let key_label = key.unwrap_or_default().to_string();
+
+  match value {
+    Value::Object(_) if is_container(value) => Node {
+      label: match key {
+        Some(key) => format!("{key}: {}", container_label(value)),
+        None => container_label(value),
+      },
+      children: value
+        .get("children")
+        .and_then(Value::as_array)
+        .map(|children| {
+          children
+            .iter()
+            .map(|child| build_node(None, child))
+            .collect()
+        })
+        .unwrap_or_default(),
+    },
+    Value::Object(map) => Node {
+      label: key_label,
+      children: map
+        .iter()
+        .map(|(key, value)| build_node(Some(key), value))
+        .collect(),
+    },
+    Value::Array(items) => Node {
+      label: key_label,
+      children: items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+          if is_container(item) {
+            build_node(None, item)
+          } else {
+            build_node(Some(&format!("[{index}]")), item)
+          }
+        })
+        .collect(),
+    },
+    _ => Node {
+      label: match key {
+        Some(key) => format!("{key}: {}", display_value(value)),
+        None => display_value(value),
+      },
+      children: Vec::new(),
+    },
+  }
+}
+
+/// Gets the label of a container (e.g. `window "Notes" (notepad)
+/// [tiling] 50% *`), where `*` marks the focused container.
+pub fn container_label(container: &Value) -> String {
+  let summary = ContainerSummary::new(container);
+
+  let name = if summary.kind == "window" {
+    format!("\"{}\"", summary.name)
+  } else {
+    summary.name
+  };
+
+  let process = (!summary.process.is_empty())
+    .then(|| format!("({})", summary.process));
+
+  let state =
+    (!summary.state.is_empty()).then(|| format!("[{}]", summary.state));
+
+  [
+    Some(summary.kind),
+    Some(name),
+    process,
+    state,
+    Some(summary.tiling_size),
+    summary.has_focus.then(|| "*".to_string()),
+  ]
+  .into_iter()
+  .flatten()
+  .filter(|part| !part.is_empty())
+  .collect::<Vec<_>>()
+  .join(" ")
+}
+
+fn write_node(
+  node: &Node,
+  prefix: &str,
+  connector: &str,
+  lines: &mut Vec<String>,
+) {
+  lines.push(format!("{prefix}{connector}{}", node.label));
+
+  let child_prefix = match connector {
+    "├── " => format!("{prefix}│   "),
+    "└── " => format!("{prefix}    "),
+    _ => prefix.to_string(),
+  };
+
+  for (index, child) in node.children.iter().enumerate() {
+    let connector = if index + 1 == node.children.len() {
+      "└── "
+    } else {
+      "├── "
+    };
+
+    write_node(child, &child_prefix, connector, lines);
+  }
+}
@@ -0,0 +1,311 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::VecDeque, time::Instant};
+
+use ratatui::{
+  backend::TestBackend,
+  layout::{Constraint, Layout, Rect},
+  style::{Modifier, Style, Stylize},
+  text::{Line, Text},
+  widgets::{Block, Paragraph},
+  Frame, Terminal,
+};
+use serde_json::Value;
+use wm_common::{BindingModeConfig, ContainerDto, WmEvent};
+use wm_ipc_client::IpcClient;
+
+use crate::{output::is_container, tree};
+
+/// Maximum number of events kept in the event log.
+const MAX_EVENT_LOG_LENGTH: usize = 200;
+
+/// Key hints shown in the footer.
+const KEY_HINTS: &str =
+  "h/j/k/l focus · H/J/K/L move · t tiling direction \
+                         · f floating · m monocle · r refresh · q quit";
+
+/// State of the `watch` dashboard.
+pub struct Dashboard {
+  monitors: Vec<ContainerDto>,
+  binding_modes: Vec<BindingModeConfig>,
+  is_paused: bool,
+  event_log: VecDeque<String>,
+
+  /// Result of the last command, or an error from the IPC server.
+  status: Option<String>,
+
+  /// Whether the container tree is outdated and should be re-queried.
+  is_stale: bool,
+
+  started_at: Instant,
+}
+
+impl Dashboard {
+  pub fn new() -> Self {
+    Self {
+      monitors: Vec::new(),
+      binding_modes: Vec::new(),
+      is_paused: false,
+      event_log: VecDeque::new(),
+      status: None,
+      is_stale: true,
+      started_at: Instant::now(),
+    }
+  }
+
+  pub fn is_stale(&self) -> bool {
+    self.is_stale
+  }
+
+  pub fn mark_stale(&mut self) {
+    self.is_stale = true;
+  }
+
+  /// Re-queries the container tree, binding modes and paused state.
+  ///
+  /// Errors are shown in the status line, since the IPC client
+  /// reconnects in the background if the WM is restarted.
+  pub async fn refresh(&mut self, client: &IpcClient) {
+    let res = async {
+      self.monitors = client.query_monitors().await?;
+      self.binding_modes = client.query_binding_modes().await?;
+      self.is_paused = client.query_paused().await?;
+      anyhow::Ok(())
+    }
+    .await;
+
+    match res {
+      Ok(()) => self.is_stale = false,
+      Err(err) => self.status = Some(format!("Failed to refresh: {err}")),
+    }
+  }
+
+  /// Adds a WM event to the event log. The container tree is refreshed
+  /// on the next tick, so that bursts of events only cause one refresh.
+  pub fn push_event(&mut self, event: &WmEvent) {
+    let event = serde_json::to_value(event).unwrap_or_default();
+
+    let event_type = event
+      .get("eventType")
+      .and_then(Value::as_str)
+      .unwrap_or("unknown");
+
+    // Show the first container in the event (e.g. the focused container
+    // of `focus_changed`).
+    let container = event
+      .as_object()
+      .into_iter()
+      .flat_map(|map| map.values())
+      .find(|value| is_container(value));
+
+    let elapsed = self.started_at.elapsed().as_secs_f32();
+
+    let entry = match container {
+      Some(container) => format!(
+        "+{elapsed:.1}s {event_type} {}",
+        tree::container_label(container)
+      ),
+      None => format!("+{elapsed:.1}s {event_type}"),
+    };
+
+    self.event_log.push_back(entry);
+
+    if self.event_log.len() > MAX_EVENT_LOG_LENGTH {
+      self.event_log.pop_front();
+    }
+
+    self.is_stale = true;
+  }
+
+  /// Runs a WM command on the focused container.
+  pub async fn run_command(&mut self, client: &IpcClient, command: &str) {
+    self.status = Some(match client.command_str(command, None).await {
+      Ok(_) => format!("Ran '{command}'."),
+      Err(err) => format!("Failed to run '{command}': {err}"),
+    });
+  }
+
+  pub fn draw(&self, frame: &mut Frame) {
+    let [main_area, footer_area] =
+      Layout::vertical([Constraint::Min(0), Constraint::Length(1)])
+        .areas(frame.area());
+
+    let [tree_area, side_area] = Layout::horizontal([
+      Constraint::Percentage(60),
+      Constraint::Percentage(40),
+    ])
+    .areas(main_area);
+
+    #[allow(clippy::cast_possible_truncation)]
+    let binding_modes_height = self.binding_modes.len().max(1) as u16 + 2;
+
+    let [binding_modes_area, event_log_area] = Layout::vertical([
+      Constraint::Length(binding_modes_height),
+      Constraint::Min(0),
+    ])
+    .areas(side_area);
+
+    self.draw_tree(frame, tree_area);
+    self.draw_binding_modes(frame, binding_modes_area);
+    self.draw_event_log(frame, event_log_area);
+
+    let footer = match &self.status {
+      Some(status) => format!("{status} · {KEY_HINTS}"),
+      None => KEY_HINTS.to_string(),
+    };
+
+    frame.render_widget(Paragraph::new(footer).dim(), footer_area);
+  }
+
+  /// Draws the container tree, with the focused container highlighted.
+  fn draw_tree(&self, frame: &mut Frame, area: Rect) {
+    let monitors =
+      serde_json::to_value(&self.monitors).unwrap_or_default();
+
+    let lines = tree::render(&monitors)
+      .lines()
+      .map(|line| {
+        if line.ends_with(" *") {
+          Line::styled(
+            line.to_string(),
+            Style::new().yellow().add_modifier(Modifier::BOLD),
+          )
+        } else {
+          Line::raw(line.to_string())
+        }
+      })
+      .collect::<Vec<_>>();
+
+    let title = if self.is_paused {
+      " Containers (paused) "
+    } else {
+      " Containers "
+    };
+
+    frame.render_widget(
+      Paragraph::new(Text::from(lines))
+        .block(Block::bordered().title(title)),
+      area,
+    );
+  }
+
+  fn draw_binding_modes(&self, frame: &mut Frame, area: Rect) {
+    let lines = if self.binding_modes.is_empty() {
+      vec![Line::raw("none").dim()]
+    } else {
+      self
+        .binding_modes
+        .iter()
+        .map(|mode| {
+          Line::raw(mode.display_name.clone().unwrap_or(mode.name.clone()))
+        })
+        .collect()
+    };
+
+    frame.render_widget(
+      Paragraph::new(Text::from(lines))
+        .block(Block::bordered().title(" Binding modes ")),
+      area,
+    );
+  }
+
+  /// Draws the event log, scrolled to the most recent events.
+  fn draw_event_log(&self, frame: &mut Frame, area: Rect) {
+    let visible_count = usize::from(area.height.saturating_sub(2));
+
+    let lines = self
+      .event_log
+      .iter()
+      .skip(self.event_log.len().saturating_sub(visible_count))
+      .map(|entry| Line::raw(entry.as_str()))
+      .collect::<Vec<_>>();
+
+    frame.render_widget(
+      Paragraph::new(Text::from(lines))
+        .block(Block::bordered().title(" Events ")),
+      area,
+    );
+  }
+
+  /// Renders a single frame of the dashboard as plain text.
+  pub fn render_snapshot(
+    &self,
+    width: u16,
+    height: u16,
+  ) -> anyhow::Result<String> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| self.draw(frame))?;
+
+    let buffer = terminal.backend().buffer();
+
+    let lines = buffer
+      .content()
+      .chunks(usize::from(buffer.area.width))
+      .map(|row| {
+        row
+          .iter()
+          .map(ratatui::buffer::Cell::symbol)
+          .collect::<String>()
+          .trim_end()
+          .to_string()
+      })
+      .collect::<Vec<_>>();
+
+    Ok(lines.join("\n"))
+  }
+}
+
+#[cfg(test)]
+#[path = "../../examples/mock_ipc_server/server.rs"]
+mod mock_ipc_server;
+
+#[cfg(test)]
+mod tests {
+  use tokio::net::TcpListener;
+  use wm_ipc_client::{IpcClient, IpcClientOptions};
+
+  use super::{mock_ipc_server, Dashboard};
+
+  #[tokio::test]
+  async fn renders_snapshot_of_mock_server() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(mock_ipc_server::serve(listener));
+
+    let client = IpcClient::connect_with_options(IpcClientOptions {
+      port: u32::from(port),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let mut dashboard = Dashboard::new();
+    dashboard.refresh(&client).await;
+    assert!(!dashboard.is_stale());
+
+    let snapshot = dashboard.render_snapshot(120, 40).unwrap();
+
+    for expected in [" Containers ", "Notes", "Terminal", "Browser"] {
+      assert!(snapshot.contains(expected), "missing '{expected}'");
+    }
+
+    assert!(snapshot.contains("resize"));
+    assert!(!snapshot.contains("Failed to refresh"));
+  }
+}
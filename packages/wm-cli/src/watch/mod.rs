@@ -0,0 +1,145 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+mod dashboard;
+
+use std::time::Duration;
+
+use crossterm::event::{
+  Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
+use futures_util::{Stream, StreamExt};
+use ratatui::DefaultTerminal;
+use wm_common::{SubscribableEvent, WmEvent};
+use wm_ipc_client::{IpcClient, IpcClientOptions};
+
+use self::dashboard::Dashboard;
+
+/// How often the container tree is re-queried after WM events.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Size of the frame output by `watch --snapshot`.
+const SNAPSHOT_SIZE: (u16, u16) = (120, 40);
+
+enum KeyAction {
+  Command(&'static str),
+  Refresh,
+  Quit,
+}
+
+/// Runs the `watch` dashboard until quit.
+pub async fn watch(port: u32, snapshot: bool) -> anyhow::Result<()> {
+  let client = IpcClient::connect_with_options(IpcClientOptions {
+    port,
+    ..Default::default()
+  })
+  .await?;
+
+  let mut dashboard = Dashboard::new();
+  dashboard.refresh(&client).await;
+
+  if snapshot {
+    let (width, height) = SNAPSHOT_SIZE;
+    println!("{}", dashboard.render_snapshot(width, height)?);
+    return Ok(());
+  }
+
+  let events = client.subscribe(&[SubscribableEvent::All]).await?;
+
+  let mut terminal = ratatui::init();
+  let res = run(&mut terminal, &client, dashboard, events).await;
+  ratatui::restore();
+
+  res
+}
+
+async fn run(
+  terminal: &mut DefaultTerminal,
+  client: &IpcClient,
+  mut dashboard: Dashboard,
+  events: impl Stream<Item = WmEvent>,
+) -> anyhow::Result<()> {
+  let mut events = Box::pin(events);
+  let mut input = EventStream::new();
+  let mut refresh_interval = tokio::time::interval(REFRESH_INTERVAL);
+
+  loop {
+    terminal.draw(|frame| dashboard.draw(frame))?;
+
+    tokio::select! {
+      Some(event) = events.next() => dashboard.push_event(&event),
+      Some(input_event) = input.next() => {
+        let Event::Key(key) = input_event? else {
+          continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+          continue;
+        }
+
+        match key_action(key) {
+          Some(KeyAction::Command(command)) => {
+            dashboard.run_command(client, command).await;
+          }
+          Some(KeyAction::Refresh) => dashboard.mark_stale(),
+          Some(KeyAction::Quit) => return Ok(()),
+          None => {}
+        }
+      }
+      _ = refresh_interval.tick() => {
+        if dashboard.is_stale() {
+          dashboard.refresh(client).await;
+        }
+      }
+    }
+  }
+}
+
+fn key_action(key: KeyEvent) -> Option<KeyAction> {
+  let action = match key.code {
+    KeyCode::Char('h') | KeyCode::Left => {
+      KeyAction::Command("focus --direction left")
+    }
+    KeyCode::Char('j') | KeyCode::Down => {
+      KeyAction::Command("focus --direction down")
+    }
+    KeyCode::Char('k') | KeyCode::Up => {
+      KeyAction::Command("focus --direction up")
+    }
+    KeyCode::Char('l') | KeyCode::Right => {
+      KeyAction::Command("focus --direction right")
+    }
+    KeyCode::Char('H') => KeyAction::Command("move --direction left"),
+    KeyCode::Char('J') => KeyAction::Command("move --direction down"),
+    KeyCode::Char('K') => KeyAction::Command("move --direction up"),
+    KeyCode::Char('L') => KeyAction::Command("move --direction right"),
+    KeyCode::Char('t') => KeyAction::Command("toggle-tiling-direction"),
+    KeyCode::Char('f') => KeyAction::Command("toggle-floating"),
+    KeyCode::Char('m') => KeyAction::Command("toggle-monocle"),
+    KeyCode::Char('r') => KeyAction::Refresh,
+    KeyCode::Char('c')
+      if key.modifiers.contains(KeyModifiers::CONTROL) =>
+    {
+      KeyAction::Quit
+    }
+    KeyCode::Char('q') | KeyCode::Esc => KeyAction::Quit,
+    _ => return None,
+  };
+
+  Some(action)
+}
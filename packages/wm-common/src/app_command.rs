@@ -16,19 +16,35 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{iter, path::PathBuf};
+use std::{fmt, iter, path::PathBuf};
 
-use clap::{error::KindFormatter, Args, Parser, ValueEnum};
+use clap::{
+  error::KindFormatter, Args, CommandFactory, Parser, ValueEnum,
+};
+use schemars::{
+  gen::SchemaGenerator,
+  schema::{
+    InstanceType, Metadata, Schema, SchemaObject, StringValidation,
+  },
+  JsonSchema,
+};
 use serde::{Deserialize, Deserializer, Serialize};
 use tracing::Level;
 use uuid::Uuid;
 
 use crate::{
-  Delta, Direction, LengthValue, OpacityValue, TilingDirection,
+  Color, CommandSchema, CornerStyle, Delta, Direction, GridCell, GridSize,
+  LengthValue, OpacityValue, SnapPosition, TilingDirection,
+  DEFAULT_IPC_PORT,
 };
 
 const VERSION: &str = env!("VERSION_NUMBER");
 
+/// Environment variable that is set when the WM is started by a
+/// supervising watcher, in which case the WM doesn't start a watcher of
+/// its own.
+pub const SUPERVISED_ENV_VAR: &str = "LAVAWM_SUPERVISED";
+
 #[derive(Clone, Debug, Parser)]
 #[clap(name = "lavawm", author, version = VERSION, about, long_about = None)]
 pub enum AppCommand {
@@ -40,6 +56,44 @@ pub enum AppCommand {
     #[clap(short = 'c', long = "config", value_hint = clap::ValueHint::FilePath)]
     config_path: Option<PathBuf>,
 
+    /// Records platform events, IPC messages and WM events to the given
+    /// file, which can be replayed with `lavawm replay`.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    record: Option<PathBuf>,
+
+    /// Moves windows back to the workspaces and states of a layout
+    /// snapshot (i.e. the output of `query monitors`) after startup.
+    ///
+    /// Used by the watcher when restarting the WM after a crash.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    restore_layout: Option<PathBuf>,
+
+    /// Adopts the windows and state of a previous instance from a handoff
+    /// file written by `wm-restart`.
+    #[clap(long, hide = true, value_hint = clap::ValueHint::FilePath)]
+    handoff: Option<PathBuf>,
+
+    #[clap(flatten)]
+    verbosity: Verbosity,
+  },
+
+  /// Replays a recording from `lavawm start --record` against a simulated
+  /// platform, and outputs how the resulting container tree differs from
+  /// the recorded one.
+  Replay {
+    /// Path to the recording.
+    #[clap(value_hint = clap::ValueHint::FilePath)]
+    path: PathBuf,
+
+    /// Custom path to a user config file to replay with, instead of the
+    /// recorded config.
+    #[clap(short = 'c', long = "config", value_hint = clap::ValueHint::FilePath)]
+    config_path: Option<PathBuf>,
+
+    /// Waits between events as long as when they were recorded.
+    #[clap(long)]
+    realtime: bool,
+
     #[clap(flatten)]
     verbosity: Verbosity,
   },
@@ -51,6 +105,9 @@ pub enum AppCommand {
   Query {
     #[clap(subcommand)]
     command: QueryCommand,
+
+    #[clap(flatten)]
+    output: OutputArgs,
   },
 
   /// Invokes a window manager command.
@@ -63,6 +120,9 @@ pub enum AppCommand {
 
     #[clap(subcommand)]
     command: InvokeCommand,
+
+    #[clap(flatten)]
+    output: OutputArgs,
   },
 
   /// Subscribes to one or more WM events (e.g. `window_close`), and
@@ -73,6 +133,9 @@ pub enum AppCommand {
     /// WM event(s) to subscribe to.
     #[clap(short = 'e', long, value_enum, num_args = 1..)]
     events: Vec<SubscribableEvent>,
+
+    #[clap(flatten)]
+    output: OutputArgs,
   },
 
   /// Unsubscribes from a prior event subscription.
@@ -82,6 +145,35 @@ pub enum AppCommand {
     /// Subscription ID to unsubscribe from.
     #[clap(long = "id")]
     subscription_id: Uuid,
+
+    #[clap(flatten)]
+    output: OutputArgs,
+  },
+
+  /// Outputs a shell completion script for the CLI.
+  ///
+  /// For example, `lavawm-cli completions powershell | Out-String |
+  /// Invoke-Expression` enables completions in the current PowerShell
+  /// session.
+  Completions {
+    /// Shell to generate completions for.
+    #[clap(value_enum)]
+    shell: CompletionShell,
+  },
+
+  /// Shows a live dashboard of the container tree, WM events and active
+  /// binding modes, with keys to send focus and move commands.
+  ///
+  /// Requires an already running instance of the window manager.
+  Watch {
+    /// Port of the IPC server to connect to.
+    #[clap(long, default_value_t = DEFAULT_IPC_PORT)]
+    port: u32,
+
+    /// Outputs a single frame of the dashboard and exits, without
+    /// requiring an interactive terminal.
+    #[clap(long)]
+    snapshot: bool,
   },
 }
 
@@ -94,6 +186,9 @@ impl AppCommand {
     if args.len() == 1 {
       AppCommand::Start {
         config_path: None,
+        record: None,
+        restore_layout: None,
+        handoff: None,
         verbosity: Verbosity {
           verbose: false,
           quiet: false,
@@ -103,6 +198,30 @@ impl AppCommand {
       AppCommand::parse_from(args)
     }
   }
+
+  /// Gets the schema of all commands and their arguments.
+  #[must_use]
+  pub fn schema() -> CommandSchema {
+    let mut command = AppCommand::command();
+    command.build();
+
+    CommandSchema::from_command(&command)
+  }
+
+  /// Gets the output flags of commands that are sent to the IPC server.
+  #[must_use]
+  pub fn output(&self) -> Option<&OutputArgs> {
+    match self {
+      AppCommand::Query { output, .. }
+      | AppCommand::Command { output, .. }
+      | AppCommand::Sub { output, .. }
+      | AppCommand::Unsub { output, .. } => Some(output),
+      AppCommand::Start { .. }
+      | AppCommand::Replay { .. }
+      | AppCommand::Completions { .. }
+      | AppCommand::Watch { .. } => None,
+    }
+  }
 }
 
 /// Verbosity flags to be used with `#[command(flatten)]`.
@@ -130,6 +249,46 @@ impl Verbosity {
   }
 }
 
+/// Output flags to be used with `#[command(flatten)]`.
+#[derive(Args, Clone, Debug, Default)]
+#[clap(about = None, long_about = None)]
+pub struct OutputArgs {
+  /// Format to output the response in.
+  #[clap(long, value_enum, global = true, default_value_t)]
+  pub format: OutputFormat,
+
+  /// Outputs a single field of the response data (e.g. `title` or
+  /// `workspaces[0].name`).
+  ///
+  /// Fields of list items are collected into a list.
+  #[clap(long, global = true)]
+  pub field: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+#[clap(rename_all = "snake_case")]
+pub enum CompletionShell {
+  Bash,
+  Fish,
+  Powershell,
+  Zsh,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+#[clap(rename_all = "snake_case")]
+pub enum OutputFormat {
+  /// Compact JSON of the full response.
+  #[default]
+  Json,
+  /// Indented JSON of the full response.
+  Pretty,
+  /// Table of the response data, with a row per list item.
+  Table,
+  /// Tree of the response data, with containers shown by their
+  /// hierarchy (e.g. monitors, workspaces, splits and windows).
+  Tree,
+}
+
 #[derive(Clone, Debug, Parser)]
 pub enum QueryCommand {
   /// Outputs metadata about the application (e.g. version number).
@@ -145,10 +304,18 @@ pub enum QueryCommand {
   Monitors,
   /// Outputs all windows.
   Windows,
+  /// Outputs all windows, from most to least recently focused.
+  FocusHistory,
   /// Outputs all active workspaces.
   Workspaces,
   /// Outputs whether the window manager is paused.
   Paused,
+  /// Outputs a schema of all commands and their arguments.
+  Commands,
+  /// Outputs a JSON Schema of the user config.
+  ConfigSchema,
+  /// Outputs counters and latency histograms of the event loop.
+  Metrics,
 }
 
 #[derive(Clone, Debug, PartialEq, ValueEnum)]
@@ -166,6 +333,7 @@ pub enum SubscribableEvent {
   UserConfigChanged,
   WindowManaged,
   WindowUnmanaged,
+  WindowUrgencyChanged,
   WorkspaceActivated,
   WorkspaceDeactivated,
   WorkspaceUpdated,
@@ -175,6 +343,7 @@ pub enum SubscribableEvent {
 #[derive(Clone, Debug, Parser, PartialEq, Serialize)]
 pub enum InvokeCommand {
   AdjustBorders(InvokeAdjustBordersCommand),
+  ArrangeFloating(InvokeArrangeFloatingCommand),
   Close,
   Focus(InvokeFocusCommand),
   Ignore,
@@ -185,6 +354,7 @@ pub enum InvokeCommand {
   },
   Position(InvokePositionCommand),
   Resize(InvokeResizeCommand),
+  SetEffects(InvokeSetEffectsCommand),
   SetFloating {
     #[clap(long, default_missing_value = "true", require_equals = true, num_args = 0..=1)]
     shown_on_top: Option<bool>,
@@ -212,6 +382,14 @@ pub enum InvokeCommand {
     maximized: Option<bool>,
   },
   SetMinimized,
+  SetMonocle,
+  SetSizeConstraints(InvokeSizeConstraintsCommand),
+  SetSticky {
+    /// Follow the focused workspace across all monitors, instead of only
+    /// the displayed workspace of the window's monitor.
+    #[clap(long)]
+    all_monitors: bool,
+  },
   SetTiling,
   SetTitleBarVisibility {
     #[clap(required = true, value_enum)]
@@ -242,6 +420,13 @@ pub enum InvokeCommand {
     maximized: Option<bool>,
   },
   ToggleMinimized,
+  ToggleMonocle,
+  ToggleSticky {
+    /// Follow the focused workspace across all monitors, instead of only
+    /// the displayed workspace of the window's monitor.
+    #[clap(long)]
+    all_monitors: bool,
+  },
   ToggleTiling,
   ToggleTilingDirection,
   SetTilingDirection {
@@ -265,6 +450,14 @@ pub enum InvokeCommand {
     #[clap(long)]
     name: String,
   },
+  /// Writes the container tree, config, monitors and recent logs to a
+  /// `.tar.gz` file for bug reports.
+  WmDumpDiagnostics {
+    /// Path of the file to write. Defaults to a timestamped file next to
+    /// the user config.
+    #[clap(long, value_hint = clap::ValueHint::FilePath)]
+    path: Option<PathBuf>,
+  },
   WmEnableBindingMode {
     #[clap(long)]
     name: String,
@@ -276,7 +469,12 @@ pub enum InvokeCommand {
   WmExit,
   WmRedraw,
   WmReloadConfig,
+  /// Restarts the WM in place, e.g. after upgrading it. Windows stay
+  /// where they are, and the new instance takes over the container tree,
+  /// binding modes and pause state.
+  WmRestart,
   WmTogglePause,
+  Workspace(InvokeWorkspaceCommand),
 }
 
 impl<'de> Deserialize<'de> for InvokeCommand {
@@ -298,7 +496,320 @@ impl<'de> Deserialize<'de> for InvokeCommand {
   }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+/// Commands are deserialized from strings, so the schema only checks
+/// the command name.
+impl JsonSchema for InvokeCommand {
+  fn schema_name() -> String {
+    "InvokeCommand".to_string()
+  }
+
+  fn json_schema(_: &mut SchemaGenerator) -> Schema {
+    let command = InvokeCommand::command();
+
+    let command_names = command
+      .get_subcommands()
+      .flat_map(|command| {
+        iter::once(command.get_name()).chain(command.get_all_aliases())
+      })
+      .collect::<Vec<_>>();
+
+    SchemaObject {
+      instance_type: Some(InstanceType::String.into()),
+      metadata: Some(Box::new(Metadata {
+        description: Some(
+          "WM command (e.g. `focus --direction left`).".to_string(),
+        ),
+        ..Default::default()
+      })),
+      string: Some(Box::new(StringValidation {
+        pattern: Some(format!("^({})( |$)", command_names.join("|"))),
+        ..Default::default()
+      })),
+      ..Default::default()
+    }
+    .into()
+  }
+}
+
+/// Formats the command as a string that can be parsed back (e.g.
+/// `focus --direction left`), which is the form the IPC server accepts.
+impl fmt::Display for InvokeCommand {
+  #[allow(clippy::too_many_lines)]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let args = match self {
+      InvokeCommand::AdjustBorders(args) => {
+        CommandArgs::new("adjust-borders")
+          .value("top", args.top.as_ref())
+          .value("right", args.right.as_ref())
+          .value("bottom", args.bottom.as_ref())
+          .value("left", args.left.as_ref())
+      }
+      InvokeCommand::ArrangeFloating(args) => {
+        CommandArgs::new("arrange-floating")
+          .flag("cascade", args.cascade)
+          .flag("tile", args.tile)
+      }
+      InvokeCommand::Close => CommandArgs::new("close"),
+      InvokeCommand::Focus(args) => CommandArgs::new("focus")
+        .value("direction", args.direction.as_ref())
+        .value("container-id", args.container_id.as_ref())
+        .value(
+          "workspace-in-direction",
+          args.workspace_in_direction.as_ref(),
+        )
+        .value("workspace", args.workspace.as_ref())
+        .value("monitor", args.monitor.as_ref())
+        .flag("next-active-workspace", args.next_active_workspace)
+        .flag("prev-active-workspace", args.prev_active_workspace)
+        .flag("next-workspace", args.next_workspace)
+        .flag("prev-workspace", args.prev_workspace)
+        .flag(
+          "next-active-workspace-on-monitor",
+          args.next_active_workspace_on_monitor,
+        )
+        .flag(
+          "prev-active-workspace-on-monitor",
+          args.prev_active_workspace_on_monitor,
+        )
+        .flag("recent-workspace", args.recent_workspace)
+        .flag("back", args.back)
+        .flag("forward", args.forward)
+        .flag("cycle-recent", args.cycle_recent)
+        .flag("urgent", args.urgent),
+      InvokeCommand::Ignore => CommandArgs::new("ignore"),
+      InvokeCommand::Move(args) => CommandArgs::new("move")
+        .value("direction", args.direction.as_ref())
+        .value(
+          "workspace-in-direction",
+          args.workspace_in_direction.as_ref(),
+        )
+        .value("workspace", args.workspace.as_ref())
+        .flag("next-active-workspace", args.next_active_workspace)
+        .flag("prev-active-workspace", args.prev_active_workspace)
+        .flag("next-workspace", args.next_workspace)
+        .flag("prev-workspace", args.prev_workspace)
+        .flag(
+          "next-active-workspace-on-monitor",
+          args.next_active_workspace_on_monitor,
+        )
+        .flag(
+          "prev-active-workspace-on-monitor",
+          args.prev_active_workspace_on_monitor,
+        )
+        .flag("recent-workspace", args.recent_workspace),
+      InvokeCommand::MoveWorkspace { direction } => {
+        CommandArgs::new("move-workspace")
+          .value("direction", Some(direction))
+      }
+      InvokeCommand::Position(args) => CommandArgs::new("position")
+        .flag("centered", args.centered)
+        .value("x-pos", args.x_pos.as_ref())
+        .value("y-pos", args.y_pos.as_ref())
+        .value("snap", args.snap.as_ref().map(value_enum_name))
+        .value("grid", args.grid.as_ref())
+        .value("cell", args.cell.as_ref())
+        .value("span", args.span.as_ref()),
+      InvokeCommand::Resize(args) => CommandArgs::new("resize")
+        .value("width", args.width.as_ref())
+        .value("height", args.height.as_ref()),
+      InvokeCommand::SetEffects(args) => CommandArgs::new("set-effects")
+        .value("border-color", args.border_color.as_ref())
+        .value(
+          "corner-style",
+          args.corner_style.as_ref().map(value_enum_name),
+        )
+        .value("opacity", args.opacity.as_ref())
+        .value("title-bar", args.title_bar.as_ref().map(value_enum_name))
+        .flag("reset", args.reset),
+      InvokeCommand::SetFloating {
+        shown_on_top,
+        centered,
+        x_pos,
+        y_pos,
+        width,
+        height,
+      } => CommandArgs::new("set-floating")
+        .optional_flag("shown-on-top", *shown_on_top)
+        .optional_flag("centered", *centered)
+        .value("x-pos", x_pos.as_ref())
+        .value("y-pos", y_pos.as_ref())
+        .value("width", width.as_ref())
+        .value("height", height.as_ref()),
+      InvokeCommand::SetFullscreen {
+        shown_on_top,
+        maximized,
+      } => CommandArgs::new("set-fullscreen")
+        .optional_flag("shown-on-top", *shown_on_top)
+        .optional_flag("maximized", *maximized),
+      InvokeCommand::SetMinimized => CommandArgs::new("set-minimized"),
+      InvokeCommand::SetMonocle => CommandArgs::new("set-monocle"),
+      InvokeCommand::SetSizeConstraints(args) => {
+        CommandArgs::new("set-size-constraints")
+          .value("min-width", args.min_width.as_ref())
+          .value("min-height", args.min_height.as_ref())
+          .value("max-width", args.max_width.as_ref())
+          .value("max-height", args.max_height.as_ref())
+      }
+      InvokeCommand::SetSticky { all_monitors } => {
+        CommandArgs::new("set-sticky").flag("all-monitors", *all_monitors)
+      }
+      InvokeCommand::SetTiling => CommandArgs::new("set-tiling"),
+      InvokeCommand::SetTitleBarVisibility { visibility } => {
+        CommandArgs::new("set-title-bar-visibility")
+          .positional(value_enum_name(visibility))
+      }
+      InvokeCommand::SetTransparency(args) => {
+        CommandArgs::new("set-transparency")
+          .value("opacity", args.opacity.as_ref())
+          .value("opacity-delta", args.opacity_delta.as_ref())
+      }
+      InvokeCommand::ShellExec {
+        hide_window,
+        command,
+      } => command.iter().fold(
+        CommandArgs::new("shell-exec").flag("hide-window", *hide_window),
+        CommandArgs::positional,
+      ),
+      InvokeCommand::Size(args) => CommandArgs::new("size")
+        .value("width", args.width.as_ref())
+        .value("height", args.height.as_ref()),
+      InvokeCommand::ToggleFloating {
+        shown_on_top,
+        centered,
+      } => CommandArgs::new("toggle-floating")
+        .optional_flag("shown-on-top", *shown_on_top)
+        .optional_flag("centered", *centered),
+      InvokeCommand::ToggleFullscreen {
+        shown_on_top,
+        maximized,
+      } => CommandArgs::new("toggle-fullscreen")
+        .optional_flag("shown-on-top", *shown_on_top)
+        .optional_flag("maximized", *maximized),
+      InvokeCommand::ToggleMinimized => {
+        CommandArgs::new("toggle-minimized")
+      }
+      InvokeCommand::ToggleMonocle => CommandArgs::new("toggle-monocle"),
+      InvokeCommand::ToggleSticky { all_monitors } => {
+        CommandArgs::new("toggle-sticky")
+          .flag("all-monitors", *all_monitors)
+      }
+      InvokeCommand::ToggleTiling => CommandArgs::new("toggle-tiling"),
+      InvokeCommand::ToggleTilingDirection => {
+        CommandArgs::new("toggle-tiling-direction")
+      }
+      InvokeCommand::SetTilingDirection { tiling_direction } => {
+        CommandArgs::new("set-tiling-direction")
+          .positional(tiling_direction)
+      }
+      InvokeCommand::WmCycleFocus {
+        omit_floating,
+        omit_fullscreen,
+        omit_minimized,
+        omit_tiling,
+      } => CommandArgs::new("wm-cycle-focus")
+        .flag("omit-floating", *omit_floating)
+        .flag("omit-fullscreen", *omit_fullscreen)
+        .flag("omit-minimized", *omit_minimized)
+        .flag("omit-tiling", *omit_tiling),
+      InvokeCommand::WmDisableBindingMode { name } => {
+        CommandArgs::new("wm-disable-binding-mode")
+          .value("name", Some(name))
+      }
+      InvokeCommand::WmDumpDiagnostics { path } => {
+        CommandArgs::new("wm-dump-diagnostics")
+          .value("path", path.as_ref().map(|path| path.display()))
+      }
+      InvokeCommand::WmEnableBindingMode { name } => {
+        CommandArgs::new("wm-enable-binding-mode")
+          .value("name", Some(name))
+      }
+      InvokeCommand::WmCleanupWindows => {
+        CommandArgs::new("wm-cleanup-windows")
+      }
+      InvokeCommand::WmInjectGhost => CommandArgs::new("wm-inject-ghost"),
+      InvokeCommand::WmExit => CommandArgs::new("wm-exit"),
+      InvokeCommand::WmRedraw => CommandArgs::new("wm-redraw"),
+      InvokeCommand::WmReloadConfig => {
+        CommandArgs::new("wm-reload-config")
+      }
+      InvokeCommand::WmRestart => CommandArgs::new("wm-restart"),
+      InvokeCommand::WmTogglePause => CommandArgs::new("wm-toggle-pause"),
+      InvokeCommand::Workspace(args) => {
+        let command_args = CommandArgs::new("workspace")
+          .value("create", args.create.as_ref())
+          .value("rename", args.rename.as_ref())
+          .value("reorder", args.reorder.as_ref());
+
+        // The workspace name is optional when deleting.
+        match &args.delete {
+          Some(Some(name)) => command_args.value("delete", Some(name)),
+          Some(None) => command_args.flag("delete", true),
+          None => command_args,
+        }
+      }
+    };
+
+    f.write_str(&args.0.join(" "))
+  }
+}
+
+/// Arguments of a command, in the form that clap parses them.
+struct CommandArgs(Vec<String>);
+
+impl CommandArgs {
+  fn new(name: &str) -> Self {
+    Self(vec![name.to_string()])
+  }
+
+  /// Adds a flag (e.g. `--centered`) if it's set.
+  fn flag(mut self, name: &str, is_set: bool) -> Self {
+    if is_set {
+      self.0.push(format!("--{name}"));
+    }
+
+    self
+  }
+
+  /// Adds a flag with an optional value (e.g. `--centered=false`) if
+  /// it's set.
+  fn optional_flag(mut self, name: &str, value: Option<bool>) -> Self {
+    if let Some(value) = value {
+      self.0.push(format!("--{name}={value}"));
+    }
+
+    self
+  }
+
+  /// Adds an option and its value (e.g. `--direction left`) if the value
+  /// is set.
+  fn value(
+    mut self,
+    name: &str,
+    value: Option<impl fmt::Display>,
+  ) -> Self {
+    if let Some(value) = value {
+      self.0.push(format!("--{name}"));
+      self.0.push(value.to_string());
+    }
+
+    self
+  }
+
+  fn positional(mut self, value: impl fmt::Display) -> Self {
+    self.0.push(value.to_string());
+    self
+  }
+}
+
+/// Gets the name of a value enum as it's parsed from the command line.
+fn value_enum_name(value: &impl ValueEnum) -> String {
+  value
+    .to_possible_value()
+    .map(|value| value.get_name().to_string())
+    .unwrap_or_default()
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, ValueEnum)]
 #[clap(rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum TitleBarVisibility {
@@ -361,6 +872,23 @@ pub struct InvokeFocusCommand {
 
   #[clap(long)]
   pub recent_workspace: bool,
+
+  /// Focus the previous window in the focus history.
+  #[clap(long)]
+  pub back: bool,
+
+  /// Focus the next window in the focus history.
+  #[clap(long)]
+  pub forward: bool,
+
+  /// Cycle through recently focused windows. The cycle ends once all
+  /// modifier keys are released.
+  #[clap(long)]
+  pub cycle_recent: bool,
+
+  /// Focus the window that has been requesting attention the longest.
+  #[clap(long)]
+  pub urgent: bool,
 }
 
 #[derive(Args, Clone, Debug, PartialEq, Serialize)]
@@ -401,6 +929,29 @@ pub struct InvokeMoveCommand {
   pub recent_workspace: bool,
 }
 
+#[derive(Args, Clone, Debug, PartialEq, Serialize)]
+#[group(required = true, multiple = false)]
+pub struct InvokeWorkspaceCommand {
+  /// Name of workspace to create and focus.
+  #[clap(long)]
+  pub create: Option<String>,
+
+  /// New name for the focused workspace.
+  #[clap(long)]
+  pub rename: Option<String>,
+
+  /// Number of positions to move the focused workspace by, relative to
+  /// the other workspaces on its monitor (e.g. `-1` to move it before
+  /// the previous workspace).
+  #[clap(long, allow_hyphen_values = true)]
+  pub reorder: Option<i32>,
+
+  /// Name of empty workspace to delete. Defaults to the focused
+  /// workspace if no name is given.
+  #[clap(long, num_args = 0..=1)]
+  pub delete: Option<Option<String>>,
+}
+
 #[derive(Args, Clone, Debug, PartialEq, Serialize)]
 #[group(required = true, multiple = true)]
 pub struct InvokeResizeCommand {
@@ -421,6 +972,27 @@ pub struct SetTransparencyCommand {
   pub opacity_delta: Option<Delta<OpacityValue>>,
 }
 
+#[derive(Args, Clone, Debug, PartialEq, Serialize)]
+#[group(required = true, multiple = true)]
+pub struct InvokeSetEffectsCommand {
+  #[clap(long)]
+  pub border_color: Option<Color>,
+
+  #[clap(long, value_enum)]
+  pub corner_style: Option<CornerStyle>,
+
+  #[clap(long)]
+  pub opacity: Option<OpacityValue>,
+
+  #[clap(long, value_enum)]
+  pub title_bar: Option<TitleBarVisibility>,
+
+  /// Remove all effect overrides of the window, so that the window
+  /// effects from the user config apply again.
+  #[clap(long, action)]
+  pub reset: bool,
+}
+
 #[derive(Args, Clone, Debug, PartialEq, Serialize)]
 #[group(required = true, multiple = true)]
 pub struct InvokePositionCommand {
@@ -432,4 +1004,90 @@ pub struct InvokePositionCommand {
 
   #[clap(long, allow_hyphen_values = true)]
   pub y_pos: Option<i32>,
+
+  #[clap(long)]
+  pub snap: Option<SnapPosition>,
+
+  #[clap(long)]
+  pub grid: Option<GridSize>,
+
+  #[clap(long, requires = "grid")]
+  pub cell: Option<GridCell>,
+
+  #[clap(long, requires = "grid")]
+  pub span: Option<GridCell>,
+}
+
+#[derive(Args, Clone, Debug, PartialEq, Serialize)]
+#[group(required = true, multiple = true)]
+pub struct InvokeSizeConstraintsCommand {
+  #[clap(long)]
+  pub min_width: Option<i32>,
+
+  #[clap(long)]
+  pub min_height: Option<i32>,
+
+  #[clap(long)]
+  pub max_width: Option<i32>,
+
+  #[clap(long)]
+  pub max_height: Option<i32>,
+}
+
+#[derive(Args, Clone, Debug, PartialEq, Serialize)]
+#[group(required = true, multiple = false)]
+pub struct InvokeArrangeFloatingCommand {
+  #[clap(long, action)]
+  pub cascade: bool,
+
+  #[clap(long, action)]
+  pub tile: bool,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(command: &str) -> InvokeCommand {
+    InvokeCommand::try_parse_from(
+      iter::once("").chain(command.split_whitespace()),
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn formats_commands_that_parse_back() {
+    let commands = [
+      "adjust-borders --top 10px --left -5%",
+      "close",
+      "focus --direction left",
+      "focus --workspace 1",
+      "focus --next-active-workspace-on-monitor",
+      "move --workspace-in-direction right",
+      "move-workspace --direction up",
+      "position --grid 3x2 --cell 0,1 --span 2,1",
+      "position --snap left-two-thirds",
+      "position --centered --x-pos -20",
+      "resize --width +10% --height -20px",
+      "set-effects --border-color #ff0000 --corner-style rounded",
+      "set-floating --shown-on-top=false --width 50%",
+      "set-fullscreen --maximized",
+      "set-size-constraints --min-width 200 --max-height 800",
+      "set-sticky --all-monitors",
+      "set-title-bar-visibility hidden",
+      "set-transparency --opacity-delta -0.1",
+      "shell-exec --hide-window code --new-window",
+      "set-tiling-direction vertical",
+      "wm-cycle-focus --omit-floating",
+      "wm-enable-binding-mode --name resize",
+      "workspace --reorder -1",
+      "workspace --delete",
+      "workspace --delete 2",
+    ];
+
+    for command in commands {
+      let parsed = parse(command);
+      assert_eq!(parse(&parsed.to_string()), parsed, "{command}");
+    }
+  }
 }
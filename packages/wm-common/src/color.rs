@@ -1,9 +1,28 @@
-use std::str::FromStr;
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
 
 use anyhow::bail;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Color {
   pub r: u8,
   pub g: u8,
@@ -44,19 +63,41 @@ impl FromStr for Color {
   }
 }
 
+impl fmt::Display for Color {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "#{:02x}{:02x}{:02x}{:02x}",
+      self.r, self.g, self.b, self.a
+    )
+  }
+}
+
+/// Forms that a `Color` can be deserialized from.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(description = "Hex color (e.g. `#ff0000` or `#ff000080`).")]
+enum ColorDe {
+  Struct {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+  },
+  /// Hex value with an optional alpha channel (e.g. `#ff0000` or
+  /// `#ff000080`).
+  String(
+    #[schemars(regex(pattern = r"^#([0-9a-fA-F]{6}|[0-9a-fA-F]{8})$"))]
+    String,
+  ),
+}
+
 /// Deserialize a `Color` from either a string or a struct.
 impl<'de> Deserialize<'de> for Color {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
     D: Deserializer<'de>,
   {
-    #[derive(Deserialize)]
-    #[serde(untagged)]
-    enum ColorDe {
-      Struct { r: u8, g: u8, b: u8, a: u8 },
-      String(String),
-    }
-
     match ColorDe::deserialize(deserializer)? {
       ColorDe::Struct { r, g, b, a } => Ok(Self { r, g, b, a }),
       ColorDe::String(str) => {
@@ -65,3 +106,13 @@ impl<'de> Deserialize<'de> for Color {
     }
   }
 }
+
+impl JsonSchema for Color {
+  fn schema_name() -> String {
+    "Color".to_string()
+  }
+
+  fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+    ColorDe::json_schema(generator)
+  }
+}
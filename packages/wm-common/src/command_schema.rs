@@ -0,0 +1,99 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use clap::Command;
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable description of a command and its arguments, generated
+/// from its clap definition.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandSchema {
+  pub name: String,
+  pub aliases: Vec<String>,
+  pub description: Option<String>,
+  pub args: Vec<CommandArgSchema>,
+  pub subcommands: Vec<CommandSchema>,
+}
+
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandArgSchema {
+  pub name: String,
+  pub long: Option<String>,
+  pub short: Option<char>,
+  pub description: Option<String>,
+  pub is_required: bool,
+  pub is_positional: bool,
+  pub takes_value: bool,
+  pub is_multiple: bool,
+
+  /// Allowed values of the argument. Empty if any value is allowed.
+  pub possible_values: Vec<String>,
+}
+
+impl CommandSchema {
+  /// Gets the schema of a built clap command.
+  ///
+  /// Hidden arguments and subcommands, as well as the help and version
+  /// flags, are excluded.
+  #[must_use]
+  pub fn from_command(command: &Command) -> Self {
+    Self {
+      name: command.get_name().to_string(),
+      aliases: command
+        .get_all_aliases()
+        .map(ToString::to_string)
+        .collect(),
+      description: command.get_about().map(ToString::to_string),
+      args: command
+        .get_arguments()
+        .filter(|arg| {
+          !arg.is_hide_set()
+            && !matches!(arg.get_id().as_str(), "help" | "version")
+        })
+        .map(|arg| CommandArgSchema {
+          name: arg.get_id().to_string(),
+          long: arg.get_long().map(ToString::to_string),
+          short: arg.get_short(),
+          description: arg.get_help().map(ToString::to_string),
+          is_required: arg.is_required_set(),
+          is_positional: arg.is_positional(),
+          takes_value: arg.get_action().takes_values(),
+          is_multiple: arg
+            .get_num_args()
+            .is_some_and(|num_args| num_args.max_values() > 1),
+          possible_values: arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect(),
+        })
+        .collect(),
+      subcommands: command
+        .get_subcommands()
+        .filter(|subcommand| {
+          !subcommand.is_hide_set() && subcommand.get_name() != "help"
+        })
+        .map(Self::from_command)
+        .collect(),
+    }
+  }
+}
@@ -1,4 +1,22 @@
-use std::str::FromStr;
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
 
 use anyhow::bail;
 use serde::Serialize;
@@ -33,3 +51,10 @@ impl<T: FromStr<Err = anyhow::Error>> FromStr for Delta<T> {
     Ok(Self { inner, is_negative })
   }
 }
+
+impl<T: fmt::Display> fmt::Display for Delta<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let sign = if self.is_negative { '-' } else { '+' };
+    write!(f, "{sign}{}", self.inner)
+  }
+}
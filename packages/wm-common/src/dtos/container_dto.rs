@@ -1,4 +1,23 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use super::{
   MonitorDto, RootContainerDto, SplitContainerDto, WindowDto, WorkspaceDto,
@@ -16,3 +35,17 @@ pub enum ContainerDto {
   Split(SplitContainerDto),
   Window(WindowDto),
 }
+
+impl ContainerDto {
+  /// Gets the ID of the container.
+  #[must_use]
+  pub fn id(&self) -> Uuid {
+    match self {
+      ContainerDto::Root(root) => root.id,
+      ContainerDto::Monitor(monitor) => monitor.id,
+      ContainerDto::Workspace(workspace) => workspace.id,
+      ContainerDto::Split(split) => split.id,
+      ContainerDto::Window(window) => window.id,
+    }
+  }
+}
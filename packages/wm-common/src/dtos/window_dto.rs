@@ -1,7 +1,28 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{ActiveDrag, DisplayState, Rect, RectDelta, WindowState};
+use crate::{
+  ActiveDrag, DisplayState, Rect, RectDelta, SizeConstraints, StickyScope,
+  WindowEffectOverrides, WindowState,
+};
 
 /// User-friendly representation of a tiling or non-tiling window.
 ///
@@ -27,4 +48,8 @@ pub struct WindowDto {
   pub class_name: String,
   pub process_name: String,
   pub active_drag: Option<ActiveDrag>,
+  pub is_urgent: bool,
+  pub sticky: Option<StickyScope>,
+  pub size_constraints: SizeConstraints,
+  pub effect_overrides: WindowEffectOverrides,
 }
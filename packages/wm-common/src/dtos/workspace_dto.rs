@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -7,6 +25,7 @@ use crate::TilingDirection;
 /// User-friendly representation of a workspace.
 ///
 /// Used for IPC and debug logging.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WorkspaceDto {
@@ -18,6 +37,8 @@ pub struct WorkspaceDto {
   pub child_focus_order: Vec<Uuid>,
   pub has_focus: bool,
   pub is_displayed: bool,
+  pub is_urgent: bool,
+  pub is_monocle: bool,
   pub width: i32,
   pub height: i32,
   pub x: i32,
@@ -0,0 +1,183 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Number of columns and rows to divide an area into.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GridSize {
+  pub columns: u32,
+  pub rows: u32,
+}
+
+impl FromStr for GridSize {
+  type Err = anyhow::Error;
+
+  /// Parses a string of format `<columns>x<rows>` (e.g. `3x2`).
+  fn from_str(unparsed: &str) -> anyhow::Result<Self> {
+    let err_msg = format!(
+      "Not a valid grid size '{unparsed}'. Must be of format '3x2'."
+    );
+
+    let (columns, rows) =
+      unparsed.split_once('x').context(err_msg.clone())?;
+    let columns =
+      columns.trim().parse::<u32>().context(err_msg.clone())?;
+    let rows = rows.trim().parse::<u32>().context(err_msg.clone())?;
+
+    if columns == 0 || rows == 0 {
+      bail!(err_msg);
+    }
+
+    Ok(Self { columns, rows })
+  }
+}
+
+impl fmt::Display for GridSize {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}x{}", self.columns, self.rows)
+  }
+}
+
+/// Column and row pair within a grid. Used both for the position of a
+/// cell and for the number of cells spanned.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GridCell {
+  pub column: u32,
+  pub row: u32,
+}
+
+impl FromStr for GridCell {
+  type Err = anyhow::Error;
+
+  /// Parses a string of format `<column>,<row>` (e.g. `0,1`).
+  fn from_str(unparsed: &str) -> anyhow::Result<Self> {
+    let err_msg = format!(
+      "Not a valid grid cell '{unparsed}'. Must be of format '0,1'."
+    );
+
+    let (column, row) =
+      unparsed.split_once(',').context(err_msg.clone())?;
+
+    Ok(Self {
+      column: column.trim().parse::<u32>().context(err_msg.clone())?,
+      row: row.trim().parse::<u32>().context(err_msg)?,
+    })
+  }
+}
+
+impl fmt::Display for GridCell {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{},{}", self.column, self.row)
+  }
+}
+
+/// Area of a grid covered by a window.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GridPlacement {
+  pub size: GridSize,
+
+  /// Top-left cell of the covered area (zero-indexed).
+  pub cell: GridCell,
+
+  /// Number of columns and rows covered.
+  pub span: GridCell,
+}
+
+impl GridPlacement {
+  /// Clamps the cell and span to fit within the grid.
+  ///
+  /// Grid sizes that aren't parsed from a string can have 0 columns or
+  /// rows, in which case the grid is treated as having one.
+  #[must_use]
+  pub fn clamped(&self) -> Self {
+    let size = GridSize {
+      columns: self.size.columns.max(1),
+      rows: self.size.rows.max(1),
+    };
+
+    let column = self.cell.column.min(size.columns - 1);
+    let row = self.cell.row.min(size.rows - 1);
+
+    Self {
+      cell: GridCell { column, row },
+      span: GridCell {
+        column: self.span.column.clamp(1, size.columns - column),
+        row: self.span.row.clamp(1, size.rows - row),
+      },
+      size,
+    }
+  }
+}
+
+/// Preset positions for snapping a floating window within its workspace.
+#[derive(Clone, Debug, PartialEq, Serialize, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+#[serde(rename_all = "snake_case")]
+pub enum SnapPosition {
+  LeftHalf,
+  RightHalf,
+  TopHalf,
+  BottomHalf,
+  TopLeft,
+  TopRight,
+  BottomLeft,
+  BottomRight,
+  LeftThird,
+  CenterThird,
+  RightThird,
+  LeftTwoThirds,
+  RightTwoThirds,
+  Maximized,
+}
+
+impl SnapPosition {
+  /// Gets the equivalent placement within a grid.
+  #[must_use]
+  pub fn grid_placement(&self) -> GridPlacement {
+    let (columns, rows, column, row, column_span) = match self {
+      Self::LeftHalf => (2, 1, 0, 0, 1),
+      Self::RightHalf => (2, 1, 1, 0, 1),
+      Self::TopHalf => (1, 2, 0, 0, 1),
+      Self::BottomHalf => (1, 2, 0, 1, 1),
+      Self::TopLeft => (2, 2, 0, 0, 1),
+      Self::TopRight => (2, 2, 1, 0, 1),
+      Self::BottomLeft => (2, 2, 0, 1, 1),
+      Self::BottomRight => (2, 2, 1, 1, 1),
+      Self::LeftThird => (3, 1, 0, 0, 1),
+      Self::CenterThird => (3, 1, 1, 0, 1),
+      Self::RightThird => (3, 1, 2, 0, 1),
+      Self::LeftTwoThirds => (3, 1, 0, 0, 2),
+      Self::RightTwoThirds => (3, 1, 1, 0, 2),
+      Self::Maximized => (1, 1, 0, 0, 1),
+    };
+
+    GridPlacement {
+      size: GridSize { columns, rows },
+      cell: GridCell { column, row },
+      span: GridCell {
+        column: column_span,
+        row: 1,
+      },
+    }
+  }
+}
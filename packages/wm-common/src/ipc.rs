@@ -16,10 +16,14 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use schemars::schema::RootSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{BindingModeConfig, ContainerDto, TilingDirection, WmEvent};
+use crate::{
+  BindingModeConfig, CommandSchema, ContainerDto, MetricsDto,
+  TilingDirection, WmEvent,
+};
 
 pub const DEFAULT_IPC_PORT: u32 = 6123;
 
@@ -45,9 +49,13 @@ pub enum ClientResponseData {
   AppMetadata(AppMetadataData),
   BindingModes(BindingModesData),
   Command(CommandData),
+  Commands(CommandsData),
+  ConfigSchema(ConfigSchemaData),
   EventSubscribe(EventSubscribeData),
   EventUnsubscribe,
   Focused(FocusedData),
+  FocusHistory(FocusHistoryData),
+  Metrics(MetricsData),
   Monitors(MonitorsData),
   TilingDirection(TilingDirectionData),
   Windows(WindowsData),
@@ -73,6 +81,18 @@ pub struct CommandData {
   pub subject_container_id: Uuid,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandsData {
+  pub commands: Vec<CommandSchema>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigSchemaData {
+  pub config_schema: RootSchema,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EventSubscribeData {
@@ -85,6 +105,18 @@ pub struct FocusedData {
   pub focused: ContainerDto,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusHistoryData {
+  pub focus_history: Vec<ContainerDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsData {
+  pub metrics: MetricsDto,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MonitorsData {
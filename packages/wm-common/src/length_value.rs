@@ -1,7 +1,26 @@
-use std::str::FromStr;
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
 
 use anyhow::{bail, Context};
 use regex::Regex;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -10,7 +29,7 @@ pub struct LengthValue {
   pub unit: LengthUnit,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, Clone, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LengthUnit {
   Percentage,
@@ -96,19 +115,39 @@ impl FromStr for LengthValue {
   }
 }
 
+impl fmt::Display for LengthValue {
+  /// Formats the length value as a string that can be parsed back with
+  /// `from_str`. Since only whole numbers are parsed, the amount is
+  /// rounded.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.unit {
+      LengthUnit::Pixel => write!(f, "{}px", self.amount.round()),
+      LengthUnit::Percentage => {
+        write!(f, "{}%", (self.amount * 100.0).round())
+      }
+    }
+  }
+}
+
+/// Forms that a `LengthValue` can be deserialized from.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(description = "Length in pixels or percent (e.g. `10px`).")]
+enum LengthValueDe {
+  Struct {
+    amount: f32,
+    unit: LengthUnit,
+  },
+  /// Number followed by a unit (e.g. `10px` or `10%`).
+  String(#[schemars(regex(pattern = r"^[+-]?\d+(%|px)?$"))] String),
+}
+
 /// Deserialize a `LengthValue` from either a string or a struct.
 impl<'de> Deserialize<'de> for LengthValue {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
     D: Deserializer<'de>,
   {
-    #[derive(Deserialize)]
-    #[serde(untagged)]
-    enum LengthValueDe {
-      Struct { amount: f32, unit: LengthUnit },
-      String(String),
-    }
-
     match LengthValueDe::deserialize(deserializer)? {
       LengthValueDe::Struct { amount, unit } => Ok(Self { amount, unit }),
       LengthValueDe::String(str) => {
@@ -117,3 +156,13 @@ impl<'de> Deserialize<'de> for LengthValue {
     }
   }
 }
+
+impl JsonSchema for LengthValue {
+  fn schema_name() -> String {
+    "LengthValue".to_string()
+  }
+
+  fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+    LengthValueDe::json_schema(generator)
+  }
+}
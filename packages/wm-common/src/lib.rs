@@ -1,40 +1,70 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
 mod active_drag;
 mod app_command;
 mod color;
+mod command_schema;
 mod delta;
 mod direction;
 mod display_state;
 mod dtos;
+mod grid_placement;
 mod ipc;
 mod length_value;
+mod metrics;
 mod opacity_value;
 mod parsed_config;
 mod point;
 mod rect;
 mod rect_delta;
+mod size_constraints;
+mod sticky_scope;
 mod tiling_direction;
 mod utils;
+mod window_effect_overrides;
 mod window_state;
 mod wm_event;
 
 pub use active_drag::*;
 pub use app_command::*;
 pub use color::*;
+pub use command_schema::*;
 pub use delta::*;
 pub use direction::*;
 pub use display_state::*;
 pub use dtos::*;
+pub use grid_placement::*;
 pub use ipc::*;
 pub use length_value::*;
+pub use metrics::*;
 pub use opacity_value::*;
 pub use parsed_config::*;
 pub use point::*;
 pub use rect::*;
 pub use rect_delta::*;
+pub use size_constraints::*;
+pub use sticky_scope::*;
 pub use tiling_direction::*;
 pub use utils::*;
+pub use window_effect_overrides::*;
 pub use window_state::*;
 pub use wm_event::*;
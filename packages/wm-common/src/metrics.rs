@@ -0,0 +1,263 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use serde::{Deserialize, Serialize};
+
+/// Counters and latency histograms of the WM's event loop.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsDto {
+  /// Seconds since the WM was started.
+  pub uptime_seconds: u64,
+
+  /// Number of processed platform events by event type (e.g.
+  /// `window_focused`).
+  pub platform_events: BTreeMap<String, u64>,
+
+  /// Number of commands that have been run, including commands run by
+  /// keybindings.
+  pub commands: u64,
+
+  /// Number of times that pending changes have been synced to the
+  /// platform.
+  pub platform_syncs: u64,
+
+  /// Number of windows whose position or z-order has been updated.
+  pub windows_redrawn: u64,
+
+  /// Number of received IPC messages.
+  pub ipc_messages: u64,
+
+  /// Number of IPC messages that are waiting to be processed.
+  pub ipc_queue_depth: u64,
+
+  /// Highest number of IPC messages that have been waiting to be
+  /// processed.
+  pub ipc_max_queue_depth: u64,
+
+  /// Number of WM events that were dropped for event subscriptions that
+  /// couldn't keep up.
+  pub ipc_lagged_events: u64,
+
+  /// Time taken to process platform events by event type, including the
+  /// resulting platform sync.
+  pub process_event_seconds: BTreeMap<String, HistogramDto>,
+
+  /// Time taken to run commands, excluding the resulting platform sync.
+  pub run_commands_seconds: HistogramDto,
+
+  /// Time taken to sync pending changes to the platform.
+  pub platform_sync_seconds: HistogramDto,
+
+  /// Time taken to update the position and z-order of windows.
+  pub redraw_containers_seconds: HistogramDto,
+
+  /// Number of windows that are redrawn per platform sync.
+  pub windows_redrawn_per_sync: HistogramDto,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramDto {
+  /// Cumulative counts of observations that are less than or equal to
+  /// each bucket's upper bound.
+  pub buckets: Vec<HistogramBucketDto>,
+
+  /// Total number of observations.
+  pub count: u64,
+
+  /// Sum of all observations.
+  pub sum: f64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramBucketDto {
+  pub le: f64,
+  pub count: u64,
+}
+
+impl MetricsDto {
+  /// Formats the metrics in the `OpenMetrics` text format, with metric
+  /// names prefixed with `lavawm_`.
+  #[must_use]
+  #[allow(clippy::too_many_lines)]
+  pub fn to_open_metrics(&self) -> String {
+    let mut output = String::new();
+
+    gauge(
+      &mut output,
+      "uptime_seconds",
+      "Seconds since the WM was started.",
+      self.uptime_seconds,
+    );
+
+    family(
+      &mut output,
+      "platform_events",
+      "counter",
+      "Number of processed platform events.",
+    );
+
+    for (kind, count) in &self.platform_events {
+      let _ = writeln!(
+        output,
+        "lavawm_platform_events_total{{kind=\"{kind}\"}} {count}"
+      );
+    }
+
+    counter(
+      &mut output,
+      "commands",
+      "Number of commands that have been run.",
+      self.commands,
+    );
+    counter(
+      &mut output,
+      "platform_syncs",
+      "Number of syncs of pending changes to the platform.",
+      self.platform_syncs,
+    );
+    counter(
+      &mut output,
+      "windows_redrawn",
+      "Number of windows whose position or z-order has been updated.",
+      self.windows_redrawn,
+    );
+    counter(
+      &mut output,
+      "ipc_messages",
+      "Number of received IPC messages.",
+      self.ipc_messages,
+    );
+    gauge(
+      &mut output,
+      "ipc_queue_depth",
+      "Number of IPC messages waiting to be processed.",
+      self.ipc_queue_depth,
+    );
+    gauge(
+      &mut output,
+      "ipc_max_queue_depth",
+      "Highest number of IPC messages waiting to be processed.",
+      self.ipc_max_queue_depth,
+    );
+    counter(
+      &mut output,
+      "ipc_lagged_events",
+      "Number of WM events dropped for lagging event subscriptions.",
+      self.ipc_lagged_events,
+    );
+
+    family(
+      &mut output,
+      "process_event_seconds",
+      "histogram",
+      "Time taken to process platform events.",
+    );
+
+    for (kind, histogram) in &self.process_event_seconds {
+      histogram_samples(
+        &mut output,
+        "process_event_seconds",
+        &format!("kind=\"{kind}\","),
+        histogram,
+      );
+    }
+
+    for (name, help, histogram) in [
+      (
+        "run_commands_seconds",
+        "Time taken to run commands.",
+        &self.run_commands_seconds,
+      ),
+      (
+        "platform_sync_seconds",
+        "Time taken to sync pending changes to the platform.",
+        &self.platform_sync_seconds,
+      ),
+      (
+        "redraw_containers_seconds",
+        "Time taken to update the position and z-order of windows.",
+        &self.redraw_containers_seconds,
+      ),
+      (
+        "windows_redrawn_per_sync",
+        "Number of windows redrawn per platform sync.",
+        &self.windows_redrawn_per_sync,
+      ),
+    ] {
+      family(&mut output, name, "histogram", help);
+      histogram_samples(&mut output, name, "", histogram);
+    }
+
+    output.push_str("# EOF\n");
+    output
+  }
+}
+
+fn family(output: &mut String, name: &str, kind: &str, help: &str) {
+  let _ = writeln!(output, "# TYPE lavawm_{name} {kind}");
+  let _ = writeln!(output, "# HELP lavawm_{name} {help}");
+}
+
+fn counter(output: &mut String, name: &str, help: &str, value: u64) {
+  family(output, name, "counter", help);
+  let _ = writeln!(output, "lavawm_{name}_total {value}");
+}
+
+fn gauge(output: &mut String, name: &str, help: &str, value: u64) {
+  family(output, name, "gauge", help);
+  let _ = writeln!(output, "lavawm_{name} {value}");
+}
+
+/// Writes the bucket, count and sum samples of a histogram. The given
+/// labels are prepended to the `le` label of each bucket.
+fn histogram_samples(
+  output: &mut String,
+  name: &str,
+  labels: &str,
+  histogram: &HistogramDto,
+) {
+  for bucket in &histogram.buckets {
+    let _ = writeln!(
+      output,
+      "lavawm_{name}_bucket{{{labels}le=\"{}\"}} {}",
+      bucket.le, bucket.count
+    );
+  }
+
+  let _ = writeln!(
+    output,
+    "lavawm_{name}_bucket{{{labels}le=\"+Inf\"}} {}",
+    histogram.count
+  );
+
+  let labels = labels.trim_end_matches(',');
+  let labels = if labels.is_empty() {
+    String::new()
+  } else {
+    format!("{{{labels}}}")
+  };
+
+  let _ =
+    writeln!(output, "lavawm_{name}_count{labels} {}", histogram.count);
+  let _ = writeln!(output, "lavawm_{name}_sum{labels} {}", histogram.sum);
+}
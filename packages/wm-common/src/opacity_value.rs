@@ -1,6 +1,25 @@
-use std::str::FromStr;
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fmt, str::FromStr};
 
 use anyhow::Context;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -59,19 +78,31 @@ impl FromStr for OpacityValue {
   }
 }
 
+impl fmt::Display for OpacityValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+/// Forms that an `OpacityValue` can be deserialized from.
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged, rename_all = "camelCase")]
+#[schemars(
+  description = "Opacity in percent or as a decimal (e.g. `80%`)."
+)]
+enum OpacityValueDe {
+  /// Decimal number between 0 and 1.
+  Number(f32),
+  /// Percentage or decimal number (e.g. `80%` or `0.8`).
+  String(#[schemars(regex(pattern = r"^\d+(\.\d+)?%?$"))] String),
+}
+
 /// Deserialize an `OpacityValue` from either a number or a string.
 impl<'de> Deserialize<'de> for OpacityValue {
   fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
   where
     D: Deserializer<'de>,
   {
-    #[derive(Deserialize)]
-    #[serde(untagged, rename_all = "camelCase")]
-    enum OpacityValueDe {
-      Number(f32),
-      String(String),
-    }
-
     match OpacityValueDe::deserialize(deserializer)? {
       OpacityValueDe::Number(num) => Ok(Self(num)),
       OpacityValueDe::String(str) => {
@@ -80,3 +111,13 @@ impl<'de> Deserialize<'de> for OpacityValue {
     }
   }
 }
+
+impl JsonSchema for OpacityValue {
+  fn schema_name() -> String {
+    "OpacityValue".to_string()
+  }
+
+  fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+    OpacityValueDe::json_schema(generator)
+  }
+}
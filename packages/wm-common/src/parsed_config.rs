@@ -1,10 +1,39 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::BTreeMap;
+
+use clap::ValueEnum;
+use schemars::{
+  gen::{SchemaGenerator, SchemaSettings},
+  schema::{RootSchema, Schema, SchemaObject},
+  visit::{visit_schema_object, Visitor},
+  JsonSchema,
+};
 use serde::{Deserialize, Serialize};
+use tracing::Level;
 
 use crate::{
   app_command::InvokeCommand, Color, LengthValue, OpacityValue, RectDelta,
+  TilingDirection, WindowState,
 };
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct ParsedConfig {
   pub binding_modes: Vec<BindingModeConfig>,
@@ -14,10 +43,74 @@ pub struct ParsedConfig {
   pub window_behavior: WindowBehaviorConfig,
   pub window_effects: WindowEffectsConfig,
   pub window_rules: Vec<WindowRuleConfig>,
+  pub window_swallowing: WindowSwallowingConfig,
   pub workspaces: Vec<WorkspaceConfig>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl ParsedConfig {
+  /// Gets a JSON Schema of the user config (e.g. for validating config
+  /// files in editors).
+  #[must_use]
+  pub fn json_schema() -> RootSchema {
+    SchemaSettings::draft07()
+      .with_visitor(RemoveStructDefaults)
+      .into_generator()
+      .into_root_schema_for::<Self>()
+  }
+
+  /// Validates parts of the config that can't be checked while it's
+  /// deserialized, so that errors can name where in the config they
+  /// occur (e.g. `window_rules[3].match[0].window_title`).
+  pub fn validate(&self) -> anyhow::Result<()> {
+    for (rule_index, rule) in self.window_rules.iter().enumerate() {
+      for (index, match_config) in rule.match_window.iter().enumerate() {
+        match_config.validate(&format!(
+          "window_rules[{rule_index}].match[{index}]"
+        ))?;
+      }
+    }
+
+    let swallowing = &self.window_swallowing;
+
+    for (index, match_config) in swallowing.swallowers.iter().enumerate() {
+      match_config
+        .validate(&format!("window_swallowing.swallowers[{index}]"))?;
+    }
+
+    for (index, match_config) in swallowing.exclusions.iter().enumerate() {
+      match_config
+        .validate(&format!("window_swallowing.exclusions[{index}]"))?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Removes default values of objects and lists from a schema, since
+/// they're serialized with camel case keys instead of the snake case keys
+/// used in config files.
+#[derive(Clone, Debug)]
+struct RemoveStructDefaults;
+
+impl Visitor for RemoveStructDefaults {
+  fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+    if let Some(metadata) = &mut schema.metadata {
+      let is_struct_default =
+        metadata.default.as_ref().is_some_and(|default| {
+          default.is_object()
+            || default.as_array().is_some_and(|items| !items.is_empty())
+        });
+
+      if is_struct_default {
+        metadata.default = None;
+      }
+    }
+
+    visit_schema_object(self, schema);
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct BindingModeConfig {
   /// Name of the binding mode.
@@ -32,7 +125,7 @@ pub struct BindingModeConfig {
   pub keybindings: Vec<KeybindingConfig>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct GapsConfig {
   /// Whether to scale the gaps with the DPI of the monitor.
@@ -65,8 +158,9 @@ impl Default for GapsConfig {
   }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
+#[allow(clippy::struct_excessive_bools)]
 pub struct GeneralConfig {
   /// Config for automatically moving the cursor.
   pub cursor_jump: CursorJumpConfig,
@@ -74,6 +168,9 @@ pub struct GeneralConfig {
   /// Whether to automatically focus windows underneath the cursor.
   pub focus_follows_cursor: bool,
 
+  /// Config for navigating the history of focused windows.
+  pub focus_history: FocusHistoryConfig,
+
   /// Whether to switch back and forth between the previously focused
   /// workspace when focusing the current workspace.
   pub toggle_workspace_on_refocus: bool,
@@ -93,6 +190,24 @@ pub struct GeneralConfig {
 
   /// Affects which windows get shown in the native Windows taskbar.
   pub show_all_in_taskbar: bool,
+
+  /// How the target of directional commands (e.g. `focus --direction`)
+  /// is found.
+  pub directional_navigation: DirectionalNavigation,
+
+  /// Whether to save workspaces that are created, renamed, reordered or
+  /// deleted at runtime, and restore them on the next startup.
+  pub persist_dynamic_workspaces: bool,
+
+  /// Config for the log files.
+  pub logging: LoggingConfig,
+
+  /// Config for serving metrics of the event loop over HTTP.
+  pub metrics_endpoint: MetricsEndpointConfig,
+
+  /// Config for the watcher process, which restores hidden windows if
+  /// the WM crashes.
+  pub watcher: WatcherConfig,
 }
 
 impl Default for GeneralConfig {
@@ -100,17 +215,23 @@ impl Default for GeneralConfig {
     GeneralConfig {
       cursor_jump: CursorJumpConfig::default(),
       focus_follows_cursor: false,
+      focus_history: FocusHistoryConfig::default(),
       toggle_workspace_on_refocus: true,
       startup_commands: vec![],
       shutdown_commands: vec![],
       config_reload_commands: vec![],
       hide_method: HideMethod::Cloak,
       show_all_in_taskbar: false,
+      directional_navigation: DirectionalNavigation::Tree,
+      persist_dynamic_workspaces: false,
+      logging: LoggingConfig::default(),
+      metrics_endpoint: MetricsEndpointConfig::default(),
+      watcher: WatcherConfig::default(),
     }
   }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct CursorJumpConfig {
   /// Whether to automatically move the cursor on the specified trigger.
@@ -120,7 +241,9 @@ pub struct CursorJumpConfig {
   pub trigger: CursorJumpTrigger,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum CursorJumpTrigger {
   #[default]
@@ -128,7 +251,130 @@ pub enum CursorJumpTrigger {
   WindowFocus,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct FocusHistoryConfig {
+  /// Whether windows on hidden workspaces are included when navigating
+  /// back and forward or cycling through recent windows.
+  pub include_hidden_workspaces: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct LoggingConfig {
+  /// Minimum level of messages that are written to the log files.
+  pub level: LogLevel,
+
+  /// Levels of individual modules (e.g. `lavawm::events: debug`), which
+  /// take precedence over `level`.
+  pub modules: BTreeMap<String, LogLevel>,
+
+  /// Number of daily log files to keep.
+  pub max_files: usize,
+}
+
+impl Default for LoggingConfig {
+  fn default() -> Self {
+    LoggingConfig {
+      level: LogLevel::Info,
+      modules: BTreeMap::new(),
+      max_files: 7,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct MetricsEndpointConfig {
+  /// Whether to serve metrics in the `OpenMetrics` text format at
+  /// `http://127.0.0.1:<port>/metrics`.
+  pub enabled: bool,
+
+  /// Port to serve metrics on.
+  pub port: u16,
+}
+
+impl Default for MetricsEndpointConfig {
+  fn default() -> Self {
+    MetricsEndpointConfig {
+      enabled: false,
+      port: 9464,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct WatcherConfig {
+  /// Whether the watcher restarts the WM after it crashes or stops
+  /// responding, instead of exiting after restoring hidden windows.
+  pub supervise: bool,
+
+  /// Maximum number of restarts within `restart_window_secs`, after which
+  /// the watcher stops restarting the WM.
+  pub max_restarts: u32,
+
+  /// Period in seconds over which restarts are counted.
+  pub restart_window_secs: u64,
+
+  /// Delay before restarting the WM, which doubles with each restart
+  /// within `restart_window_secs`.
+  pub restart_delay_ms: u64,
+
+  /// Upper limit of the delay before restarting the WM.
+  pub max_restart_delay_ms: u64,
+
+  /// Interval between heartbeats, which check that the WM still responds
+  /// to IPC messages.
+  pub heartbeat_interval_ms: u64,
+
+  /// How long to wait for a heartbeat response before the WM is
+  /// considered hung and is terminated.
+  pub heartbeat_timeout_ms: u64,
+}
+
+impl Default for WatcherConfig {
+  fn default() -> Self {
+    WatcherConfig {
+      supervise: false,
+      max_restarts: 3,
+      restart_window_secs: 300,
+      restart_delay_ms: 1000,
+      max_restart_delay_ms: 30000,
+      heartbeat_interval_ms: 5000,
+      heartbeat_timeout_ms: 10000,
+    }
+  }
+}
+
+#[derive(
+  Clone, Copy, Debug, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+  Error,
+  Warn,
+  Info,
+  Debug,
+  Trace,
+}
+
+impl LogLevel {
+  #[must_use]
+  pub fn level(self) -> Level {
+    match self {
+      LogLevel::Error => Level::ERROR,
+      LogLevel::Warn => Level::WARN,
+      LogLevel::Info => Level::INFO,
+      LogLevel::Debug => Level::DEBUG,
+      LogLevel::Trace => Level::TRACE,
+    }
+  }
+}
+
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum HideMethod {
   Hide,
@@ -136,7 +382,22 @@ pub enum HideMethod {
   Cloak,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectionalNavigation {
+  /// Traverse the container tree of the current workspace, and only
+  /// move to other monitors at the edge of the workspace.
+  #[default]
+  Tree,
+
+  /// Pick the nearest window in the given direction based on window
+  /// positions, across tiling and floating windows and monitors.
+  Geometric,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct KeybindingConfig {
   /// Keyboard shortcut to trigger the keybinding.
@@ -146,7 +407,7 @@ pub struct KeybindingConfig {
   pub commands: Vec<InvokeCommand>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowBehaviorConfig {
   /// New windows are created in this state whenever possible.
@@ -156,9 +417,35 @@ pub struct WindowBehaviorConfig {
   /// changes the defaults for when the state change commands, like
   /// `set_floating`, are used without any flags.
   pub state_defaults: WindowStateDefaultsConfig,
+
+  /// Where new floating windows are placed on their workspace.
+  pub floating_placement: FloatingPlacementStrategy,
+
+  /// Distance in pixels within which a dragged floating window snaps to
+  /// the edges of its workspace and of other windows. Snapping is
+  /// disabled when set to 0.
+  pub snap_threshold: u32,
+
+  /// Whether tiling windows that make themselves fullscreen (e.g. video
+  /// players) are kept within their tile instead of being fullscreened.
+  pub fake_fullscreen: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+impl Default for WindowBehaviorConfig {
+  fn default() -> Self {
+    WindowBehaviorConfig {
+      initial_state: InitialWindowState::default(),
+      state_defaults: WindowStateDefaultsConfig::default(),
+      floating_placement: FloatingPlacementStrategy::default(),
+      snap_threshold: 10,
+      fake_fullscreen: false,
+    }
+  }
+}
+
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum InitialWindowState {
   #[default]
@@ -166,14 +453,37 @@ pub enum InitialWindowState {
   Floating,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FloatingPlacementStrategy {
+  /// Centers the window on its workspace. Keeps the position chosen by
+  /// the application instead if `state_defaults.floating.centered` is
+  /// disabled.
+  #[default]
+  Center,
+  /// Centers the window, offset diagonally from any floating windows
+  /// already at that position.
+  Cascade,
+  /// Centers the window under the mouse cursor.
+  UnderCursor,
+  /// Places the window where it overlaps the least with other floating
+  /// windows.
+  LeastOverlap,
+  /// Restores the last position and size that the user moved a window
+  /// of the same application to. Falls back to `cascade`.
+  RememberPerApp,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowStateDefaultsConfig {
   pub floating: FloatingStateConfig,
   pub fullscreen: FullscreenStateConfig,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct FloatingStateConfig {
   /// Whether to center new floating windows.
@@ -192,7 +502,7 @@ impl Default for FloatingStateConfig {
   }
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct FullscreenStateConfig {
   /// Whether to prefer fullscreen windows to be maximized.
@@ -211,7 +521,7 @@ impl Default for FullscreenStateConfig {
   }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowEffectsConfig {
   /// Visual effects to apply to the focused window.
@@ -221,7 +531,7 @@ pub struct WindowEffectsConfig {
   pub other_windows: WindowEffectConfig,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowEffectConfig {
   /// Config for optionally applying a colored border.
@@ -237,7 +547,7 @@ pub struct WindowEffectConfig {
   pub transparency: TransparencyEffectConfig,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct BorderEffectConfig {
   /// Whether to enable the effect.
@@ -261,14 +571,14 @@ impl Default for BorderEffectConfig {
   }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct HideTitleBarEffectConfig {
   /// Whether to enable the effect.
   pub enabled: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct CornerEffectConfig {
   /// Whether to enable the effect.
@@ -278,7 +588,18 @@ pub struct CornerEffectConfig {
   pub style: CornerStyle,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(
+  Clone,
+  Debug,
+  Default,
+  Deserialize,
+  Eq,
+  JsonSchema,
+  PartialEq,
+  Serialize,
+  ValueEnum,
+)]
+#[clap(rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
 pub enum CornerStyle {
   #[default]
@@ -288,7 +609,7 @@ pub enum CornerStyle {
   SmallRounded,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, JsonSchema, Serialize)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct TransparencyEffectConfig {
   /// Whether to enable the effect.
@@ -298,7 +619,24 @@ pub struct TransparencyEffectConfig {
   pub opacity: OpacityValue,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct WindowSwallowingConfig {
+  /// Whether tiling windows launched from a swallower window (e.g. a
+  /// terminal) take its place, with the swallower being hidden until the
+  /// launched window is closed.
+  pub enabled: bool,
+
+  /// Windows that can be swallowed by the windows they launch.
+  pub swallowers: Vec<WindowMatchConfig>,
+
+  /// Launched windows that should never swallow their swallower.
+  pub exclusions: Vec<WindowMatchConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct WindowRuleConfig {
   pub commands: Vec<InvokeCommand>,
@@ -313,25 +651,279 @@ pub struct WindowRuleConfig {
   pub run_once: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
 #[serde(default, rename_all(serialize = "camelCase"))]
 pub struct WindowMatchConfig {
   pub window_process: Option<MatchType>,
   pub window_class: Option<MatchType>,
   pub window_title: Option<MatchType>,
+
+  /// Full path to the executable of the window's process.
+  pub window_path: Option<MatchType>,
+
+  /// Whether the window has the tool window style (e.g. palettes and
+  /// floating toolbars).
+  pub is_tool_window: Option<bool>,
+
+  /// Whether the window is a dialog (e.g. file pickers and message
+  /// boxes).
+  pub is_dialog: Option<bool>,
+
+  /// Whether the window has an owner window.
+  pub has_owner: Option<bool>,
+
+  /// Size of the window when it was first managed.
+  pub initial_size: Option<SizeMatchConfig>,
+
+  /// Index of the monitor that the window is on.
+  pub monitor: Option<u32>,
+
+  /// Name of the workspace that the window is in.
+  pub workspace: Option<MatchType>,
+
+  /// Current state of the window.
+  pub window_state: Option<WindowStateMatch>,
+
+  /// Whether the window's process is running with elevated privileges.
+  pub is_elevated: Option<bool>,
+
+  /// Whether the window is requesting attention.
+  pub is_urgent: Option<bool>,
+
+  /// Match configs that must *all* match.
+  pub all: Vec<WindowMatchConfig>,
+
+  /// Match configs where at least one must match. Ignored if empty.
+  pub any: Vec<WindowMatchConfig>,
+
+  /// Match config that must *not* match.
+  pub not: Option<Box<WindowMatchConfig>>,
 }
 
-/// Due to limitations in `serde_yaml`, we need to use an untagged enum
-/// instead of a regular enum for serialization. Using a regular enum
-/// causes issues with flow-style objects in YAML.
+impl WindowMatchConfig {
+  /// Whether the given window properties are a match for the config.
+  ///
+  /// All matchers that are set need to match, which includes the `all`,
+  /// `any` and `not` combinators.
+  #[must_use]
+  pub fn is_match(&self, properties: &WindowMatchProperties) -> bool {
+    let is_str_match = |match_type: &Option<MatchType>, value: &str| {
+      match_type
+        .as_ref()
+        .is_none_or(|match_type| match_type.is_match(value))
+    };
+
+    let is_bool_match = |expected: Option<bool>, value: bool| {
+      expected.is_none_or(|expected| expected == value)
+    };
+
+    is_str_match(&self.window_process, &properties.process_name)
+      && is_str_match(&self.window_class, &properties.class_name)
+      && is_str_match(&self.window_title, &properties.title)
+      && self.window_path.as_ref().is_none_or(|match_type| {
+        properties
+          .process_path
+          .as_ref()
+          .is_some_and(|path| match_type.is_match(path))
+      })
+      && is_bool_match(self.is_tool_window, properties.is_tool_window)
+      && is_bool_match(self.is_dialog, properties.is_dialog)
+      && is_bool_match(self.has_owner, properties.has_owner)
+      && self
+        .is_elevated
+        .is_none_or(|expected| properties.is_elevated == Some(expected))
+      && is_bool_match(self.is_urgent, properties.is_urgent)
+      && self.initial_size.as_ref().is_none_or(|size| {
+        size.is_match(properties.initial_width, properties.initial_height)
+      })
+      && self.monitor.is_none_or(|monitor_index| {
+        properties.monitor_index == Some(monitor_index as usize)
+      })
+      && self.workspace.as_ref().is_none_or(|match_type| {
+        properties
+          .workspace_name
+          .as_ref()
+          .is_some_and(|name| match_type.is_match(name))
+      })
+      && self
+        .window_state
+        .as_ref()
+        .is_none_or(|state| state.is_match(&properties.state))
+      && self.all.iter().all(|config| config.is_match(properties))
+      && (self.any.is_empty()
+        || self.any.iter().any(|config| config.is_match(properties)))
+      && self
+        .not
+        .as_ref()
+        .is_none_or(|config| !config.is_match(properties))
+  }
+
+  /// Whether the config or any of its nested configs matches on the
+  /// executable path of the window's process.
+  #[must_use]
+  pub fn uses_process_path(&self) -> bool {
+    self.window_path.is_some()
+      || self
+        .nested_configs()
+        .any(WindowMatchConfig::uses_process_path)
+  }
+
+  /// Whether the config or any of its nested configs matches on whether
+  /// the window's process is elevated.
+  #[must_use]
+  pub fn uses_is_elevated(&self) -> bool {
+    self.is_elevated.is_some()
+      || self
+        .nested_configs()
+        .any(WindowMatchConfig::uses_is_elevated)
+  }
+
+  /// Checks that the patterns of the config and its nested configs are
+  /// valid, where `path` is the location of the config for errors.
+  fn validate(&self, path: &str) -> anyhow::Result<()> {
+    let matchers = [
+      ("window_process", &self.window_process),
+      ("window_class", &self.window_class),
+      ("window_title", &self.window_title),
+      ("window_path", &self.window_path),
+      ("workspace", &self.workspace),
+    ];
+
+    for (field, match_type) in matchers {
+      if let Some(match_type) = match_type {
+        match_type.validate(&format!("{path}.{field}"))?;
+      }
+    }
+
+    for (index, config) in self.all.iter().enumerate() {
+      config.validate(&format!("{path}.all[{index}]"))?;
+    }
+
+    for (index, config) in self.any.iter().enumerate() {
+      config.validate(&format!("{path}.any[{index}]"))?;
+    }
+
+    if let Some(config) = &self.not {
+      config.validate(&format!("{path}.not"))?;
+    }
+
+    Ok(())
+  }
+
+  /// Configs of the `all`, `any` and `not` combinators.
+  fn nested_configs(&self) -> impl Iterator<Item = &WindowMatchConfig> {
+    self
+      .all
+      .iter()
+      .chain(self.any.iter())
+      .chain(self.not.as_deref())
+  }
+}
+
+/// Properties of a window that window rules are matched against.
+///
+/// Properties that are costly to retrieve are `None` when none of the
+/// match configs use them, or when they can't be retrieved. Matchers on
+/// a `None` property never match.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug)]
+pub struct WindowMatchProperties {
+  pub process_name: String,
+  pub class_name: String,
+  pub title: String,
+  pub process_path: Option<String>,
+  pub is_tool_window: bool,
+  pub is_dialog: bool,
+  pub has_owner: bool,
+  pub initial_width: i32,
+  pub initial_height: i32,
+  pub monitor_index: Option<usize>,
+  pub workspace_name: Option<String>,
+  pub state: WindowState,
+  pub is_elevated: Option<bool>,
+  pub is_urgent: bool,
+}
+
+/// Inclusive bounds to match a window's dimensions against.
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct SizeMatchConfig {
+  pub min_width: Option<i32>,
+  pub max_width: Option<i32>,
+  pub min_height: Option<i32>,
+  pub max_height: Option<i32>,
+}
+
+impl SizeMatchConfig {
+  /// Whether the given dimensions are within the bounds.
+  #[must_use]
+  pub fn is_match(&self, width: i32, height: i32) -> bool {
+    self.min_width.is_none_or(|min| width >= min)
+      && self.max_width.is_none_or(|max| width <= max)
+      && self.min_height.is_none_or(|min| height >= min)
+      && self.max_height.is_none_or(|max| height <= max)
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowStateMatch {
+  Tiling,
+  Floating,
+  Minimized,
+  Fullscreen,
+}
+
+impl WindowStateMatch {
+  /// Whether the given window state is a match.
+  #[must_use]
+  pub fn is_match(&self, state: &WindowState) -> bool {
+    matches!(
+      (self, state),
+      (WindowStateMatch::Tiling, WindowState::Tiling)
+        | (WindowStateMatch::Floating, WindowState::Floating(_))
+        | (WindowStateMatch::Minimized, WindowState::Minimized)
+        | (WindowStateMatch::Fullscreen, WindowState::Fullscreen(_))
+    )
+  }
+}
+
+/// A matcher for a string value of a window (e.g. its title).
+///
+/// Regex and glob patterns are compiled once when the config is
+/// deserialized, and invalid patterns cause the config to fail loading
+/// on `ParsedConfig::validate`.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
-#[serde(untagged)]
+#[serde(from = "MatchTypeConfig", into = "MatchTypeConfig")]
 pub enum MatchType {
-  Equals { equals: String },
-  Includes { includes: String },
-  Regex { regex: String },
-  NotEquals { not_equals: String },
-  NotRegex { not_regex: String },
+  Equals {
+    equals: String,
+    case_insensitive: bool,
+  },
+  Includes {
+    includes: String,
+    case_insensitive: bool,
+  },
+  Regex {
+    regex: MatchPattern,
+  },
+  NotEquals {
+    not_equals: String,
+    case_insensitive: bool,
+  },
+  NotRegex {
+    not_regex: MatchPattern,
+  },
+  Glob {
+    glob: MatchPattern,
+  },
+  NotGlob {
+    not_glob: MatchPattern,
+  },
 }
 
 impl MatchType {
@@ -339,33 +931,370 @@ impl MatchType {
   #[must_use]
   pub fn is_match(&self, value: &str) -> bool {
     match self {
-      MatchType::Equals { equals } => value == equals,
-      MatchType::Includes { includes } => value.contains(includes),
-      MatchType::Regex { regex } => {
-        regex::Regex::new(regex).is_ok_and(|re| re.is_match(value))
+      MatchType::Equals {
+        equals,
+        case_insensitive,
+      } => is_equal(value, equals, *case_insensitive),
+      MatchType::Includes {
+        includes,
+        case_insensitive,
+      } => {
+        if *case_insensitive {
+          value.to_lowercase().contains(&includes.to_lowercase())
+        } else {
+          value.contains(includes)
+        }
       }
-      MatchType::NotEquals { not_equals } => value != not_equals,
-      MatchType::NotRegex { not_regex } => {
-        regex::Regex::new(not_regex).is_ok_and(|re| !re.is_match(value))
+      MatchType::Regex { regex } => regex.is_match(value),
+      MatchType::NotEquals {
+        not_equals,
+        case_insensitive,
+      } => !is_equal(value, not_equals, *case_insensitive),
+      MatchType::NotRegex { not_regex } => !not_regex.is_match(value),
+      MatchType::Glob { glob } => glob.is_match(value),
+      MatchType::NotGlob { not_glob } => !not_glob.is_match(value),
+    }
+  }
+
+  /// Checks that the pattern of the match type is valid, where `path` is
+  /// the location of the match type for errors.
+  pub fn validate(&self, path: &str) -> anyhow::Result<()> {
+    let (kind, pattern) = match self {
+      MatchType::Regex { regex: pattern }
+      | MatchType::NotRegex { not_regex: pattern } => ("regex", pattern),
+      MatchType::Glob { glob: pattern }
+      | MatchType::NotGlob { not_glob: pattern } => ("glob", pattern),
+      _ => return Ok(()),
+    };
+
+    match pattern.error() {
+      Some(err) => anyhow::bail!(
+        "Invalid {kind} '{}' in {path}: {err}",
+        pattern.source()
+      ),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Whether two strings are equal, optionally ignoring case.
+fn is_equal(value: &str, other: &str, case_insensitive: bool) -> bool {
+  if case_insensitive {
+    value.to_lowercase() == other.to_lowercase()
+  } else {
+    value == other
+  }
+}
+
+/// A regex or glob pattern that has been compiled to a regex.
+#[derive(Clone, Debug)]
+pub struct MatchPattern {
+  /// The pattern as written in the config.
+  source: String,
+  case_insensitive: bool,
+  /// Compiled pattern, or why the pattern is invalid. Invalid patterns
+  /// never match.
+  regex: Result<regex::Regex, regex::Error>,
+}
+
+impl MatchPattern {
+  /// Compiles a regex pattern.
+  #[must_use]
+  pub fn regex(source: &str, case_insensitive: bool) -> Self {
+    let regex = regex::RegexBuilder::new(source)
+      .case_insensitive(case_insensitive)
+      .build();
+
+    Self {
+      source: source.to_string(),
+      case_insensitive,
+      regex,
+    }
+  }
+
+  /// Compiles a glob pattern. Supports `*` for any sequence of
+  /// characters, `?` for any single character, and `[...]` or `[!...]`
+  /// for character classes. The glob needs to match the whole value.
+  #[must_use]
+  pub fn glob(source: &str, case_insensitive: bool) -> Self {
+    let regex = regex::RegexBuilder::new(&glob_to_regex(source))
+      .case_insensitive(case_insensitive)
+      .build();
+
+    Self {
+      source: source.to_string(),
+      case_insensitive,
+      regex,
+    }
+  }
+
+  /// Why the pattern failed to compile, if it's invalid.
+  #[must_use]
+  pub fn error(&self) -> Option<&regex::Error> {
+    self.regex.as_ref().err()
+  }
+
+  /// The pattern as written in the config.
+  #[must_use]
+  pub fn source(&self) -> &str {
+    &self.source
+  }
+
+  #[must_use]
+  pub fn is_case_insensitive(&self) -> bool {
+    self.case_insensitive
+  }
+
+  #[must_use]
+  pub fn is_match(&self, value: &str) -> bool {
+    self.regex.as_ref().is_ok_and(|regex| regex.is_match(value))
+  }
+}
+
+impl PartialEq for MatchPattern {
+  fn eq(&self, other: &Self) -> bool {
+    self.source == other.source
+      && self.case_insensitive == other.case_insensitive
+  }
+}
+
+/// Converts a glob pattern to an anchored regex pattern.
+fn glob_to_regex(glob: &str) -> String {
+  let mut pattern = String::from("^");
+  let mut chars = glob.chars().peekable();
+
+  while let Some(char) = chars.next() {
+    match char {
+      '*' => pattern.push_str(".*"),
+      '?' => pattern.push('.'),
+      '[' => {
+        pattern.push('[');
+
+        if chars.next_if_eq(&'!').is_some() {
+          pattern.push('^');
+        }
+
+        // Copy the character class as-is, escaping characters that have
+        // a special meaning within regex character classes.
+        for char in chars.by_ref() {
+          if char == ']' {
+            break;
+          }
+
+          if matches!(char, '\\' | '[' | '&' | '~') {
+            pattern.push('\\');
+          }
+
+          pattern.push(char);
+        }
+
+        pattern.push(']');
       }
+      _ => pattern.push_str(&regex::escape(&char.to_string())),
     }
   }
+
+  pattern.push('$');
+  pattern
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+/// Serialized form of `MatchType`.
+///
+/// Due to limitations in `serde_yaml`, we need to use an untagged enum
+/// instead of a regular enum for serialization. Using a regular enum
+/// causes issues with flow-style objects in YAML.
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(untagged)]
+#[schemars(description = "Matcher for a string value of a window.")]
+enum MatchTypeConfig {
+  Equals {
+    equals: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+  Includes {
+    includes: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+  Regex {
+    regex: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+  NotEquals {
+    not_equals: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+  NotRegex {
+    not_regex: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+  Glob {
+    glob: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+  NotGlob {
+    not_glob: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    case_insensitive: bool,
+  },
+}
+
+impl JsonSchema for MatchType {
+  fn schema_name() -> String {
+    "MatchType".to_string()
+  }
+
+  fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+    MatchTypeConfig::json_schema(generator)
+  }
+}
+
+impl From<MatchTypeConfig> for MatchType {
+  fn from(config: MatchTypeConfig) -> Self {
+    match config {
+      MatchTypeConfig::Equals {
+        equals,
+        case_insensitive,
+      } => MatchType::Equals {
+        equals,
+        case_insensitive,
+      },
+      MatchTypeConfig::Includes {
+        includes,
+        case_insensitive,
+      } => MatchType::Includes {
+        includes,
+        case_insensitive,
+      },
+      MatchTypeConfig::Regex {
+        regex,
+        case_insensitive,
+      } => MatchType::Regex {
+        regex: MatchPattern::regex(&regex, case_insensitive),
+      },
+      MatchTypeConfig::NotEquals {
+        not_equals,
+        case_insensitive,
+      } => MatchType::NotEquals {
+        not_equals,
+        case_insensitive,
+      },
+      MatchTypeConfig::NotRegex {
+        not_regex,
+        case_insensitive,
+      } => MatchType::NotRegex {
+        not_regex: MatchPattern::regex(&not_regex, case_insensitive),
+      },
+      MatchTypeConfig::Glob {
+        glob,
+        case_insensitive,
+      } => MatchType::Glob {
+        glob: MatchPattern::glob(&glob, case_insensitive),
+      },
+      MatchTypeConfig::NotGlob {
+        not_glob,
+        case_insensitive,
+      } => MatchType::NotGlob {
+        not_glob: MatchPattern::glob(&not_glob, case_insensitive),
+      },
+    }
+  }
+}
+
+impl From<MatchType> for MatchTypeConfig {
+  fn from(match_type: MatchType) -> Self {
+    match match_type {
+      MatchType::Equals {
+        equals,
+        case_insensitive,
+      } => MatchTypeConfig::Equals {
+        equals,
+        case_insensitive,
+      },
+      MatchType::Includes {
+        includes,
+        case_insensitive,
+      } => MatchTypeConfig::Includes {
+        includes,
+        case_insensitive,
+      },
+      MatchType::Regex { regex } => MatchTypeConfig::Regex {
+        case_insensitive: regex.case_insensitive,
+        regex: regex.source,
+      },
+      MatchType::NotEquals {
+        not_equals,
+        case_insensitive,
+      } => MatchTypeConfig::NotEquals {
+        not_equals,
+        case_insensitive,
+      },
+      MatchType::NotRegex { not_regex } => MatchTypeConfig::NotRegex {
+        case_insensitive: not_regex.case_insensitive,
+        not_regex: not_regex.source,
+      },
+      MatchType::Glob { glob } => MatchTypeConfig::Glob {
+        case_insensitive: glob.case_insensitive,
+        glob: glob.source,
+      },
+      MatchType::NotGlob { not_glob } => MatchTypeConfig::NotGlob {
+        case_insensitive: not_glob.case_insensitive,
+        not_glob: not_glob.source,
+      },
+    }
+  }
+}
+
+#[derive(
+  Clone, Debug, Deserialize, Eq, Hash, JsonSchema, PartialEq, Serialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum WindowRuleEvent {
+  /// When a window loses native focus to another window.
+  Blur,
+
   /// When a window receives native focus.
   Focus,
 
   /// When a window is initially managed.
   Manage,
 
+  /// When a window is moved to a different monitor (e.g. by a move
+  /// command, by being dragged or on a display settings change).
+  MonitorChange,
+
+  /// When a window's state changes, either by a command or by the window
+  /// itself (e.g. it goes fullscreen or gets minimized).
+  StateChange,
+
+  /// When the WM starts, for each window that is managed on startup.
+  Startup,
+
   /// When the title of a window changes.
   TitleChange,
+
+  /// When a window requests attention (e.g. by flashing its taskbar
+  /// button) while it isn't focused.
+  Urgent,
+
+  /// When a window is closed or hidden and is no longer managed. Commands
+  /// are run against the container that receives focus afterwards, and
+  /// the `monitor` and `workspace` matchers never match.
+  Unmanage,
+
+  /// When a window is moved to a different workspace (e.g. by a move
+  /// command or by being dragged).
+  WorkspaceChange,
 }
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
 #[serde(rename_all(serialize = "camelCase"))]
 pub struct WorkspaceConfig {
   pub name: String,
@@ -378,6 +1307,55 @@ pub struct WorkspaceConfig {
 
   #[serde(default = "default_bool::<false>")]
   pub keep_alive: bool,
+
+  /// Gaps to use instead of the global `gaps` config.
+  #[serde(default)]
+  pub gaps: Option<GapsConfig>,
+
+  /// Tiling direction of the workspace when it's activated. Defaults to
+  /// vertical for portrait monitors and horizontal otherwise.
+  #[serde(default)]
+  pub tiling_direction: Option<TilingDirection>,
+
+  /// How new tiling windows are inserted into the workspace.
+  #[serde(default)]
+  pub layout: WorkspaceLayout,
+
+  /// State to use for new windows instead of the global
+  /// `window_behavior.initial_state` config.
+  #[serde(default)]
+  pub initial_window_state: Option<InitialWindowState>,
+
+  /// Hide method to use instead of the global `general.hide_method`
+  /// config.
+  #[serde(default)]
+  pub hide_method: Option<HideMethod>,
+
+  /// Commands to run when the workspace is activated.
+  #[serde(default)]
+  pub on_activate: Vec<InvokeCommand>,
+
+  /// Commands to run when the workspace is deactivated.
+  #[serde(default)]
+  pub on_deactivate: Vec<InvokeCommand>,
+
+  /// Commands to run when the last window leaves the workspace.
+  #[serde(default)]
+  pub on_empty: Vec<InvokeCommand>,
+}
+
+#[derive(
+  Clone, Debug, Default, Deserialize, JsonSchema, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceLayout {
+  /// New windows are inserted next to the focused window.
+  #[default]
+  Tiling,
+
+  /// New windows split the focused window, alternating between
+  /// horizontal and vertical splits.
+  Dwindle,
 }
 
 /// Helper function for setting a default value for a boolean field.
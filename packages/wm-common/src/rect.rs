@@ -1,6 +1,24 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use serde::{Deserialize, Serialize};
 
-use super::{Direction, LengthValue, Point, RectDelta};
+use super::{Direction, GridPlacement, LengthValue, Point, RectDelta};
 
 #[derive(Debug, Deserialize, Clone, Serialize, Eq, PartialEq)]
 pub struct Rect {
@@ -96,6 +114,23 @@ impl Rect {
     )
   }
 
+  /// Translates this rect so that it lies within the bounds of the given
+  /// outer rectangle. Rects that are larger than the outer rectangle get
+  /// aligned to its top-left.
+  #[must_use]
+  pub fn translate_within(&self, outer_rect: &Rect) -> Self {
+    self.translate_to_coordinates(
+      self
+        .left
+        .min(outer_rect.right - self.width())
+        .max(outer_rect.left),
+      self
+        .top
+        .min(outer_rect.bottom - self.height())
+        .max(outer_rect.top),
+    )
+  }
+
   /// Returns a new `Rect` that is clamped within the bounds of the given
   /// outer rectangle. Attempts to preserve the width and height of the
   /// original rectangle.
@@ -202,4 +237,238 @@ impl Rect {
     #[allow(clippy::cast_precision_loss)]
     ((dx * dx + dy * dy) as f32).sqrt()
   }
+
+  /// Gets the index of the nearest rect in the given direction out of
+  /// the candidate rects.
+  ///
+  /// Candidates qualify if their center lies past this rect's center in
+  /// the given direction. Candidates that overlap with this rect on the
+  /// perpendicular axis are preferred, after which the nearest one is
+  /// picked by the gap between edges, and then by the offset between
+  /// centers on the perpendicular axis.
+  #[must_use]
+  pub fn nearest_in_direction(
+    &self,
+    direction: &Direction,
+    candidates: &[Rect],
+  ) -> Option<usize> {
+    let center = self.center_point();
+
+    candidates
+      .iter()
+      .enumerate()
+      .filter_map(|(index, candidate)| {
+        let candidate_center = candidate.center_point();
+
+        let (is_in_direction, edge_gap, has_overlap, center_offset) =
+          match direction {
+            Direction::Left => (
+              candidate_center.x < center.x,
+              self.left - candidate.right,
+              self.has_overlap_y(candidate),
+              (candidate_center.y - center.y).abs(),
+            ),
+            Direction::Right => (
+              candidate_center.x > center.x,
+              candidate.left - self.right,
+              self.has_overlap_y(candidate),
+              (candidate_center.y - center.y).abs(),
+            ),
+            Direction::Up => (
+              candidate_center.y < center.y,
+              self.top - candidate.bottom,
+              self.has_overlap_x(candidate),
+              (candidate_center.x - center.x).abs(),
+            ),
+            Direction::Down => (
+              candidate_center.y > center.y,
+              candidate.top - self.bottom,
+              self.has_overlap_x(candidate),
+              (candidate_center.x - center.x).abs(),
+            ),
+          };
+
+        // Overlapping rects have a negative gap, which is treated the
+        // same as adjacent rects.
+        is_in_direction.then_some((
+          index,
+          (!has_overlap, edge_gap.max(0), center_offset),
+        ))
+      })
+      .min_by_key(|(_, score)| *score)
+      .map(|(index, _)| index)
+  }
+
+  /// Gets the area of the given grid placement within this rect, with
+  /// `gap` pixels between adjacent cells.
+  #[must_use]
+  pub fn grid_cell(&self, placement: &GridPlacement, gap: i32) -> Rect {
+    let placement = placement.clamped();
+
+    #[allow(clippy::cast_possible_wrap)]
+    let (columns, rows) =
+      (placement.size.columns as i32, placement.size.rows as i32);
+
+    // Edge of the cell at the given index, where gaps are only added
+    // between cells and not at the outer edges.
+    let edge = |start: i32, length: i32, count: i32, index: i32| {
+      let cell_length = (length - gap * (count - 1)) / count;
+      start + index * (cell_length + gap)
+    };
+
+    #[allow(clippy::cast_possible_wrap)]
+    let (column, row, column_end, row_end) = (
+      placement.cell.column as i32,
+      placement.cell.row as i32,
+      (placement.cell.column + placement.span.column) as i32,
+      (placement.cell.row + placement.span.row) as i32,
+    );
+
+    let right = if column_end == columns {
+      self.right
+    } else {
+      edge(self.left, self.width(), columns, column_end) - gap
+    };
+
+    let bottom = if row_end == rows {
+      self.bottom
+    } else {
+      edge(self.top, self.height(), rows, row_end) - gap
+    };
+
+    Self::from_ltrb(
+      edge(self.left, self.width(), columns, column),
+      edge(self.top, self.height(), rows, row),
+      right,
+      bottom,
+    )
+  }
+
+  /// Translates this rect so that its edges align with the nearest edges
+  /// of the target rects, if within `threshold` pixels.
+  ///
+  /// Edges snap to both the inside and outside of each target, so that
+  /// a rect can be aligned to a workspace's bounds as well as placed
+  /// flush against a neighbouring window.
+  #[must_use]
+  pub fn snap_to_edges(&self, targets: &[Rect], threshold: i32) -> Self {
+    let nearest_offset = |edges: &[i32], target_edges: &[i32]| {
+      edges
+        .iter()
+        .flat_map(|edge| {
+          target_edges.iter().map(move |target| target - edge)
+        })
+        .filter(|offset| offset.abs() <= threshold)
+        .min_by_key(|offset| offset.abs())
+        .unwrap_or(0)
+    };
+
+    // Only consider targets that are alongside this rect on the
+    // perpendicular axis.
+    let x_targets = targets
+      .iter()
+      .filter(|target| self.has_overlap_y(target))
+      .flat_map(|target| [target.left, target.right])
+      .collect::<Vec<_>>();
+
+    let y_targets = targets
+      .iter()
+      .filter(|target| self.has_overlap_x(target))
+      .flat_map(|target| [target.top, target.bottom])
+      .collect::<Vec<_>>();
+
+    Self::from_xy(
+      self.x() + nearest_offset(&[self.left, self.right], &x_targets),
+      self.y() + nearest_offset(&[self.top, self.bottom], &y_targets),
+      self.width(),
+      self.height(),
+    )
+  }
+
+  /// Gets the area of the intersection between this rect and another
+  /// rect.
+  #[must_use]
+  pub fn overlap_area(&self, other: &Rect) -> i64 {
+    let width =
+      (self.right.min(other.right) - self.left.max(other.left)).max(0);
+
+    let height =
+      (self.bottom.min(other.bottom) - self.top.max(other.top)).max(0);
+
+    i64::from(width) * i64::from(height)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn nearest_in_direction_prefers_overlap_over_distance() {
+    let rect = Rect::from_xy(100, 100, 100, 100);
+
+    let candidates = [
+      // Nearer, but doesn't overlap vertically.
+      Rect::from_xy(210, 300, 100, 100),
+      // Overlapping, but further away and off-center.
+      Rect::from_xy(400, 150, 100, 100),
+      // Overlapping, with the same gap and closer to the center.
+      Rect::from_xy(400, 110, 100, 100),
+    ];
+
+    assert_eq!(
+      rect.nearest_in_direction(&Direction::Right, &candidates),
+      Some(2)
+    );
+
+    // Without overlapping candidates, the nearest one is picked.
+    assert_eq!(
+      rect.nearest_in_direction(&Direction::Right, &candidates[..1]),
+      Some(0)
+    );
+  }
+
+  #[test]
+  fn nearest_in_direction_without_candidates_in_direction() {
+    let rect = Rect::from_xy(100, 100, 100, 100);
+
+    let candidates = [
+      Rect::from_xy(0, 100, 50, 100),
+      Rect::from_xy(100, 300, 100, 100),
+    ];
+
+    assert_eq!(
+      rect.nearest_in_direction(&Direction::Right, &candidates),
+      None
+    );
+    assert_eq!(
+      rect.nearest_in_direction(&Direction::Up, &candidates),
+      None
+    );
+    assert_eq!(rect.nearest_in_direction(&Direction::Left, &[]), None);
+  }
+
+  #[test]
+  fn nearest_in_direction_from_floating_across_monitor_edge() {
+    // Floating window that spans the edge between two 1920x1080
+    // monitors.
+    let floating = Rect::from_xy(1800, 200, 300, 300);
+
+    let tiled = [
+      // Window filling the left monitor.
+      Rect::from_xy(0, 0, 1920, 1080),
+      // Windows tiled side by side on the right monitor.
+      Rect::from_xy(1920, 0, 960, 1080),
+      Rect::from_xy(2880, 0, 960, 1080),
+    ];
+
+    assert_eq!(
+      floating.nearest_in_direction(&Direction::Right, &tiled),
+      Some(1)
+    );
+    assert_eq!(
+      floating.nearest_in_direction(&Direction::Left, &tiled),
+      Some(0)
+    );
+  }
 }
@@ -0,0 +1,148 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+/// Minimum and maximum dimensions of a window in pixels. Dimensions that
+/// are `None` are unconstrained.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeConstraints {
+  pub min_width: Option<i32>,
+  pub min_height: Option<i32>,
+  pub max_width: Option<i32>,
+  pub max_height: Option<i32>,
+}
+
+impl SizeConstraints {
+  /// Gets the minimum width or height.
+  #[must_use]
+  pub fn min_length(&self, is_width: bool) -> Option<i32> {
+    if is_width {
+      self.min_width
+    } else {
+      self.min_height
+    }
+  }
+
+  /// Gets the maximum width or height.
+  #[must_use]
+  pub fn max_length(&self, is_width: bool) -> Option<i32> {
+    if is_width {
+      self.max_width
+    } else {
+      self.max_height
+    }
+  }
+
+  /// Returns a copy with the dimensions that are set in `overrides`
+  /// replaced.
+  #[must_use]
+  pub fn with_overrides(&self, overrides: &SizeConstraints) -> Self {
+    Self {
+      min_width: overrides.min_width.or(self.min_width),
+      min_height: overrides.min_height.or(self.min_height),
+      max_width: overrides.max_width.or(self.max_width),
+      max_height: overrides.max_height.or(self.max_height),
+    }
+  }
+}
+
+/// Distributes a length between items in proportion to their weights,
+/// while keeping each item within its `(min, max)` length constraint.
+///
+/// Space that constrained items can't take is redistributed to the
+/// remaining items. Items may exceed the total length if their minimum
+/// lengths don't fit.
+#[must_use]
+pub fn distribute_length(
+  total_length: f32,
+  weights: &[f32],
+  constraints: &[(Option<i32>, Option<i32>)],
+) -> Vec<f32> {
+  let mut lengths = vec![None; weights.len()];
+  let mut remaining_length = total_length;
+
+  loop {
+    let free_indices = (0..weights.len())
+      .filter(|&index| lengths[index].is_none())
+      .collect::<Vec<_>>();
+
+    if free_indices.is_empty() {
+      break;
+    }
+
+    let weight_sum = free_indices
+      .iter()
+      .map(|&index| weights[index])
+      .sum::<f32>();
+
+    #[allow(clippy::cast_precision_loss)]
+    let provisional_length = |index: usize| {
+      if weight_sum > 0. {
+        remaining_length * weights[index] / weight_sum
+      } else {
+        remaining_length / free_indices.len() as f32
+      }
+    };
+
+    // Get the lengths of items that violate their constraints, and how
+    // much they differ from their provisional length.
+    #[allow(clippy::cast_precision_loss)]
+    let violations = free_indices
+      .iter()
+      .filter_map(|&index| {
+        let length = provisional_length(index);
+        let (min, max) = constraints[index];
+
+        let clamped_length = length
+          .max(min.map_or(f32::MIN, |min| min as f32))
+          .min(max.map_or(f32::MAX, |max| max as f32));
+
+        ((clamped_length - length).abs() > f32::EPSILON).then_some((
+          index,
+          clamped_length,
+          clamped_length - length,
+        ))
+      })
+      .collect::<Vec<_>>();
+
+    if violations.is_empty() {
+      for &index in &free_indices {
+        lengths[index] = Some(provisional_length(index));
+      }
+
+      break;
+    }
+
+    // Fix either the items below their minimum or the items above their
+    // maximum, depending on which way the violations lean. The other
+    // items are re-evaluated with the remaining length.
+    let total_violation =
+      violations.iter().map(|(_, _, delta)| delta).sum::<f32>();
+
+    for (index, clamped_length, delta) in violations {
+      if (total_violation >= 0.) == (delta > 0.) {
+        lengths[index] = Some(clamped_length);
+        remaining_length -= clamped_length;
+      }
+    }
+  }
+
+  lengths.into_iter().map(Option::unwrap_or_default).collect()
+}
@@ -0,0 +1,60 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Color, CornerStyle, OpacityValue, TitleBarVisibility};
+
+/// Window effects that are set for an individual window (e.g. via the
+/// `set-effects` command), and which take precedence over the focused and
+/// unfocused window effects from the user config. Effects that are `None`
+/// aren't overridden.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowEffectOverrides {
+  pub border_color: Option<Color>,
+  pub corner_style: Option<CornerStyle>,
+  pub transparency: Option<OpacityValue>,
+  pub title_bar: Option<TitleBarVisibility>,
+}
+
+impl WindowEffectOverrides {
+  /// Returns a copy with the effects that are set in `overrides`
+  /// replaced.
+  #[must_use]
+  pub fn with_overrides(&self, overrides: &WindowEffectOverrides) -> Self {
+    Self {
+      border_color: overrides
+        .border_color
+        .clone()
+        .or_else(|| self.border_color.clone()),
+      corner_style: overrides
+        .corner_style
+        .clone()
+        .or_else(|| self.corner_style.clone()),
+      transparency: overrides
+        .transparency
+        .clone()
+        .or_else(|| self.transparency.clone()),
+      title_bar: overrides
+        .title_bar
+        .clone()
+        .or_else(|| self.title_bar.clone()),
+    }
+  }
+}
@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -41,7 +59,7 @@ pub enum WmEvent {
   UserConfigChanged {
     config_path: String,
     config_string: String,
-    parsed_config: ParsedConfig,
+    parsed_config: Box<ParsedConfig>,
   },
   WindowManaged {
     managed_window: ContainerDto,
@@ -50,6 +68,10 @@ pub enum WmEvent {
     unmanaged_id: Uuid,
     unmanaged_handle: isize,
   },
+  WindowUrgencyChanged {
+    updated_window: ContainerDto,
+    is_urgent: bool,
+  },
   WorkspaceActivated {
     activated_workspace: ContainerDto,
   },
@@ -0,0 +1,311 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  collections::{HashMap, VecDeque},
+  time::Duration,
+};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+  net::TcpStream,
+  sync::{mpsc, oneshot},
+};
+use tokio_tungstenite::{
+  connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream,
+};
+use uuid::Uuid;
+use wm_common::{
+  ClientResponseData, ClientResponseMessage, EventSubscriptionMessage,
+  ServerMessage,
+};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Delay before the first reconnection attempt. The delay is doubled
+/// after each failed attempt, up to `MAX_RECONNECT_DELAY`.
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// A message to send to the IPC server, along with where to route its
+/// response.
+pub(crate) struct Request {
+  pub message: String,
+  pub waiter: Waiter,
+}
+
+pub(crate) enum Waiter {
+  /// Receives the response to a message.
+  Response(oneshot::Sender<ClientResponseMessage>),
+
+  /// Receives the response to a subscribe message, as well as the events
+  /// of the resulting subscription.
+  Subscription {
+    response_tx: Option<oneshot::Sender<ClientResponseMessage>>,
+    event_tx: mpsc::UnboundedSender<EventSubscriptionMessage>,
+  },
+}
+
+/// An active event subscription.
+struct Subscription {
+  /// Subscribe message that created the subscription. Resent on
+  /// reconnection.
+  message: String,
+  event_tx: mpsc::UnboundedSender<EventSubscriptionMessage>,
+}
+
+enum ConnectionEnd {
+  /// All `IpcClient` handles have been dropped.
+  ClientDropped,
+  /// Connection to the IPC server was lost.
+  Disconnected,
+}
+
+/// Background task that owns the connection to the IPC server.
+///
+/// Responses are routed to the waiter of the matching client message in
+/// the order the messages were sent, and events are routed by their
+/// subscription ID. This way, events aren't lost while waiting on a
+/// response and vice versa.
+pub(crate) struct Connection {
+  server_addr: String,
+  reconnect: bool,
+  request_rx: mpsc::UnboundedReceiver<Request>,
+
+  /// Waiters for responses, keyed by the client message they're for.
+  pending: HashMap<String, VecDeque<Waiter>>,
+
+  /// Active subscriptions, keyed by subscription ID.
+  subscriptions: HashMap<Uuid, Subscription>,
+}
+
+impl Connection {
+  pub fn new(
+    server_addr: String,
+    reconnect: bool,
+    request_rx: mpsc::UnboundedReceiver<Request>,
+  ) -> Self {
+    Self {
+      server_addr,
+      reconnect,
+      request_rx,
+      pending: HashMap::new(),
+      subscriptions: HashMap::new(),
+    }
+  }
+
+  pub async fn run(mut self, mut stream: WsStream) {
+    loop {
+      if let ConnectionEnd::ClientDropped = self.process(&mut stream).await
+      {
+        _ = stream.close(None).await;
+        return;
+      }
+
+      // Dropping the pending waiters makes their requests fail instead of
+      // waiting on responses that will never arrive.
+      self.pending.clear();
+
+      if !self.reconnect {
+        return;
+      }
+
+      let Some(new_stream) = self.reconnect().await else {
+        return;
+      };
+
+      stream = new_stream;
+      self.resubscribe(&mut stream).await;
+    }
+  }
+
+  /// Processes outgoing requests and incoming server messages until the
+  /// connection ends.
+  async fn process(&mut self, stream: &mut WsStream) -> ConnectionEnd {
+    loop {
+      tokio::select! {
+        request = self.request_rx.recv() => {
+          let Some(request) = request else {
+            return ConnectionEnd::ClientDropped;
+          };
+
+          if stream
+            .send(Message::Text(request.message.clone().into()))
+            .await
+            .is_err()
+          {
+            return ConnectionEnd::Disconnected;
+          }
+
+          self
+            .pending
+            .entry(request.message)
+            .or_default()
+            .push_back(request.waiter);
+        }
+        message = stream.next() => match message {
+          Some(Ok(Message::Text(text))) => {
+            if let Ok(server_message) =
+              serde_json::from_str::<ServerMessage>(&text)
+            {
+              self.route_server_message(server_message, stream).await;
+            }
+          }
+          Some(Ok(Message::Close(_)) | Err(_)) | None => {
+            return ConnectionEnd::Disconnected;
+          }
+          Some(Ok(_)) => {}
+        },
+      }
+    }
+  }
+
+  async fn route_server_message(
+    &mut self,
+    server_message: ServerMessage,
+    stream: &mut WsStream,
+  ) {
+    match server_message {
+      ServerMessage::ClientResponse(response) => {
+        // Responses without a waiter (e.g. to unsubscribe messages sent
+        // by this task) are ignored.
+        let Some(waiter) = self
+          .pending
+          .get_mut(&response.client_message)
+          .and_then(VecDeque::pop_front)
+        else {
+          return;
+        };
+
+        match waiter {
+          Waiter::Response(response_tx) => {
+            _ = response_tx.send(response);
+          }
+          Waiter::Subscription {
+            response_tx,
+            event_tx,
+          } => {
+            if let Some(ClientResponseData::EventSubscribe(data)) =
+              &response.data
+            {
+              self.subscriptions.insert(
+                data.subscription_id,
+                Subscription {
+                  message: response.client_message.clone(),
+                  event_tx,
+                },
+              );
+            }
+
+            if let Some(response_tx) = response_tx {
+              _ = response_tx.send(response);
+            }
+          }
+        }
+      }
+      ServerMessage::EventSubscription(event) => {
+        let subscription_id = event.subscription_id;
+
+        let Some(subscription) = self.subscriptions.get(&subscription_id)
+        else {
+          return;
+        };
+
+        // Unsubscribe once the receiving end of the subscription has been
+        // dropped.
+        if subscription.event_tx.send(event).is_err() {
+          self.subscriptions.remove(&subscription_id);
+
+          _ = stream
+            .send(Message::Text(
+              format!("unsub --id {subscription_id}").into(),
+            ))
+            .await;
+        }
+      }
+    }
+  }
+
+  /// Reconnects to the IPC server with exponential backoff.
+  ///
+  /// Returns `None` if all `IpcClient` handles are dropped while waiting
+  /// to reconnect.
+  async fn reconnect(&mut self) -> Option<WsStream> {
+    let mut delay = INITIAL_RECONNECT_DELAY;
+
+    loop {
+      // Requests shouldn't restart the timer, otherwise a steady stream of
+      // requests would prevent reconnecting.
+      let deadline = tokio::time::Instant::now() + delay;
+      let sleep = tokio::time::sleep_until(deadline);
+      tokio::pin!(sleep);
+
+      loop {
+        tokio::select! {
+          () = &mut sleep => break,
+          request = self.request_rx.recv() => {
+            // Requests made while disconnected fail immediately.
+            drop(request?);
+          }
+        }
+      }
+
+      if let Ok((stream, _)) = connect_async(&self.server_addr).await {
+        return Some(stream);
+      }
+
+      delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+  }
+
+  /// Resends the subscribe messages of active subscriptions after a
+  /// reconnection. Events of the new subscriptions are routed to the
+  /// existing receivers.
+  ///
+  /// If the connection drops midway, all subscriptions are kept so that
+  /// the next reconnection resends them.
+  async fn resubscribe(&mut self, stream: &mut WsStream) {
+    let subscriptions = std::mem::take(&mut self.subscriptions)
+      .into_iter()
+      .filter(|(_, subscription)| !subscription.event_tx.is_closed())
+      .collect::<Vec<_>>();
+
+    for (_, subscription) in &subscriptions {
+      if stream
+        .send(Message::Text(subscription.message.clone().into()))
+        .await
+        .is_err()
+      {
+        // Subscriptions that were already resent are kept as well, since
+        // their waiters are dropped on disconnection.
+        self.subscriptions = subscriptions.into_iter().collect();
+        return;
+      }
+
+      self
+        .pending
+        .entry(subscription.message.clone())
+        .or_default()
+        .push_back(Waiter::Subscription {
+          response_tx: None,
+          event_tx: subscription.event_tx.clone(),
+        });
+    }
+  }
+}
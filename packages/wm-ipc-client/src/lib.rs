@@ -1,85 +1,359 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 #![allow(clippy::missing_errors_doc)]
 
-use anyhow::Context;
-use futures_util::{SinkExt, StreamExt};
-use tokio::net::TcpStream;
-use tokio_tungstenite::{
-  connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream,
-};
+mod connection;
+
+use std::{iter, time::Duration};
+
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use futures_util::{stream, Stream};
+use schemars::schema::RootSchema;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::connect_async;
 use uuid::Uuid;
 use wm_common::{
-  ClientResponseMessage, EventSubscriptionMessage, ServerMessage,
-  DEFAULT_IPC_PORT,
+  AppMetadataData, BindingModeConfig, ClientResponseData,
+  ClientResponseMessage, CommandSchema, ContainerDto,
+  EventSubscriptionMessage, InvokeCommand, MetricsDto, SubscribableEvent,
+  TilingDirectionData, WmEvent, DEFAULT_IPC_PORT,
 };
 
+use crate::connection::{Connection, Request, Waiter};
+
+/// Options for connecting to the IPC server.
+#[derive(Clone, Debug)]
+pub struct IpcClientOptions {
+  /// Port of the IPC server.
+  pub port: u32,
+
+  /// How long to wait for a response before a request fails.
+  pub timeout: Duration,
+
+  /// Whether to reconnect when the connection to the IPC server is lost.
+  /// Active subscriptions are resubscribed to on reconnection.
+  pub reconnect: bool,
+}
+
+impl Default for IpcClientOptions {
+  fn default() -> Self {
+    Self {
+      port: DEFAULT_IPC_PORT,
+      timeout: Duration::from_secs(5),
+      reconnect: true,
+    }
+  }
+}
+
+/// Client for the IPC server of the window manager.
+///
+/// The connection is owned by a background task, which routes responses
+/// and events to their respective waiters. This allows queries and
+/// commands to be sent while event subscriptions are active. The task
+/// exits once all clones of the client are dropped, which also ends any
+/// subscription streams.
+#[derive(Clone, Debug)]
 pub struct IpcClient {
-  stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+  request_tx: mpsc::UnboundedSender<Request>,
+  timeout: Duration,
 }
 
 impl IpcClient {
+  /// Connects to the IPC server with the default options.
   pub async fn connect() -> anyhow::Result<Self> {
-    let server_addr = format!("ws://127.0.0.1:{DEFAULT_IPC_PORT}");
+    Self::connect_with_options(IpcClientOptions::default()).await
+  }
+
+  /// Connects to the IPC server.
+  pub async fn connect_with_options(
+    options: IpcClientOptions,
+  ) -> anyhow::Result<Self> {
+    let server_addr = format!("ws://127.0.0.1:{}", options.port);
 
-    let (stream, _) = connect_async(server_addr)
+    let (stream, _) = connect_async(&server_addr)
       .await
       .context("Failed to connect to IPC server.")?;
 
-    Ok(Self { stream })
+    let (request_tx, request_rx) = mpsc::unbounded_channel();
+
+    let connection =
+      Connection::new(server_addr, options.reconnect, request_rx);
+
+    tokio::spawn(connection.run(stream));
+
+    Ok(Self {
+      request_tx,
+      timeout: options.timeout,
+    })
   }
 
-  /// Sends a message to the IPC server.
-  pub async fn send(&mut self, message: &str) -> anyhow::Result<()> {
+  /// Sends a raw message (e.g. `query windows`) to the IPC server and
+  /// waits for its response.
+  pub async fn send_message(
+    &self,
+    message: &str,
+  ) -> anyhow::Result<ClientResponseMessage> {
+    let (response_tx, response_rx) = oneshot::channel();
+
+    self.request(message, Waiter::Response(response_tx))?;
+    self.wait_for_response(response_rx).await
+  }
+
+  /// Sends a raw subscribe message (e.g. `sub -e focus_changed`) to the
+  /// IPC server.
+  ///
+  /// Returns the response to the subscribe message, along with a stream
+  /// of the subscription's events. Dropping the stream unsubscribes.
+  pub async fn subscribe_message(
+    &self,
+    message: &str,
+  ) -> anyhow::Result<(
+    ClientResponseMessage,
+    impl Stream<Item = EventSubscriptionMessage>,
+  )> {
+    let (response_tx, response_rx) = oneshot::channel();
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+
+    self.request(
+      message,
+      Waiter::Subscription {
+        response_tx: Some(response_tx),
+        event_tx,
+      },
+    )?;
+
+    let response = self.wait_for_response(response_rx).await?;
+
+    let events = stream::unfold(event_rx, |mut event_rx| async move {
+      event_rx.recv().await.map(|event| (event, event_rx))
+    });
+
+    Ok((response, events))
+  }
+
+  /// Runs a WM command.
+  ///
+  /// Returns the ID of the container the command was run on.
+  pub async fn command(
+    &self,
+    command: &InvokeCommand,
+    subject_container_id: Option<Uuid>,
+  ) -> anyhow::Result<Uuid> {
     self
-      .stream
-      .send(Message::Text(message.into()))
+      .command_str(&command.to_string(), subject_container_id)
       .await
-      .context("Failed to send command.")?;
+  }
+
+  /// Runs a WM command given as a string (e.g. `focus --direction left`).
+  ///
+  /// The command is validated locally before being sent.
+  ///
+  /// Returns the ID of the container the command was run on.
+  pub async fn command_str(
+    &self,
+    command: &str,
+    subject_container_id: Option<Uuid>,
+  ) -> anyhow::Result<Uuid> {
+    InvokeCommand::try_parse_from(
+      iter::once("").chain(command.split_whitespace()),
+    )
+    .with_context(|| format!("Invalid command '{command}'."))?;
+
+    let message = match subject_container_id {
+      Some(id) => format!("command --id {id} {command}"),
+      None => format!("command {command}"),
+    };
 
-    Ok(())
+    match self.send_data(&message).await? {
+      ClientResponseData::Command(data) => Ok(data.subject_container_id),
+      _ => bail!("Unexpected response to command '{command}'."),
+    }
   }
 
-  /// Waits and returns the next reply from the IPC server.
-  pub async fn next_message(&mut self) -> anyhow::Result<ServerMessage> {
-    let response = self
-      .stream
-      .next()
-      .await
-      .context("Failed to receive response.")?
-      .context("Invalid response message.")?;
+  /// Subscribes to WM events.
+  ///
+  /// Returns a stream of the subscribed events. The subscription is
+  /// unsubscribed from once the stream is dropped.
+  pub async fn subscribe(
+    &self,
+    events: &[SubscribableEvent],
+  ) -> anyhow::Result<impl Stream<Item = WmEvent>> {
+    let event_names = events
+      .iter()
+      .filter_map(ValueEnum::to_possible_value)
+      .map(|value| value.get_name().to_string())
+      .collect::<Vec<_>>();
 
-    let json_response =
-      serde_json::from_str::<ServerMessage>(response.to_text()?)?;
+    if event_names.is_empty() {
+      bail!("No events to subscribe to.");
+    }
 
-    Ok(json_response)
+    let message = format!("sub -e {}", event_names.join(" "));
+    let (response, events) = self.subscribe_message(&message).await?;
+
+    if !response.success {
+      bail!(error_message(&response));
+    }
+
+    Ok(futures_util::StreamExt::filter_map(
+      events,
+      |event| async move { event.data },
+    ))
   }
 
-  pub async fn client_response(
-    &mut self,
-    client_message: &str,
-  ) -> Option<ClientResponseMessage> {
-    while let Ok(response) = self.next_message().await {
-      if let ServerMessage::ClientResponse(client_response) = response {
-        if client_response.client_message == client_message {
-          return Some(client_response);
-        }
-      }
+  pub async fn query_app_metadata(
+    &self,
+  ) -> anyhow::Result<AppMetadataData> {
+    match self.send_data("query app-metadata").await? {
+      ClientResponseData::AppMetadata(data) => Ok(data),
+      _ => bail!("Unexpected response to app metadata query."),
     }
+  }
 
-    None
+  pub async fn query_binding_modes(
+    &self,
+  ) -> anyhow::Result<Vec<BindingModeConfig>> {
+    match self.send_data("query binding-modes").await? {
+      ClientResponseData::BindingModes(data) => Ok(data.binding_modes),
+      _ => bail!("Unexpected response to binding modes query."),
+    }
   }
 
-  pub async fn event_subscription(
-    &mut self,
-    subscription_id: &Uuid,
-  ) -> Option<EventSubscriptionMessage> {
-    while let Ok(response) = self.next_message().await {
-      if let ServerMessage::EventSubscription(event_sub) = response {
-        if &event_sub.subscription_id == subscription_id {
-          return Some(event_sub);
-        }
-      }
+  pub async fn query_commands(
+    &self,
+  ) -> anyhow::Result<Vec<CommandSchema>> {
+    match self.send_data("query commands").await? {
+      ClientResponseData::Commands(data) => Ok(data.commands),
+      _ => bail!("Unexpected response to commands query."),
     }
+  }
 
-    None
+  pub async fn query_config_schema(&self) -> anyhow::Result<RootSchema> {
+    match self.send_data("query config-schema").await? {
+      ClientResponseData::ConfigSchema(data) => Ok(data.config_schema),
+      _ => bail!("Unexpected response to config schema query."),
+    }
+  }
+
+  pub async fn query_focused(&self) -> anyhow::Result<ContainerDto> {
+    match self.send_data("query focused").await? {
+      ClientResponseData::Focused(data) => Ok(data.focused),
+      _ => bail!("Unexpected response to focused query."),
+    }
+  }
+
+  pub async fn query_focus_history(
+    &self,
+  ) -> anyhow::Result<Vec<ContainerDto>> {
+    match self.send_data("query focus-history").await? {
+      ClientResponseData::FocusHistory(data) => Ok(data.focus_history),
+      _ => bail!("Unexpected response to focus history query."),
+    }
+  }
+
+  pub async fn query_metrics(&self) -> anyhow::Result<MetricsDto> {
+    match self.send_data("query metrics").await? {
+      ClientResponseData::Metrics(data) => Ok(data.metrics),
+      _ => bail!("Unexpected response to metrics query."),
+    }
+  }
+
+  pub async fn query_monitors(&self) -> anyhow::Result<Vec<ContainerDto>> {
+    match self.send_data("query monitors").await? {
+      ClientResponseData::Monitors(data) => Ok(data.monitors),
+      _ => bail!("Unexpected response to monitors query."),
+    }
+  }
+
+  pub async fn query_paused(&self) -> anyhow::Result<bool> {
+    match self.send_data("query paused").await? {
+      ClientResponseData::Paused(is_paused) => Ok(is_paused),
+      _ => bail!("Unexpected response to paused query."),
+    }
+  }
+
+  pub async fn query_tiling_direction(
+    &self,
+  ) -> anyhow::Result<TilingDirectionData> {
+    match self.send_data("query tiling-direction").await? {
+      ClientResponseData::TilingDirection(data) => Ok(data),
+      _ => bail!("Unexpected response to tiling direction query."),
+    }
   }
+
+  pub async fn query_windows(&self) -> anyhow::Result<Vec<ContainerDto>> {
+    match self.send_data("query windows").await? {
+      ClientResponseData::Windows(data) => Ok(data.windows),
+      _ => bail!("Unexpected response to windows query."),
+    }
+  }
+
+  pub async fn query_workspaces(
+    &self,
+  ) -> anyhow::Result<Vec<ContainerDto>> {
+    match self.send_data("query workspaces").await? {
+      ClientResponseData::Workspaces(data) => Ok(data.workspaces),
+      _ => bail!("Unexpected response to workspaces query."),
+    }
+  }
+
+  /// Sends a message and gets the data of a successful response.
+  async fn send_data(
+    &self,
+    message: &str,
+  ) -> anyhow::Result<ClientResponseData> {
+    let response = self.send_message(message).await?;
+
+    if !response.success {
+      bail!(error_message(&response));
+    }
+
+    response
+      .data
+      .with_context(|| format!("No data in response to '{message}'."))
+  }
+
+  fn request(&self, message: &str, waiter: Waiter) -> anyhow::Result<()> {
+    self
+      .request_tx
+      .send(Request {
+        message: message.to_string(),
+        waiter,
+      })
+      .ok()
+      .context("IPC connection closed.")
+  }
+
+  async fn wait_for_response(
+    &self,
+    response_rx: oneshot::Receiver<ClientResponseMessage>,
+  ) -> anyhow::Result<ClientResponseMessage> {
+    tokio::time::timeout(self.timeout, response_rx)
+      .await
+      .context("Timed out waiting for response from IPC server.")?
+      .context("IPC connection closed before receiving a response.")
+  }
+}
+
+fn error_message(response: &ClientResponseMessage) -> String {
+  response.error.clone().unwrap_or_else(|| {
+    format!("Request '{}' failed.", response.client_message)
+  })
 }
@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use tokio::sync::mpsc::{self, UnboundedReceiver};
 use wm_common::{
   BindingModeConfig, InvokeCommand, KeybindingConfig, ParsedConfig, Point,
@@ -9,8 +27,12 @@ use super::{EventWindow, NativeWindow};
 pub enum PlatformEvent {
   DisplaySettingsChanged,
   KeybindingTriggered(KeybindingConfig),
+  /// All modifier keys have been released.
+  ModifiersReleased,
   MouseMove(MouseMoveEvent),
   WindowDestroyed(NativeWindow),
+  /// A window is requesting attention by flashing its taskbar button.
+  WindowFlashed(NativeWindow),
   WindowFocused(NativeWindow),
   WindowHidden(NativeWindow),
   WindowLocationChanged(NativeWindow),
@@ -22,6 +44,53 @@ pub enum PlatformEvent {
   WindowTitleChanged(NativeWindow),
 }
 
+impl PlatformEvent {
+  /// Gets the name of the event type (e.g. `window_focused`).
+  #[must_use]
+  pub fn kind(&self) -> &'static str {
+    match self {
+      PlatformEvent::DisplaySettingsChanged => "display_settings_changed",
+      PlatformEvent::KeybindingTriggered(_) => "keybinding_triggered",
+      PlatformEvent::ModifiersReleased => "modifiers_released",
+      PlatformEvent::MouseMove(_) => "mouse_move",
+      PlatformEvent::WindowDestroyed(_) => "window_destroyed",
+      PlatformEvent::WindowFlashed(_) => "window_flashed",
+      PlatformEvent::WindowFocused(_) => "window_focused",
+      PlatformEvent::WindowHidden(_) => "window_hidden",
+      PlatformEvent::WindowLocationChanged(_) => "window_location_changed",
+      PlatformEvent::WindowMinimized(_) => "window_minimized",
+      PlatformEvent::WindowMinimizeEnded(_) => "window_minimize_ended",
+      PlatformEvent::WindowMovedOrResizedEnd(_) => {
+        "window_moved_or_resized_end"
+      }
+      PlatformEvent::WindowMovedOrResizedStart(_) => {
+        "window_moved_or_resized_start"
+      }
+      PlatformEvent::WindowShown(_) => "window_shown",
+      PlatformEvent::WindowTitleChanged(_) => "window_title_changed",
+    }
+  }
+
+  /// Gets the window that the event is for, if any.
+  #[must_use]
+  pub fn window(&self) -> Option<&NativeWindow> {
+    match self {
+      PlatformEvent::WindowDestroyed(window)
+      | PlatformEvent::WindowFlashed(window)
+      | PlatformEvent::WindowFocused(window)
+      | PlatformEvent::WindowHidden(window)
+      | PlatformEvent::WindowLocationChanged(window)
+      | PlatformEvent::WindowMinimized(window)
+      | PlatformEvent::WindowMinimizeEnded(window)
+      | PlatformEvent::WindowMovedOrResizedEnd(window)
+      | PlatformEvent::WindowMovedOrResizedStart(window)
+      | PlatformEvent::WindowShown(window)
+      | PlatformEvent::WindowTitleChanged(window) => Some(window),
+      _ => None,
+    }
+  }
+}
+
 #[derive(Debug, Clone)]
 pub struct MouseMoveEvent {
   /// Location of mouse with 0,0 being the top-left corner of the primary
@@ -85,4 +154,10 @@ impl EventListener {
       .event_window
       .update(keybindings, config.general.focus_follows_cursor && !paused);
   }
+
+  /// Emits a `ModifiersReleased` event the next time all modifier keys
+  /// are released (e.g. to end a cycle through recent windows).
+  pub fn arm_modifier_release(&self) {
+    self.event_window.arm_modifier_release();
+  }
 }
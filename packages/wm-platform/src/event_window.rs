@@ -1,6 +1,24 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::{
   sync::{
-    atomic::{AtomicBool, AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
     Arc, OnceLock,
   },
   thread::{self, JoinHandle},
@@ -9,34 +27,42 @@ use std::{
 
 use tokio::sync::mpsc;
 use tracing::{info, warn};
-use windows::Win32::{
-  Devices::HumanInterfaceDevice::{
-    HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
-  },
-  Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
-  UI::{
-    Input::{
-      GetRawInputData, RegisterRawInputDevices, HRAWINPUT, RAWINPUT,
-      RAWINPUTDEVICE, RAWINPUTHEADER, RIDEV_INPUTSINK, RID_INPUT,
-      RIM_TYPEMOUSE,
+use windows::{
+  core::w,
+  Win32::{
+    Devices::HumanInterfaceDevice::{
+      HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
     },
-    WindowsAndMessaging::{
-      DefWindowProcW, DestroyWindow, GetCursorPos, DBT_DEVNODES_CHANGED,
-      PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, PBT_APMSUSPEND,
-      RI_MOUSE_LEFT_BUTTON_DOWN, RI_MOUSE_LEFT_BUTTON_UP,
-      RI_MOUSE_RIGHT_BUTTON_DOWN, RI_MOUSE_RIGHT_BUTTON_UP,
-      SPI_ICONVERTICALSPACING, SPI_SETWORKAREA, WM_DEVICECHANGE,
-      WM_DISPLAYCHANGE, WM_INPUT, WM_POWERBROADCAST, WM_SETTINGCHANGE,
+    Foundation::{HWND, LPARAM, LRESULT, POINT, WPARAM},
+    UI::{
+      Input::{
+        GetRawInputData, RegisterRawInputDevices, HRAWINPUT, RAWINPUT,
+        RAWINPUTDEVICE, RAWINPUTHEADER, RIDEV_INPUTSINK, RID_INPUT,
+        RIM_TYPEMOUSE,
+      },
+      WindowsAndMessaging::{
+        DefWindowProcW, DeregisterShellHookWindow, DestroyWindow,
+        GetCursorPos, RegisterShellHookWindow, RegisterWindowMessageW,
+        DBT_DEVNODES_CHANGED, HSHELL_HIGHBIT, HSHELL_REDRAW,
+        PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, PBT_APMSUSPEND,
+        RI_MOUSE_LEFT_BUTTON_DOWN, RI_MOUSE_LEFT_BUTTON_UP,
+        RI_MOUSE_RIGHT_BUTTON_DOWN, RI_MOUSE_RIGHT_BUTTON_UP,
+        SPI_ICONVERTICALSPACING, SPI_SETWORKAREA, WM_DEVICECHANGE,
+        WM_DISPLAYCHANGE, WM_INPUT, WM_POWERBROADCAST, WM_SETTINGCHANGE,
+      },
     },
   },
 };
 use wm_common::{KeybindingConfig, Point};
 
 use super::{
-  KeyboardHook, MouseMoveEvent, Platform, PlatformEvent, WindowEventHook,
-  FOREGROUND_INPUT_IDENTIFIER,
+  KeyboardHook, MouseMoveEvent, NativeWindow, Platform, PlatformEvent,
+  WindowEventHook, FOREGROUND_INPUT_IDENTIFIER,
 };
 
+/// Shell hook code sent when a window flashes its taskbar button.
+const HSHELL_FLASH: u32 = HSHELL_REDRAW | HSHELL_HIGHBIT;
+
 /// Global instance of sender for platform events.
 ///
 /// For use with window procedure.
@@ -68,6 +94,12 @@ static IS_R_MOUSE_DOWN: AtomicBool = AtomicBool::new(false);
 /// For use with window procedure.
 static LAST_MOUSE_EVENT_TIME: AtomicU64 = AtomicU64::new(0);
 
+/// Message ID of shell hook messages. Is 0 if registering for shell hook
+/// messages failed.
+///
+/// For use with window procedure.
+static SHELL_HOOK_MESSAGE: AtomicU32 = AtomicU32::new(0);
+
 #[derive(Debug)]
 pub struct EventWindow {
   keyboard_hook: Arc<KeyboardHook>,
@@ -121,9 +153,21 @@ impl EventWindow {
         )
       }?;
 
+      // Register our window to receive shell hook messages (e.g. when a
+      // window flashes its taskbar button).
+      let shell_hook_message =
+        unsafe { RegisterWindowMessageW(w!("SHELLHOOK")) };
+
+      if unsafe { RegisterShellHookWindow(HWND(handle)) }.as_bool() {
+        SHELL_HOOK_MESSAGE.store(shell_hook_message, Ordering::Relaxed);
+      } else {
+        warn!("Failed to register shell hook window.");
+      }
+
       Platform::run_message_loop();
 
       // Clean-up on message loop exit.
+      unsafe { DeregisterShellHookWindow(HWND(handle)) };
       unsafe { DestroyWindow(HWND(handle)) }?;
       keyboard_hook_clone.stop()?;
       window_event_hook.stop()?;
@@ -146,6 +190,12 @@ impl EventWindow {
     ENABLE_MOUSE_EVENTS.store(enable_mouse_events, Ordering::Relaxed);
   }
 
+  /// Emits a `ModifiersReleased` event the next time all modifier keys
+  /// are released.
+  pub fn arm_modifier_release(&self) {
+    self.keyboard_hook.arm_modifier_release();
+  }
+
   /// Destroys the event window and stops the message loop.
   pub fn destroy(&mut self) -> anyhow::Result<()> {
     info!("Shutting down event window.");
@@ -219,6 +269,22 @@ pub extern "system" fn event_window_proc(
 
         LRESULT(0)
       }
+      _ if message != 0
+        && message == SHELL_HOOK_MESSAGE.load(Ordering::Relaxed) =>
+      {
+        #[allow(clippy::cast_possible_truncation)]
+        if wparam.0 as u32 == HSHELL_FLASH {
+          let native_window = NativeWindow::new(lparam.0);
+
+          if let Err(err) =
+            event_tx.send(PlatformEvent::WindowFlashed(native_window))
+          {
+            warn!("Failed to send window flash event: {}", err);
+          }
+        }
+
+        LRESULT(0)
+      }
       _ => unsafe { DefWindowProcW(handle, message, wparam, lparam) },
     };
   }
@@ -1,6 +1,27 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::{
   collections::HashMap,
-  sync::{Arc, Mutex, OnceLock},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, OnceLock,
+  },
 };
 
 use tokio::sync::mpsc;
@@ -32,11 +53,12 @@ use windows::Win32::{
     },
     WindowsAndMessaging::{
       CallNextHookEx, SetWindowsHookExW, UnhookWindowsHookEx, HHOOK,
-      KBDLLHOOKSTRUCT, WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
+      KBDLLHOOKSTRUCT, WH_KEYBOARD_LL, WM_KEYDOWN, WM_KEYUP,
+      WM_SYSKEYDOWN, WM_SYSKEYUP,
     },
   },
 };
-use wm_common::KeybindingConfig;
+use wm_common::{InvokeCommand, KeybindingConfig};
 
 use super::PlatformEvent;
 
@@ -55,6 +77,10 @@ const MODIFIER_KEYS: [u16; 6] = [
   VK_RMENU.0,
 ];
 
+/// Windows keys, which count as modifiers when detecting that all
+/// modifier keys have been released.
+const WIN_KEYS: [u16; 2] = [VK_LWIN.0, VK_RWIN.0];
+
 #[derive(Debug)]
 pub struct ActiveKeybinding {
   pub vk_codes: Vec<u16>,
@@ -73,6 +99,10 @@ pub struct KeyboardHook {
   /// final key in a key combination.
   keybindings_by_trigger_key:
     Arc<Mutex<HashMap<u16, Vec<ActiveKeybinding>>>>,
+
+  /// Whether to emit a `ModifiersReleased` event once all modifier keys
+  /// are released, i.e. while cycling through recent windows.
+  is_modifier_release_armed: AtomicBool,
 }
 
 impl KeyboardHook {
@@ -87,6 +117,7 @@ impl KeyboardHook {
       keybindings_by_trigger_key: Arc::new(Mutex::new(
         Self::keybindings_by_trigger_key(keybindings),
       )),
+      is_modifier_release_armed: AtomicBool::new(false),
     });
 
     KEYBOARD_HOOK
@@ -121,6 +152,18 @@ impl KeyboardHook {
       Self::keybindings_by_trigger_key(keybindings);
   }
 
+  /// Emits a `ModifiersReleased` event the next time all modifier keys
+  /// are released.
+  ///
+  /// Keybindings that cycle through recent windows do this on their own,
+  /// so that releasing the modifiers can't be missed before the WM has
+  /// started the cycle.
+  pub fn arm_modifier_release(&self) {
+    self
+      .is_modifier_release_armed
+      .store(true, Ordering::Relaxed);
+  }
+
   /// Stops the low-level keyboard hook.
   ///
   /// # Panics
@@ -392,6 +435,15 @@ impl KeyboardHook {
           return false;
         }
 
+        let is_cycle_recent =
+          longest_keybinding.config.commands.iter().any(|command| {
+            matches!(command, InvokeCommand::Focus(args) if args.cycle_recent)
+          });
+
+        if is_cycle_recent {
+          self.arm_modifier_release();
+        }
+
         // Invoke the callback function for the longest matching
         // keybinding.
         let _ = self.event_tx.send(PlatformEvent::KeybindingTriggered(
@@ -403,6 +455,27 @@ impl KeyboardHook {
     }
   }
 
+  /// Emits a platform event if the released key was the last modifier
+  /// key being held down, and a modifier release has been armed.
+  fn handle_key_up_event(&self, vk_code: u16) {
+    let mut modifier_keys = MODIFIER_KEYS.iter().chain(&WIN_KEYS);
+
+    if !modifier_keys.clone().any(|&key| key == vk_code) {
+      return;
+    }
+
+    let has_modifier_keys_down = modifier_keys
+      .any(|&key| key != vk_code && Self::is_key_down_raw(key));
+
+    if !has_modifier_keys_down
+      && self
+        .is_modifier_release_armed
+        .swap(false, Ordering::Relaxed)
+    {
+      let _ = self.event_tx.send(PlatformEvent::ModifiersReleased);
+    }
+  }
+
   /// Gets the generic key code for a given key code.
   fn generic_key(key: u16) -> u16 {
     match VIRTUAL_KEY(key) {
@@ -445,20 +518,31 @@ extern "system" fn keyboard_hook_proc(
   lparam: LPARAM,
 ) -> LRESULT {
   #[allow(clippy::cast_possible_truncation)]
-  let should_ignore = code != 0
-    || !(wparam.0 as u32 == WM_KEYDOWN
-      || wparam.0 as u32 == WM_SYSKEYDOWN);
+  let message = wparam.0 as u32;
+
+  let is_key_down = message == WM_KEYDOWN || message == WM_SYSKEYDOWN;
+  let is_key_up = message == WM_KEYUP || message == WM_SYSKEYUP;
 
   // If the code is less than zero, the hook procedure must pass the hook
   // notification directly to other applications. We also only care about
-  // keydown events.
-  if should_ignore {
+  // keydown and keyup events.
+  if code != 0 || !(is_key_down || is_key_up) {
     return unsafe { CallNextHookEx(None, code, wparam, lparam) };
   }
 
   // Get struct with keyboard input event.
   let input = unsafe { *(lparam.0 as *const KBDLLHOOKSTRUCT) };
 
+  // Keyup events are never blocked.
+  if is_key_up {
+    if let Some(hook) = KEYBOARD_HOOK.get() {
+      #[allow(clippy::cast_possible_truncation)]
+      hook.handle_key_up_event(input.vkCode as u16);
+    }
+
+    return unsafe { CallNextHookEx(None, code, wparam, lparam) };
+  }
+
   if let Some(hook) = KEYBOARD_HOOK.get() {
     #[allow(clippy::cast_possible_truncation)]
     let should_block = hook.handle_key_event(input.vkCode as u16);
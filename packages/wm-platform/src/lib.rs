@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 #![feature(iterator_try_collect)]
@@ -10,6 +28,7 @@ mod keyboard_hook;
 mod native_monitor;
 mod native_window;
 mod platform;
+mod simulated_platform;
 mod single_instance;
 mod window_event_hook;
 
@@ -20,5 +39,6 @@ pub use keyboard_hook::*;
 pub use native_monitor::*;
 pub use native_window::*;
 pub use platform::*;
+pub use simulated_platform::*;
 pub use single_instance::*;
 pub use window_event_hook::*;
@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::cell::OnceCell;
 
 use windows::{
@@ -17,6 +35,8 @@ use windows::{
 };
 use wm_common::Rect;
 
+use super::{simulated_platform, MonitorSnapshot};
+
 #[derive(Clone, Debug)]
 pub struct NativeMonitor {
   pub handle: isize,
@@ -71,8 +91,28 @@ impl NativeMonitor {
     self.monitor_info().map(|info| info.scale_factor)
   }
 
+  /// Gets a snapshot of the monitor's properties, which can be replayed
+  /// on a simulated platform.
+  pub fn snapshot(&self) -> anyhow::Result<MonitorSnapshot> {
+    let info = self.monitor_info()?;
+
+    Ok(MonitorSnapshot {
+      handle: self.handle,
+      device_name: info.device_name.clone(),
+      device_path: info.device_path.clone(),
+      hardware_id: info.hardware_id.clone(),
+      rect: info.rect.clone(),
+      working_rect: info.working_rect.clone(),
+      dpi: info.dpi,
+    })
+  }
+
   fn monitor_info(&self) -> anyhow::Result<&MonitorInfo> {
     self.info.get_or_try_init(|| {
+      if let Some(res) = simulated_platform::monitor(self.handle) {
+        return res.map(MonitorInfo::from);
+      }
+
       let mut monitor_info = MONITORINFOEXW {
         monitorInfo: MONITORINFO {
           #[allow(clippy::cast_possible_truncation)]
@@ -166,6 +206,23 @@ impl NativeMonitor {
   }
 }
 
+impl From<MonitorSnapshot> for MonitorInfo {
+  fn from(snapshot: MonitorSnapshot) -> Self {
+    #[allow(clippy::cast_precision_loss)]
+    let scale_factor = snapshot.dpi as f32 / 96.0;
+
+    Self {
+      device_name: snapshot.device_name,
+      device_path: snapshot.device_path,
+      hardware_id: snapshot.hardware_id,
+      rect: snapshot.rect,
+      working_rect: snapshot.working_rect,
+      dpi: snapshot.dpi,
+      scale_factor,
+    }
+  }
+}
+
 impl PartialEq for NativeMonitor {
   fn eq(&self, other: &Self) -> bool {
     self.handle == other.handle
@@ -186,6 +243,18 @@ pub fn available_monitors() -> anyhow::Result<Vec<NativeMonitor>> {
 
 /// Gets all available monitor handles.
 fn available_monitor_handles() -> anyhow::Result<Vec<isize>> {
+  if let Some(handles) =
+    simulated_platform::with_simulation(|simulation| {
+      simulation
+        .monitors
+        .iter()
+        .map(|monitor| monitor.handle)
+        .collect()
+    })
+  {
+    return Ok(handles);
+  }
+
   let mut monitors: Vec<isize> = Vec::new();
 
   unsafe {
@@ -216,6 +285,12 @@ extern "system" fn available_monitor_handles_proc(
 
 #[must_use]
 pub fn nearest_monitor(window_handle: isize) -> NativeMonitor {
+  if let Some(handle) = simulated_platform::with_simulation(|simulation| {
+    simulation.nearest_monitor(window_handle)
+  }) {
+    return NativeMonitor::new(handle.unwrap_or_default());
+  }
+
   let handle = unsafe {
     MonitorFromWindow(HWND(window_handle), MONITOR_DEFAULTTONEAREST)
   };
@@ -24,36 +24,44 @@ use tracing::warn;
 use windows::{
   core::PWSTR,
   Win32::{
-    Foundation::{CloseHandle, BOOL, HWND, LPARAM, RECT},
+    Foundation::{
+      CloseHandle, BOOL, HANDLE, HWND, LPARAM, POINT, RECT, WPARAM,
+    },
     Graphics::Dwm::{
       DwmGetWindowAttribute, DwmSetWindowAttribute, DWMWA_BORDER_COLOR,
       DWMWA_CLOAKED, DWMWA_COLOR_NONE, DWMWA_EXTENDED_FRAME_BOUNDS,
       DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND,
       DWMWCP_ROUND, DWMWCP_ROUNDSMALL,
     },
+    Security::{
+      GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    },
     System::Threading::{
-      OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
-      PROCESS_QUERY_LIMITED_INFORMATION,
+      OpenProcess, OpenProcessToken, QueryFullProcessImageNameW,
+      PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
     },
     UI::{
       Input::KeyboardAndMouse::{
         SendInput, INPUT, INPUT_0, INPUT_MOUSE, MOUSEINPUT,
       },
       WindowsAndMessaging::{
-        EnumWindows, GetClassNameW, GetLayeredWindowAttributes, GetWindow,
-        GetWindowLongPtrW, GetWindowRect, GetWindowTextW,
-        GetWindowThreadProcessId, IsIconic, IsWindow, IsWindowVisible, IsZoomed,
+        EnumWindows, GetClassNameW, GetLayeredWindowAttributes,
+        GetSystemMetrics, GetWindow, GetWindowLongPtrW, GetWindowRect,
+        GetWindowTextW, GetWindowThreadProcessId, IsIconic, IsWindow,
+        IsWindowVisible, IsZoomed, SendMessageTimeoutW,
         SendNotifyMessageW, SetForegroundWindow,
         SetLayeredWindowAttributes, SetWindowLongPtrW, SetWindowPlacement,
         SetWindowPos, ShowWindowAsync, GWL_EXSTYLE, GWL_STYLE, GW_OWNER,
         HWND_NOTOPMOST, HWND_TOP, HWND_TOPMOST,
         LAYERED_WINDOW_ATTRIBUTES_FLAGS, LWA_ALPHA, LWA_COLORKEY,
-        SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED, SWP_NOACTIVATE,
-        SWP_NOCOPYBITS, SWP_NOMOVE, SWP_NOOWNERZORDER, SWP_NOSENDCHANGING,
-        SWP_NOSIZE, SWP_NOZORDER, SWP_SHOWWINDOW, SW_HIDE, SW_MAXIMIZE,
-        SW_MINIMIZE, SW_RESTORE, SW_SHOWNA, WINDOWPLACEMENT,
-        WINDOW_EX_STYLE, WINDOW_STYLE, WM_CLOSE, WPF_ASYNCWINDOWPLACEMENT,
-        WS_CAPTION, WS_CHILD, WS_DLGFRAME, WS_EX_LAYERED,
+        MINMAXINFO, SMTO_ABORTIFHUNG, SM_CXMAXTRACK, SM_CXMINTRACK,
+        SM_CYMAXTRACK, SM_CYMINTRACK, SWP_ASYNCWINDOWPOS,
+        SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOCOPYBITS, SWP_NOMOVE,
+        SWP_NOOWNERZORDER, SWP_NOSENDCHANGING, SWP_NOSIZE, SWP_NOZORDER,
+        SWP_SHOWWINDOW, SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+        SW_SHOWNA, WINDOWPLACEMENT, WINDOW_EX_STYLE, WINDOW_STYLE,
+        WM_CLOSE, WM_GETMINMAXINFO, WPF_ASYNCWINDOWPLACEMENT, WS_CAPTION,
+        WS_CHILD, WS_DLGFRAME, WS_EX_DLGMODALFRAME, WS_EX_LAYERED,
         WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_MAXIMIZEBOX, WS_THICKFRAME,
       },
     },
@@ -61,10 +69,12 @@ use windows::{
 };
 use wm_common::{
   Color, CornerStyle, Delta, HideMethod, LengthValue, Memo, OpacityValue,
-  Rect, RectDelta, WindowState,
+  Rect, RectDelta, SizeConstraints, WindowState,
 };
 
-use super::COM_INIT;
+use super::{
+  simulated_platform, SimulatedPlatform, WindowSnapshot, COM_INIT,
+};
 
 /// Magic number used to identify programmatic mouse inputs from our own
 /// process.
@@ -83,11 +93,15 @@ pub struct NativeWindow {
   pub handle: isize,
   title: Memo<String>,
   process_name: Memo<String>,
+  process_path: Memo<String>,
   class_name: Memo<String>,
+  is_elevated: Memo<bool>,
+  initial_frame_position: Memo<Rect>,
   frame_position: Memo<Rect>,
   border_position: Memo<Rect>,
   is_minimized: Memo<bool>,
   is_maximized: Memo<bool>,
+  size_constraints: Memo<SizeConstraints>,
 }
 
 impl NativeWindow {
@@ -98,11 +112,15 @@ impl NativeWindow {
       handle,
       title: Memo::new(),
       process_name: Memo::new(),
+      process_path: Memo::new(),
       class_name: Memo::new(),
+      is_elevated: Memo::new(),
+      initial_frame_position: Memo::new(),
       frame_position: Memo::new(),
       border_position: Memo::new(),
       is_minimized: Memo::new(),
       is_maximized: Memo::new(),
+      size_constraints: Memo::new(),
     }
   }
 
@@ -123,6 +141,10 @@ impl NativeWindow {
   /// string.
   #[allow(clippy::unnecessary_wraps)]
   fn updated_title(&self) -> anyhow::Result<String> {
+    if let Some(res) = self.simulated(|window| window.title.clone()) {
+      return res;
+    }
+
     let mut text: [u16; 512] = [0; 512];
     let length = unsafe { GetWindowTextW(HWND(self.handle), &mut text) };
 
@@ -141,17 +163,34 @@ impl NativeWindow {
 
   /// Gets the process name associated with the window.
   fn updated_process_name(&self) -> anyhow::Result<String> {
-    let mut process_id = 0u32;
-    unsafe {
-      GetWindowThreadProcessId(
-        HWND(self.handle),
-        Some(&raw mut process_id),
-      );
+    let exe_path = self.process_path()?;
+
+    exe_path
+      .split('\\')
+      .next_back()
+      .map(|file_name| {
+        file_name.split('.').next().unwrap_or(file_name).to_string()
+      })
+      .context("Failed to parse process name.")
+  }
+
+  /// Gets the full path to the executable of the window's process.
+  ///
+  /// This value is lazily retrieved and cached after first retrieval.
+  pub fn process_path(&self) -> anyhow::Result<String> {
+    self
+      .process_path
+      .get_or_init(Self::updated_process_path, self)
+  }
+
+  /// Gets the full path to the executable of the window's process.
+  fn updated_process_path(&self) -> anyhow::Result<String> {
+    if let Some(res) = self.simulated(|window| window.process_path.clone())
+    {
+      return res?.context("Failed to get process path.");
     }
 
-    let process_handle = unsafe {
-      OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id)
-    }?;
+    let process_handle = self.open_process()?;
 
     let mut buffer = [0u16; 256];
     let mut length = u32::try_from(buffer.len())?;
@@ -166,15 +205,85 @@ impl NativeWindow {
       CloseHandle(process_handle)?;
     };
 
-    let exe_path = String::from_utf16_lossy(&buffer[..length as usize]);
+    Ok(String::from_utf16_lossy(&buffer[..length as usize]))
+  }
 
-    exe_path
-      .split('\\')
-      .next_back()
-      .map(|file_name| {
-        file_name.split('.').next().unwrap_or(file_name).to_string()
-      })
-      .context("Failed to parse process name.")
+  /// Gets the ID of the window's process.
+  #[must_use]
+  pub fn process_id(&self) -> u32 {
+    if let Some(res) = self.simulated(|window| window.process_id) {
+      return res.unwrap_or(0);
+    }
+
+    let mut process_id = 0u32;
+    unsafe {
+      GetWindowThreadProcessId(
+        HWND(self.handle),
+        Some(&raw mut process_id),
+      );
+    }
+
+    process_id
+  }
+
+  /// Opens a handle to the window's process with limited query access.
+  ///
+  /// The returned handle needs to be closed by the caller.
+  fn open_process(&self) -> anyhow::Result<HANDLE> {
+    let process_handle = unsafe {
+      OpenProcess(
+        PROCESS_QUERY_LIMITED_INFORMATION,
+        false,
+        self.process_id(),
+      )
+    }?;
+
+    Ok(process_handle)
+  }
+
+  /// Whether the window's process is running with elevated privileges.
+  ///
+  /// This value is lazily retrieved and cached after first retrieval.
+  pub fn is_elevated(&self) -> anyhow::Result<bool> {
+    self
+      .is_elevated
+      .get_or_init(Self::updated_is_elevated, self)
+  }
+
+  /// Whether the window's process is running with elevated privileges.
+  fn updated_is_elevated(&self) -> anyhow::Result<bool> {
+    if let Some(res) = self.simulated(|window| window.is_elevated) {
+      return res;
+    }
+
+    let process_handle = self.open_process()?;
+
+    let mut token_handle = HANDLE::default();
+    let token_result = unsafe {
+      OpenProcessToken(process_handle, TOKEN_QUERY, &raw mut token_handle)
+    };
+
+    unsafe { CloseHandle(process_handle) }?;
+    token_result.context("Failed to open process token.")?;
+
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut length = 0u32;
+
+    let elevation_result = unsafe {
+      #[allow(clippy::cast_possible_truncation)]
+      GetTokenInformation(
+        token_handle,
+        TokenElevation,
+        Some(std::ptr::from_mut(&mut elevation).cast()),
+        std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+        &raw mut length,
+      )
+    };
+
+    unsafe { CloseHandle(token_handle) }?;
+    elevation_result?;
+
+    Ok(elevation.TokenIsElevated != 0)
   }
 
   /// Gets the class name of the window.
@@ -186,6 +295,10 @@ impl NativeWindow {
 
   /// Gets the class name of the window.
   fn updated_class_name(&self) -> anyhow::Result<String> {
+    if let Some(res) = self.simulated(|window| window.class_name.clone()) {
+      return res?.context("Failed to get class name.");
+    }
+
     let mut buffer = [0u16; 256];
     let result = unsafe { GetClassNameW(HWND(self.handle), &mut buffer) };
 
@@ -202,15 +315,26 @@ impl NativeWindow {
   ///
   /// Returns `true` if the underlying window handle refers to an existing window.
   pub fn is_valid(&self) -> bool {
+    if let Some(res) = self.simulated(|_| ()) {
+      return res.is_ok();
+    }
+
     unsafe { IsWindow(HWND(self.handle)) }.as_bool()
   }
 
   /// Whether the window is actually visible.
   pub fn is_visible(&self) -> anyhow::Result<bool> {
-    let is_visible =
-      unsafe { IsWindowVisible(HWND(self.handle)) }.as_bool();
+    Ok(self.has_visible_style() && !self.is_cloaked()?)
+  }
+
+  /// Whether the window has the `WS_VISIBLE` style. Cloaked windows can
+  /// have this style while not actually being visible.
+  pub(crate) fn has_visible_style(&self) -> bool {
+    if let Some(res) = self.simulated(|window| window.has_visible_style) {
+      return res.unwrap_or(false);
+    }
 
-    Ok(is_visible && !self.is_cloaked()?)
+    unsafe { IsWindowVisible(HWND(self.handle)) }.as_bool()
   }
 
   /// Returns the raw `DWMWA_CLOAKED` flags for this window.
@@ -220,6 +344,10 @@ impl NativeWindow {
   /// - `0x2` (`DWM_CLOAKED_SHELL`): cloaked by the Windows shell.
   /// - `0x4` (`DWM_CLOAKED_INHERITED`): inherited from a cloaked owner.
   pub fn cloaked_flags(&self) -> anyhow::Result<u32> {
+    if let Some(res) = self.simulated(|window| window.cloaked_flags) {
+      return res;
+    }
+
     let mut cloaked = 0u32;
 
     unsafe {
@@ -278,12 +406,36 @@ impl NativeWindow {
     // bar menu in Keepass. Although not foolproof, these can typically be
     // identified by having an owner window and no title bar.
     let is_menu_window =
-      unsafe { GetWindow(HWND(self.handle), GW_OWNER) }.0 != 0
-        && !self.has_window_style(WS_CAPTION);
+      self.has_owner() && !self.has_window_style(WS_CAPTION);
 
     Ok(!is_menu_window)
   }
 
+  /// Whether the window has the tool window style.
+  #[must_use]
+  pub fn is_tool_window(&self) -> bool {
+    self.has_window_style_ex(WS_EX_TOOLWINDOW)
+  }
+
+  /// Whether the window is a dialog. This is either a window with the
+  /// system dialog class or a window with a modal dialog frame.
+  pub fn is_dialog(&self) -> anyhow::Result<bool> {
+    Ok(
+      self.class_name()? == "#32770"
+        || self.has_window_style_ex(WS_EX_DLGMODALFRAME),
+    )
+  }
+
+  /// Whether the window has an owner window.
+  #[must_use]
+  pub fn has_owner(&self) -> bool {
+    if let Some(res) = self.simulated(|window| window.has_owner) {
+      return res.unwrap_or(false);
+    }
+
+    unsafe { GetWindow(HWND(self.handle), GW_OWNER) }.0 != 0
+  }
+
   /// Whether the window is minimized.
   ///
   /// This value is lazily retrieved and cached after first retrieval.
@@ -301,6 +453,10 @@ impl NativeWindow {
   /// Whether the window is minimized.
   #[allow(clippy::unnecessary_wraps)]
   fn updated_is_minimized(&self) -> anyhow::Result<bool> {
+    if let Some(res) = self.simulated(|window| window.is_minimized) {
+      return res;
+    }
+
     Ok(unsafe { IsIconic(HWND(self.handle)) }.as_bool())
   }
 
@@ -321,9 +477,101 @@ impl NativeWindow {
   /// Whether the window is maximized.
   #[allow(clippy::unnecessary_wraps)]
   fn updated_is_maximized(&self) -> anyhow::Result<bool> {
+    if let Some(res) = self.simulated(|window| window.is_maximized) {
+      return res;
+    }
+
     Ok(unsafe { IsZoomed(HWND(self.handle)) }.as_bool())
   }
 
+  /// Gets the minimum and maximum size that the window can be resized to
+  /// (i.e. via `WM_GETMINMAXINFO`). Dimensions that match the system
+  /// defaults are treated as unconstrained.
+  ///
+  /// This value is lazily retrieved and cached after first retrieval.
+  pub fn size_constraints(&self) -> anyhow::Result<SizeConstraints> {
+    self
+      .size_constraints
+      .get_or_init(Self::updated_size_constraints, self)
+  }
+
+  /// Retrieves the window's size constraints on a blocking thread and
+  /// runs the callback with the result.
+  ///
+  /// Windows that are slow to respond to `WM_GETMINMAXINFO` would
+  /// otherwise stall the caller for up to the query timeout. On a
+  /// simulated platform, the callback is run immediately.
+  pub fn query_size_constraints(
+    &self,
+    callback: impl FnOnce(anyhow::Result<SizeConstraints>) + Send + 'static,
+  ) {
+    if SimulatedPlatform::is_active() {
+      callback(self.size_constraints());
+      return;
+    }
+
+    let window = self.clone();
+    task::spawn_blocking(move || callback(window.size_constraints()));
+  }
+
+  /// Gets the minimum and maximum size of the window.
+  fn updated_size_constraints(&self) -> anyhow::Result<SizeConstraints> {
+    if let Some(res) =
+      self.simulated(|window| window.size_constraints.clone())
+    {
+      return res?
+        .context("Window did not respond to size constraints query.");
+    }
+
+    let default_min = POINT {
+      x: unsafe { GetSystemMetrics(SM_CXMINTRACK) },
+      y: unsafe { GetSystemMetrics(SM_CYMINTRACK) },
+    };
+
+    let default_max = POINT {
+      x: unsafe { GetSystemMetrics(SM_CXMAXTRACK) },
+      y: unsafe { GetSystemMetrics(SM_CYMAXTRACK) },
+    };
+
+    // The system prefills the default values before sending the message,
+    // so the same is done here.
+    let mut min_max_info = MINMAXINFO {
+      ptMinTrackSize: default_min,
+      ptMaxTrackSize: default_max,
+      ..Default::default()
+    };
+
+    let result = unsafe {
+      SendMessageTimeoutW(
+        HWND(self.handle),
+        WM_GETMINMAXINFO,
+        WPARAM(0),
+        LPARAM(std::ptr::addr_of_mut!(min_max_info) as isize),
+        SMTO_ABORTIFHUNG,
+        100,
+        None,
+      )
+    };
+
+    if result.0 == 0 {
+      bail!("Window did not respond to size constraints query.");
+    }
+
+    let min_length =
+      |length: i32, default: i32| (length > default).then_some(length);
+
+    let max_length = |length: i32, default: i32| {
+      (length > 0 && length < default).then_some(length)
+    };
+
+    Ok(SizeConstraints {
+      min_width: min_length(min_max_info.ptMinTrackSize.x, default_min.x),
+      min_height: min_length(min_max_info.ptMinTrackSize.y, default_min.y),
+      max_width: max_length(min_max_info.ptMaxTrackSize.x, default_max.x),
+      max_height: max_length(min_max_info.ptMaxTrackSize.y, default_max.y),
+    })
+  }
+
   /// Whether the window has resize handles.
   #[must_use]
   pub fn is_resizable(&self) -> bool {
@@ -353,6 +601,12 @@ impl NativeWindow {
   }
 
   pub fn set_foreground(&self) -> anyhow::Result<()> {
+    if let Some(()) = simulated_platform::with_simulation(|simulation| {
+      simulation.foreground_window = self.handle;
+    }) {
+      return Ok(());
+    }
+
     let input = [INPUT {
       r#type: INPUT_MOUSE,
       Anonymous: INPUT_0 {
@@ -380,6 +634,10 @@ impl NativeWindow {
     &self,
     color: Option<&Color>,
   ) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|_| ()) {
+      return res;
+    }
+
     let bgr = match color {
       Some(color) => color.to_bgr()?,
       None => DWMWA_COLOR_NONE,
@@ -402,6 +660,10 @@ impl NativeWindow {
     &self,
     corner_style: &CornerStyle,
   ) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|_| ()) {
+      return res;
+    }
+
     let corner_preference = match corner_style {
       CornerStyle::Default => DWMWCP_DEFAULT,
       CornerStyle::Square => DWMWCP_DONOTROUND,
@@ -426,7 +688,7 @@ impl NativeWindow {
     &self,
     visible: bool,
   ) -> anyhow::Result<()> {
-    let style = unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_STYLE) };
+    let style = self.window_style();
 
     #[allow(clippy::cast_possible_wrap)]
     let new_style = if visible {
@@ -435,6 +697,10 @@ impl NativeWindow {
       style & !(WS_DLGFRAME.0 as isize)
     };
 
+    if let Some(res) = self.simulated(|window| window.style = new_style) {
+      return res;
+    }
+
     if new_style != style {
       unsafe {
         SetWindowLongPtrW(HWND(self.handle), GWL_STYLE, new_style);
@@ -462,23 +728,35 @@ impl NativeWindow {
   }
 
   fn add_window_style_ex(&self, style: WINDOW_EX_STYLE) {
-    let current_style =
-      unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_EXSTYLE) };
+    let current_style = self.window_style_ex();
 
     #[allow(clippy::cast_possible_wrap)]
     if current_style & style.0 as isize == 0 {
       let new_style = current_style | style.0 as isize;
 
+      if self
+        .simulated(|window| window.ex_style = new_style)
+        .is_some()
+      {
+        return;
+      }
+
       unsafe {
         SetWindowLongPtrW(HWND(self.handle), GWL_EXSTYLE, new_style)
       };
     }
   }
 
-  pub fn adjust_transparency(
-    &self,
-    opacity_delta: &Delta<OpacityValue>,
-  ) -> anyhow::Result<()> {
+  /// Gets the current opacity of the window.
+  ///
+  /// Errors if the window isn't layered, or if it uses a color key for
+  /// its transparency.
+  pub fn transparency(&self) -> anyhow::Result<OpacityValue> {
+    if let Some(res) = self.simulated(|window| window.alpha) {
+      let alpha = res?.context("Window is not a layered window.")?;
+      return Ok(OpacityValue::from_alpha(alpha));
+    }
+
     let mut alpha = u8::MAX;
     let mut flag = LAYERED_WINDOW_ATTRIBUTES_FLAGS::default();
 
@@ -497,6 +775,15 @@ impl NativeWindow {
       );
     }
 
+    Ok(OpacityValue::from_alpha(alpha))
+  }
+
+  pub fn adjust_transparency(
+    &self,
+    opacity_delta: &Delta<OpacityValue>,
+  ) -> anyhow::Result<()> {
+    let alpha = self.transparency()?.to_alpha();
+
     let target_alpha = if opacity_delta.is_negative {
       alpha.saturating_sub(opacity_delta.inner.to_alpha())
     } else {
@@ -513,6 +800,12 @@ impl NativeWindow {
     // Make the window layered if it isn't already.
     self.add_window_style_ex(WS_EX_LAYERED);
 
+    if let Some(res) = self.simulated(|window| {
+      window.alpha = Some(opacity_value.to_alpha());
+    }) {
+      return res;
+    }
+
     unsafe {
       SetLayeredWindowAttributes(
         HWND(self.handle),
@@ -535,6 +828,16 @@ impl NativeWindow {
       .get_or_init(Self::updated_frame_position, self)
   }
 
+  /// Gets the frame position of the window from when it was first
+  /// retrieved.
+  ///
+  /// This value is lazily retrieved and never refreshed.
+  pub fn initial_frame_position(&self) -> anyhow::Result<Rect> {
+    self
+      .initial_frame_position
+      .get_or_init(Self::frame_position, self)
+  }
+
   /// Updates the cached frame position.
   pub fn refresh_frame_position(&self) -> anyhow::Result<Rect> {
     _ = self.refresh_border_position()?;
@@ -547,6 +850,12 @@ impl NativeWindow {
   /// Gets the window's position, including the window's frame. Excludes
   /// the window's shadow borders.
   fn updated_frame_position(&self) -> anyhow::Result<Rect> {
+    if let Some(res) =
+      self.simulated(|window| window.frame_position.clone())
+    {
+      return res?.context("Failed to get window's frame position.");
+    }
+
     let mut rect = RECT::default();
 
     let dwm_res = unsafe {
@@ -592,6 +901,12 @@ impl NativeWindow {
   /// Gets the window's position, including the window's frame and
   /// shadow borders.
   fn updated_border_position(&self) -> anyhow::Result<Rect> {
+    if let Some(res) =
+      self.simulated(|window| window.border_position.clone())
+    {
+      return res?.context("Failed to get window's border position.");
+    }
+
     let mut rect = RECT::default();
 
     unsafe {
@@ -624,24 +939,42 @@ impl NativeWindow {
   }
 
   fn has_window_style(&self, style: WINDOW_STYLE) -> bool {
-    let current_style =
-      unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_STYLE) };
-
     #[allow(clippy::cast_possible_wrap)]
     let style = style.0 as isize;
-    (current_style & style) != 0
+    (self.window_style() & style) != 0
   }
 
   fn has_window_style_ex(&self, style: WINDOW_EX_STYLE) -> bool {
-    let current_style =
-      unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_EXSTYLE) };
-
     #[allow(clippy::cast_possible_wrap)]
     let style = style.0 as isize;
-    (current_style & style) != 0
+    (self.window_style_ex() & style) != 0
+  }
+
+  /// Gets the raw `GWL_STYLE` value of the window.
+  fn window_style(&self) -> isize {
+    if let Some(res) = self.simulated(|window| window.style) {
+      return res.unwrap_or(0);
+    }
+
+    unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_STYLE) }
+  }
+
+  /// Gets the raw `GWL_EXSTYLE` value of the window.
+  fn window_style_ex(&self) -> isize {
+    if let Some(res) = self.simulated(|window| window.ex_style) {
+      return res.unwrap_or(0);
+    }
+
+    unsafe { GetWindowLongPtrW(HWND(self.handle), GWL_EXSTYLE) }
   }
 
   pub fn restore_to_position(&self, rect: &Rect) -> anyhow::Result<()> {
+    if let Some(res) =
+      self.simulated(|window| window.restore_to_position(rect))
+    {
+      return res;
+    }
+
     let placement = WINDOWPLACEMENT {
       #[allow(clippy::cast_possible_truncation)]
       length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
@@ -664,16 +997,31 @@ impl NativeWindow {
   }
 
   pub fn maximize(&self) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|window| window.is_maximized = true)
+    {
+      return res;
+    }
+
     unsafe { ShowWindowAsync(HWND(self.handle), SW_MAXIMIZE).ok() }?;
     Ok(())
   }
 
   pub fn minimize(&self) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|window| window.is_minimized = true)
+    {
+      return res;
+    }
+
     unsafe { ShowWindowAsync(HWND(self.handle), SW_MINIMIZE).ok() }?;
     Ok(())
   }
 
   pub fn close(&self) -> anyhow::Result<()> {
+    // Closing is left to the recorded destroy event of the window.
+    if let Some(res) = self.simulated(|_| ()) {
+      return res;
+    }
+
     unsafe {
       SendNotifyMessageW(HWND(self.handle), WM_CLOSE, None, None)
     }?;
@@ -686,29 +1034,60 @@ impl NativeWindow {
     visible: bool,
     hide_method: &HideMethod,
   ) -> anyhow::Result<()> {
+    // Hide methods can differ between workspaces, so the window might
+    // have been hidden with the other method (e.g. when moved between
+    // workspaces). Revert the other method when showing the window.
     match hide_method {
       HideMethod::Hide => {
         if visible {
+          if self.cloaked_flags().unwrap_or(0) & 0x1 != 0 {
+            self.set_cloaked(false)?;
+          }
+
           self.show()
         } else {
           self.hide()
         }
       }
-      HideMethod::Cloak => self.set_cloaked(!visible),
+      HideMethod::Cloak => {
+        if visible && !self.has_visible_style() {
+          self.show()?;
+        }
+
+        self.set_cloaked(!visible)
+      }
     }
   }
 
   pub fn show(&self) -> anyhow::Result<()> {
+    if let Some(res) =
+      self.simulated(|window| window.has_visible_style = true)
+    {
+      return res;
+    }
+
     unsafe { ShowWindowAsync(HWND(self.handle), SW_SHOWNA) }.ok()?;
     Ok(())
   }
 
   pub fn hide(&self) -> anyhow::Result<()> {
+    if let Some(res) =
+      self.simulated(|window| window.has_visible_style = false)
+    {
+      return res;
+    }
+
     unsafe { ShowWindowAsync(HWND(self.handle), SW_HIDE) }.ok()?;
     Ok(())
   }
 
   pub fn set_cloaked(&self, cloaked: bool) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|window| {
+      window.cloaked_flags = if cloaked { 2 } else { 0 };
+    }) {
+      return res;
+    }
+
     COM_INIT.with(|com_init| {
       com_init.borrow_mut().with_retry(|c| {
         let view_collection = c.application_view_collection()?;
@@ -739,6 +1118,10 @@ impl NativeWindow {
     &self,
     visible: bool,
   ) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|_| ()) {
+      return res;
+    }
+
     COM_INIT.with(|com_init| {
       com_init.borrow_mut().with_retry(|c| {
         let taskbar_list = c.taskbar_list()?;
@@ -763,6 +1146,13 @@ impl NativeWindow {
     hide_method: &HideMethod,
     has_pending_dpi_adjustment: bool,
   ) -> anyhow::Result<()> {
+    if let Some(res) =
+      self.simulated(|window| window.set_position(state, rect))
+    {
+      res?;
+      return self.set_visible(is_visible, hide_method);
+    }
+
     // Restore window if it's minimized/maximized and shouldn't be. This is
     // needed to be able to move and resize it.
     match state {
@@ -868,6 +1258,10 @@ impl NativeWindow {
   /// Causes the native Windows taskbar to be moved to the bottom of the
   /// z-order when this window is active.
   pub fn mark_fullscreen(&self, fullscreen: bool) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|_| ()) {
+      return res;
+    }
+
     COM_INIT.with(|com_init| {
       com_init.borrow_mut().with_retry(|c| {
         let taskbar_list = c.taskbar_list()?;
@@ -882,6 +1276,10 @@ impl NativeWindow {
   }
 
   pub fn set_z_order(&self, z_order: &ZOrder) -> anyhow::Result<()> {
+    if let Some(res) = self.simulated(|_| ()) {
+      return res;
+    }
+
     let z_order = match z_order {
       ZOrder::TopMost => HWND_TOPMOST,
       ZOrder::Top => HWND_TOP,
@@ -933,6 +1331,41 @@ impl NativeWindow {
     Ok(())
   }
 
+  /// Gets a snapshot of the window's properties, which can be replayed
+  /// on a simulated platform.
+  #[must_use]
+  pub fn snapshot(&self) -> WindowSnapshot {
+    WindowSnapshot {
+      handle: self.handle,
+      is_valid: self.is_valid(),
+      title: self.updated_title().unwrap_or_default(),
+      class_name: self.updated_class_name().ok(),
+      process_path: self.updated_process_path().ok(),
+      process_id: self.process_id(),
+      is_elevated: self.updated_is_elevated().unwrap_or(false),
+      style: self.window_style(),
+      ex_style: self.window_style_ex(),
+      has_visible_style: self.has_visible_style(),
+      cloaked_flags: self.cloaked_flags().unwrap_or(0),
+      has_owner: self.has_owner(),
+      is_minimized: self.updated_is_minimized().unwrap_or(false),
+      is_maximized: self.updated_is_maximized().unwrap_or(false),
+      frame_position: self.updated_frame_position().ok(),
+      border_position: self.updated_border_position().ok(),
+      size_constraints: self.updated_size_constraints().ok(),
+      alpha: self.transparency().ok().map(|opacity| opacity.to_alpha()),
+    }
+  }
+
+  /// Runs the given closure against the window on the simulated
+  /// platform, if one is active.
+  fn simulated<T>(
+    &self,
+    callback: impl FnOnce(&mut WindowSnapshot) -> T,
+  ) -> Option<anyhow::Result<T>> {
+    simulated_platform::with_window(self.handle, callback)
+  }
+
   pub fn cleanup(&self) {
     // Uncloak first (for HideMethod::Cloak), then show (for
     // HideMethod::Hide). Both are needed because we don't know which
@@ -967,6 +1400,14 @@ pub fn available_windows() -> anyhow::Result<Vec<NativeWindow>> {
 }
 
 pub fn available_window_handles() -> anyhow::Result<Vec<isize>> {
+  if let Some(handles) =
+    simulated_platform::with_simulation(|simulation| {
+      simulation.window_order.clone()
+    })
+  {
+    return Ok(handles);
+  }
+
   let mut handles: Vec<isize> = Vec::new();
 
   unsafe {
@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
+  collections::HashMap,
   os::windows::io::AsRawHandle,
   path::{Path, PathBuf},
   thread::JoinHandle,
@@ -27,9 +28,17 @@ use tracing::{info, warn};
 use windows::{
   core::{w, PCWSTR},
   Win32::{
-    Foundation::{HANDLE, HWND, LPARAM, POINT, WPARAM},
+    Foundation::{CloseHandle, HANDLE, HWND, LPARAM, POINT, WPARAM},
     System::{
-      Environment::ExpandEnvironmentStringsW, Threading::GetThreadId,
+      Console::{AttachConsole, ATTACH_PARENT_PROCESS},
+      Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW,
+        PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+      },
+      Environment::ExpandEnvironmentStringsW,
+      Threading::{
+        GetThreadId, OpenProcess, TerminateProcess, PROCESS_TERMINATE,
+      },
     },
     UI::{
       Shell::{
@@ -54,8 +63,9 @@ use windows::{
 use wm_common::{ParsedConfig, Point};
 
 use super::{
-  native_monitor, native_window, EventListener, NativeMonitor,
-  NativeWindow, SingleInstance,
+  native_monitor, native_window, simulated_platform, EventListener,
+  NativeMonitor, NativeWindow, PlatformSnapshot, SimulatedPlatform,
+  SingleInstance,
 };
 
 pub type WindowProcedure = WNDPROC;
@@ -66,6 +76,14 @@ impl Platform {
   /// Gets the `NativeWindow` instance of the currently focused window.
   #[must_use]
   pub fn foreground_window() -> NativeWindow {
+    if let Some(handle) =
+      simulated_platform::with_simulation(|simulation| {
+        simulation.foreground_window
+      })
+    {
+      return NativeWindow::new(handle);
+    }
+
     let handle = unsafe { GetForegroundWindow() };
     NativeWindow::new(handle.0)
   }
@@ -77,6 +95,14 @@ impl Platform {
   /// the wallpaper window.
   #[must_use]
   pub fn desktop_window() -> NativeWindow {
+    if let Some(handle) =
+      simulated_platform::with_simulation(|simulation| {
+        simulation.desktop_window
+      })
+    {
+      return NativeWindow::new(handle);
+    }
+
     let handle = match unsafe { GetShellWindow() } {
       HWND(0) => unsafe { GetDesktopWindow() },
       handle => handle,
@@ -183,6 +209,30 @@ impl Platform {
     Ok(())
   }
 
+  /// Gets a snapshot of the monitors and visible windows, which can be
+  /// replayed on a simulated platform.
+  pub fn snapshot() -> anyhow::Result<PlatformSnapshot> {
+    let monitors = native_monitor::available_monitors()?
+      .iter()
+      .map(NativeMonitor::snapshot)
+      .try_collect()?;
+
+    // Hidden windows are only included once they're shown.
+    let windows = native_window::available_windows()?
+      .into_iter()
+      .filter(NativeWindow::has_visible_style)
+      .map(|window| window.snapshot())
+      .collect();
+
+    Ok(PlatformSnapshot {
+      monitors,
+      windows,
+      foreground_window: Self::foreground_window().handle,
+      desktop_window: Self::desktop_window().handle,
+      mouse_position: Self::mouse_position()?,
+    })
+  }
+
   /// Creates a new `EventListener` for the specified user config.
   pub fn start_event_listener(
     config: &ParsedConfig,
@@ -199,12 +249,84 @@ impl Platform {
   pub fn root_ancestor(
     window: &NativeWindow,
   ) -> anyhow::Result<NativeWindow> {
+    // Only top-level windows are simulated.
+    if SimulatedPlatform::is_active() {
+      return Ok(NativeWindow::new(window.handle));
+    }
+
     let handle = unsafe { GetAncestor(HWND(window.handle), GA_ROOT) };
     Ok(NativeWindow::new(handle.0))
   }
 
+  /// Gets the IDs of the ancestor processes of the specified process,
+  /// starting with its parent.
+  ///
+  /// Parent process IDs can refer to processes that have since exited (or
+  /// whose IDs have been reused), so the chain stops at the first ID that
+  /// no longer exists or has already been visited.
+  pub fn process_ancestors(process_id: u32) -> anyhow::Result<Vec<u32>> {
+    // Processes aren't simulated.
+    if SimulatedPlatform::is_active() {
+      return Ok(Vec::new());
+    }
+
+    let snapshot =
+      unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }?;
+
+    let mut parent_ids = HashMap::new();
+    let mut entry = PROCESSENTRY32W {
+      dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>())?,
+      ..Default::default()
+    };
+
+    let mut res = unsafe { Process32FirstW(snapshot, &raw mut entry) };
+
+    while res.is_ok() {
+      parent_ids.insert(entry.th32ProcessID, entry.th32ParentProcessID);
+      res = unsafe { Process32NextW(snapshot, &raw mut entry) };
+    }
+
+    unsafe { CloseHandle(snapshot) }?;
+
+    let mut ancestors = Vec::new();
+    let mut current_id = process_id;
+
+    while let Some(&parent_id) = parent_ids.get(&current_id) {
+      if parent_id == 0
+        || parent_id == process_id
+        || ancestors.contains(&parent_id)
+        || !parent_ids.contains_key(&parent_id)
+      {
+        break;
+      }
+
+      ancestors.push(parent_id);
+      current_id = parent_id;
+    }
+
+    Ok(ancestors)
+  }
+
+  /// Forcefully terminates the specified process (e.g. a WM instance that
+  /// has stopped responding).
+  pub fn terminate_process(process_id: u32) -> anyhow::Result<()> {
+    let process_handle =
+      unsafe { OpenProcess(PROCESS_TERMINATE, false, process_id) }?;
+
+    let res = unsafe { TerminateProcess(process_handle, 1) };
+    unsafe { CloseHandle(process_handle) }?;
+
+    res.context("Failed to terminate process.")
+  }
+
   /// Sets the cursor position to the specified coordinates.
   pub fn set_cursor_pos(x: i32, y: i32) -> anyhow::Result<()> {
+    if let Some(()) = simulated_platform::with_simulation(|simulation| {
+      simulation.mouse_position = Point { x, y };
+    }) {
+      return Ok(());
+    }
+
     unsafe {
       SetCursorPos(x, y)?;
     };
@@ -214,6 +336,14 @@ impl Platform {
 
   /// Finds the window at the specified point in screen space.
   pub fn window_from_point(point: &Point) -> anyhow::Result<NativeWindow> {
+    if let Some(handle) =
+      simulated_platform::with_simulation(|simulation| {
+        simulation.window_from_point(point)
+      })
+    {
+      return Ok(NativeWindow::new(handle.unwrap_or_default()));
+    }
+
     let point = POINT {
       x: point.x,
       y: point.y,
@@ -225,6 +355,14 @@ impl Platform {
 
   /// Gets the mouse position in screen space.
   pub fn mouse_position() -> anyhow::Result<Point> {
+    if let Some(point) =
+      simulated_platform::with_simulation(|simulation| {
+        simulation.mouse_position.clone()
+      })
+    {
+      return Ok(point);
+    }
+
     let mut point = POINT { x: 0, y: 0 };
     unsafe { GetCursorPos(&raw mut point) }?;
 
@@ -340,6 +478,10 @@ impl Platform {
   ///
   /// Note that this is a global system setting.
   pub fn window_animations_enabled() -> anyhow::Result<bool> {
+    if SimulatedPlatform::is_active() {
+      return Ok(false);
+    }
+
     let mut animation_info = ANIMATIONINFO {
       #[allow(clippy::cast_possible_truncation)]
       cbSize: std::mem::size_of::<ANIMATIONINFO>() as u32,
@@ -364,6 +506,10 @@ impl Platform {
   pub fn set_window_animations_enabled(
     enable: bool,
   ) -> anyhow::Result<()> {
+    if SimulatedPlatform::is_active() {
+      return Ok(());
+    }
+
     let mut animation_info = ANIMATIONINFO {
       #[allow(clippy::cast_possible_truncation)]
       cbSize: std::mem::size_of::<ANIMATIONINFO>() as u32,
@@ -497,6 +643,12 @@ impl Platform {
     args: &str,
     hide_window: bool,
   ) -> anyhow::Result<()> {
+    // Avoid launching programs when replaying a recording.
+    if SimulatedPlatform::is_active() {
+      info!("Skipped running '{program} {args}' on simulated platform.");
+      return Ok(());
+    }
+
     let home_dir = home::home_dir()
       .context("Unable to get home directory.")?
       .to_str()
@@ -530,6 +682,15 @@ impl Platform {
     Ok(())
   }
 
+  /// Attaches to the console of the parent process (e.g. the terminal
+  /// that ran the command), so that output to stdout is visible in
+  /// release builds, which otherwise don't have a console.
+  pub fn attach_parent_console() {
+    // Fails if the process already has a console (e.g. in debug builds)
+    // or if the parent process doesn't have one.
+    let _ = unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+  }
+
   pub fn show_error_dialog(title: &str, message: &str) {
     let title_wide = to_wide(title);
     let message_wide = to_wide(message);
@@ -0,0 +1,309 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  collections::HashMap,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex, MutexGuard, PoisonError,
+  },
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use wm_common::{Point, Rect, SizeConstraints, WindowState};
+
+/// Simulated platform that replaces the native platform while active.
+static SIMULATION: Mutex<Option<Simulation>> = Mutex::new(None);
+
+/// Whether `SIMULATION` is set. Checked before locking the mutex, so that
+/// calls to the native platform don't contend on it.
+static IS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Snapshot of the windows and monitors of the platform, which is used as
+/// the initial state of a simulated platform.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformSnapshot {
+  pub monitors: Vec<MonitorSnapshot>,
+  pub windows: Vec<WindowSnapshot>,
+  pub foreground_window: isize,
+  pub desktop_window: isize,
+  pub mouse_position: Point,
+}
+
+/// Snapshot of the properties of a monitor.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorSnapshot {
+  pub handle: isize,
+  pub device_name: String,
+  pub device_path: Option<String>,
+  pub hardware_id: Option<String>,
+  pub rect: Rect,
+  pub working_rect: Rect,
+  pub dpi: u32,
+}
+
+/// Snapshot of the properties of a window.
+///
+/// Properties that couldn't be retrieved (e.g. the process path of an
+/// elevated window) are `None`, and error when read from a simulated
+/// platform.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowSnapshot {
+  pub handle: isize,
+  pub is_valid: bool,
+  pub title: String,
+  pub class_name: Option<String>,
+  pub process_path: Option<String>,
+  pub process_id: u32,
+  pub is_elevated: bool,
+  /// Raw `GWL_STYLE` value of the window.
+  pub style: isize,
+  /// Raw `GWL_EXSTYLE` value of the window.
+  pub ex_style: isize,
+  /// Whether the window has the `WS_VISIBLE` style.
+  pub has_visible_style: bool,
+  pub cloaked_flags: u32,
+  pub has_owner: bool,
+  pub is_minimized: bool,
+  pub is_maximized: bool,
+  pub frame_position: Option<Rect>,
+  pub border_position: Option<Rect>,
+  pub size_constraints: Option<SizeConstraints>,
+  /// Alpha value of the window if it's a layered window.
+  pub alpha: Option<u8>,
+}
+
+impl WindowSnapshot {
+  /// Moves the window to the given border position, and updates its
+  /// minimized and maximized state to match the window state.
+  pub(crate) fn set_position(&mut self, state: &WindowState, rect: &Rect) {
+    match state {
+      WindowState::Minimized => self.is_minimized = true,
+      WindowState::Fullscreen(config) => {
+        self.restore_to_position(rect);
+        self.is_maximized = config.maximized;
+      }
+      _ => self.restore_to_position(rect),
+    }
+  }
+
+  /// Restores the window from being minimized or maximized, and moves it
+  /// to the given border position.
+  pub(crate) fn restore_to_position(&mut self, rect: &Rect) {
+    self.is_minimized = false;
+    self.is_maximized = false;
+
+    // Keep the size of the window's shadow borders.
+    self.frame_position =
+      match (&self.frame_position, &self.border_position) {
+        (Some(frame), Some(border)) => Some(Rect::from_ltrb(
+          rect.left + (frame.left - border.left),
+          rect.top + (frame.top - border.top),
+          rect.right - (border.right - frame.right),
+          rect.bottom - (border.bottom - frame.bottom),
+        )),
+        _ => Some(rect.clone()),
+      };
+
+    self.border_position = Some(rect.clone());
+  }
+}
+
+/// State of the simulated platform.
+#[derive(Debug)]
+pub(crate) struct Simulation {
+  pub monitors: Vec<MonitorSnapshot>,
+  pub windows: HashMap<isize, WindowSnapshot>,
+  /// Handles of windows from top to bottom of the z-order.
+  pub window_order: Vec<isize>,
+  pub foreground_window: isize,
+  pub desktop_window: isize,
+  pub mouse_position: Point,
+}
+
+impl Simulation {
+  /// Gets the monitor that contains the center of the given window,
+  /// falling back to the monitor it overlaps the most.
+  pub fn nearest_monitor(&self, window_handle: isize) -> Option<isize> {
+    let position = self
+      .windows
+      .get(&window_handle)
+      .and_then(|window| window.frame_position.clone());
+
+    let Some(position) = position else {
+      return self.monitors.first().map(|monitor| monitor.handle);
+    };
+
+    let center = position.center_point();
+
+    self
+      .monitors
+      .iter()
+      .find(|monitor| monitor.rect.contains_point(&center))
+      .or_else(|| {
+        self
+          .monitors
+          .iter()
+          .max_by_key(|monitor| monitor.rect.overlap_area(&position))
+      })
+      .map(|monitor| monitor.handle)
+  }
+
+  /// Gets the topmost visible window at the given point.
+  pub fn window_from_point(&self, point: &Point) -> Option<isize> {
+    self.window_order.iter().copied().find(|handle| {
+      self.windows.get(handle).is_some_and(|window| {
+        window.is_valid
+          && window.has_visible_style
+          && window.cloaked_flags == 0
+          && !window.is_minimized
+          && window
+            .border_position
+            .as_ref()
+            .is_some_and(|rect| rect.contains_point(point))
+      })
+    })
+  }
+}
+
+/// Simulated platform for replaying recorded platform events.
+///
+/// While active, `Platform`, `NativeWindow` and `NativeMonitor` read from
+/// and write to the simulated state instead of the native platform.
+pub struct SimulatedPlatform;
+
+impl SimulatedPlatform {
+  /// Replaces the native platform with a simulated platform that has the
+  /// given initial state.
+  pub fn start(snapshot: PlatformSnapshot) {
+    let window_order = snapshot
+      .windows
+      .iter()
+      .map(|window| window.handle)
+      .collect();
+
+    *simulation() = Some(Simulation {
+      monitors: snapshot.monitors,
+      windows: snapshot
+        .windows
+        .into_iter()
+        .map(|window| (window.handle, window))
+        .collect(),
+      window_order,
+      foreground_window: snapshot.foreground_window,
+      desktop_window: snapshot.desktop_window,
+      mouse_position: snapshot.mouse_position,
+    });
+
+    IS_ACTIVE.store(true, Ordering::Release);
+  }
+
+  /// Restores the native platform.
+  pub fn stop() {
+    IS_ACTIVE.store(false, Ordering::Release);
+    *simulation() = None;
+  }
+
+  /// Whether a simulated platform is active.
+  #[must_use]
+  pub fn is_active() -> bool {
+    IS_ACTIVE.load(Ordering::Acquire)
+  }
+
+  /// Adds or replaces a window. Updated windows are moved to the top of
+  /// the z-order.
+  pub fn update_window(window: WindowSnapshot) {
+    with_simulation(|simulation| {
+      simulation
+        .window_order
+        .retain(|handle| *handle != window.handle);
+      simulation.window_order.insert(0, window.handle);
+      simulation.windows.insert(window.handle, window);
+    });
+  }
+
+  /// Replaces the available monitors.
+  pub fn set_monitors(monitors: Vec<MonitorSnapshot>) {
+    with_simulation(|simulation| simulation.monitors = monitors);
+  }
+
+  pub fn set_foreground_window(handle: isize) {
+    with_simulation(|simulation| simulation.foreground_window = handle);
+  }
+
+  pub fn set_mouse_position(point: Point) {
+    with_simulation(|simulation| simulation.mouse_position = point);
+  }
+}
+
+fn simulation() -> MutexGuard<'static, Option<Simulation>> {
+  SIMULATION.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs the given closure against the simulated platform.
+///
+/// Returns `None` if no simulated platform is active.
+pub(crate) fn with_simulation<T>(
+  callback: impl FnOnce(&mut Simulation) -> T,
+) -> Option<T> {
+  if !SimulatedPlatform::is_active() {
+    return None;
+  }
+
+  simulation().as_mut().map(callback)
+}
+
+/// Runs the given closure against a window of the simulated platform.
+///
+/// Returns `None` if no simulated platform is active, and an error if the
+/// window doesn't exist.
+pub(crate) fn with_window<T>(
+  handle: isize,
+  callback: impl FnOnce(&mut WindowSnapshot) -> T,
+) -> Option<anyhow::Result<T>> {
+  with_simulation(|simulation| {
+    simulation
+      .windows
+      .get_mut(&handle)
+      .filter(|window| window.is_valid)
+      .map(callback)
+      .with_context(|| format!("Window {handle} doesn't exist."))
+  })
+}
+
+/// Gets a monitor of the simulated platform.
+///
+/// Returns `None` if no simulated platform is active, and an error if the
+/// monitor doesn't exist.
+pub(crate) fn monitor(
+  handle: isize,
+) -> Option<anyhow::Result<MonitorSnapshot>> {
+  with_simulation(|simulation| {
+    simulation
+      .monitors
+      .iter()
+      .find(|monitor| monitor.handle == handle)
+      .cloned()
+      .with_context(|| format!("Monitor {handle} doesn't exist."))
+  })
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use wm_common::ContainerDto;
+
+/// Report of the WM crashing or stopping to respond, which is written
+/// next to the user config for attaching to bug reports.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+  /// Unix timestamp of when the WM stopped.
+  pub timestamp: u64,
+
+  /// Why the WM stopped (e.g. `WM stopped responding.`).
+  pub reason: String,
+
+  /// Exit code of the WM, if it was started by the watcher.
+  pub exit_code: Option<i32>,
+
+  /// Number of restarts within the restart window, including the
+  /// upcoming restart.
+  pub restart_count: u32,
+
+  /// Delay before the WM is restarted, or `None` if the watcher has
+  /// stopped restarting it.
+  pub restart_delay_ms: Option<u64>,
+
+  /// Number of windows that were restored.
+  pub restored_windows: usize,
+
+  /// Container tree as of the last heartbeat before the WM stopped.
+  pub layout_snapshot: Option<Vec<ContainerDto>>,
+}
+
+impl CrashReport {
+  /// Writes the report to `crash-<timestamp>.json` in the given directory.
+  pub fn write(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+    let path = dir.join(format!("crash-{}.json", self.timestamp));
+
+    fs::write(&path, serde_json::to_string_pretty(self)?).with_context(
+      || format!("Unable to write to {}.", path.display()),
+    )?;
+
+    Ok(path)
+  }
+}
@@ -26,127 +26,254 @@
 )]
 #![warn(clippy::all, clippy::pedantic)]
 
-use anyhow::{bail, Context};
-use tracing::info;
-use wm_common::{ClientResponseData, ContainerDto, WindowDto, WmEvent};
-use wm_ipc_client::IpcClient;
-use wm_platform::NativeWindow;
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
-  tracing_subscriber::fmt().init();
+use anyhow::Context;
+use clap::Parser;
+use tracing::{error, info, warn};
+use wm_common::{ContainerDto, WatcherConfig, DEFAULT_IPC_PORT};
+use wm_platform::{NativeWindow, Platform};
 
-  let mut client = IpcClient::connect().await?;
+use crate::{
+  crash_report::CrashReport,
+  supervisor::{Supervisor, SupervisorAction, WmExit},
+  wm_process::WmProcess,
+  wm_session::{Heartbeat, WmSession},
+};
 
-  // Get handles to windows that are already open on watcher launch.
-  let mut managed_handles = query_initial_windows(&mut client)
-    .await?
-    .into_iter()
-    .map(|window| window.handle)
-    .collect::<Vec<_>>();
+mod crash_report;
+mod supervisor;
+mod wm_process;
+mod wm_session;
 
-  // Update window handles on window manage/unmanage events.
-  let subscribe_res =
-    watch_managed_handles(&mut client, &mut managed_handles).await;
+/// Restores hidden windows if the WM exits unexpectedly, and optionally
+/// restarts the WM.
+#[derive(Debug, Parser)]
+#[clap(name = "lavawm-watcher")]
+struct WatcherArgs {
+  /// Path to the user config file, which is passed on when restarting
+  /// the WM. Crash reports are written next to it.
+  #[clap(long = "config", value_hint = clap::ValueHint::FilePath)]
+  config_path: Option<PathBuf>,
 
-  match subscribe_res {
-    Ok(()) => info!("WM exited successfully. Skipping watcher cleanup."),
-    Err(err) => {
-      info!("Running watcher cleanup. WM exited unexpectedly: {}", err);
+  /// Restarts the WM after it crashes or stops responding.
+  #[clap(long)]
+  supervise: bool,
 
-      let managed_windows = managed_handles
-        .into_iter()
-        .map(NativeWindow::new)
-        .collect::<Vec<_>>();
+  /// Process ID of an already running WM to supervise. The WM is started
+  /// by the watcher if this isn't given.
+  #[clap(long)]
+  wm_pid: Option<u32>,
 
-      for window in managed_windows {
-        window.cleanup();
-      }
+  #[clap(long, default_value_t = WatcherConfig::default().max_restarts)]
+  max_restarts: u32,
+
+  #[clap(long, default_value_t = WatcherConfig::default().restart_window_secs)]
+  restart_window_secs: u64,
+
+  #[clap(long, default_value_t = WatcherConfig::default().restart_delay_ms)]
+  restart_delay_ms: u64,
+
+  #[clap(long, default_value_t = WatcherConfig::default().max_restart_delay_ms)]
+  max_restart_delay_ms: u64,
+
+  #[clap(long, default_value_t = WatcherConfig::default().heartbeat_interval_ms)]
+  heartbeat_interval_ms: u64,
+
+  #[clap(long, default_value_t = WatcherConfig::default().heartbeat_timeout_ms)]
+  heartbeat_timeout_ms: u64,
+
+  /// Command to start the WM with instead of `lavawm start` (e.g. a fake
+  /// WM for testing).
+  #[clap(last = true)]
+  wm_command: Vec<String>,
+}
+
+impl WatcherArgs {
+  fn watcher_config(&self) -> WatcherConfig {
+    WatcherConfig {
+      supervise: self.supervise,
+      max_restarts: self.max_restarts,
+      restart_window_secs: self.restart_window_secs,
+      restart_delay_ms: self.restart_delay_ms,
+      max_restart_delay_ms: self.max_restart_delay_ms,
+      heartbeat_interval_ms: self.heartbeat_interval_ms,
+      heartbeat_timeout_ms: self.heartbeat_timeout_ms,
     }
   }
-
-  Ok(())
 }
 
-async fn query_initial_windows(
-  client: &mut IpcClient,
-) -> anyhow::Result<Vec<WindowDto>> {
-  let query_message = "query windows";
-
-  client
-    .send(query_message)
-    .await
-    .context("Failed to send window query command.")?;
-
-  client
-    .client_response(query_message)
-    .await
-    .and_then(|response| match response.data {
-      Some(ClientResponseData::Windows(data)) => Some(data),
-      _ => None,
-    })
-    .map(|data| {
-      data
-        .windows
-        .into_iter()
-        .filter_map(|container| match container {
-          ContainerDto::Window(window) => Some(window),
-          _ => None,
-        })
-        .collect::<Vec<_>>()
-    })
-    .context("Invalid data in windows query response.")
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+  tracing_subscriber::fmt().init();
+
+  let args = WatcherArgs::parse();
+
+  if args.supervise {
+    supervise(&args).await
+  } else {
+    let (session, exit) = WmSession::watch(DEFAULT_IPC_PORT, None).await;
+
+    if exit == WmExit::Exited {
+      info!("WM exited successfully. Skipping watcher cleanup.");
+    } else {
+      info!("Running watcher cleanup. {}", exit);
+      cleanup(&session.managed_handles);
+    }
+
+    Ok(())
+  }
 }
 
-async fn watch_managed_handles(
-  client: &mut IpcClient,
-  handles: &mut Vec<isize>,
-) -> anyhow::Result<()> {
-  let subscription_message =
-    "sub -e window_managed window_unmanaged application_exiting";
-
-  client
-    .send(subscription_message)
-    .await
-    .context("Failed to send subscribe command to IPC server.")?;
-
-  let subscription_id = client
-    .client_response(subscription_message)
-    .await
-    .and_then(|response| match response.data {
-      Some(ClientResponseData::EventSubscribe(data)) => {
-        Some(data.subscription_id)
-      }
-      _ => None,
-    })
-    .context("No subscription ID in watcher event subscription.")?;
+/// Watches the WM, and restarts it after it crashes or stops responding.
+///
+/// Before restarting, hidden windows are restored, and a crash report and
+/// the last layout snapshot are written. The snapshot is passed to the
+/// new WM instance, which moves windows back to their workspaces.
+async fn supervise(args: &WatcherArgs) -> anyhow::Result<()> {
+  let config = args.watcher_config();
+  let data_dir = data_dir(args.config_path.as_deref())?;
+  let layout_snapshot_path = data_dir.join("layout-snapshot.json");
+
+  let heartbeat = Heartbeat {
+    interval: Duration::from_millis(config.heartbeat_interval_ms),
+    timeout: Duration::from_millis(config.heartbeat_timeout_ms),
+  };
+
+  let mut supervisor = Supervisor::new(config);
+
+  let mut wm_process = match args.wm_pid {
+    Some(process_id) => WmProcess::External {
+      process_id: Some(process_id),
+    },
+    None => WmProcess::spawn(
+      &args.wm_command,
+      args.config_path.as_deref(),
+      None,
+    )?,
+  };
 
   loop {
-    let event_data = client
-      .event_subscription(&subscription_id)
-      .await
-      .and_then(|event| event.data);
-
-    match event_data {
-      Some(WmEvent::WindowManaged { managed_window }) => {
-        if let ContainerDto::Window(window) = managed_window {
-          info!("Watcher added handle: {}.", window.handle);
-          handles.push(window.handle);
-        }
-      }
-      Some(WmEvent::WindowUnmanaged {
-        unmanaged_handle, ..
-      }) => {
-        info!("Watcher removed handle: {}.", unmanaged_handle);
-        handles.retain(|&handle| handle != unmanaged_handle);
+    let (session, exit) =
+      WmSession::watch(DEFAULT_IPC_PORT, Some(&heartbeat)).await;
+
+    // A WM that crashed might still be running (e.g. if only its IPC
+    // connection failed). It would otherwise keep managing the windows
+    // that are restored below, and prevent the restarted WM from starting
+    // by holding the single instance lock.
+    if exit != WmExit::Exited {
+      if exit == WmExit::Hung {
+        warn!("WM stopped responding. Terminating it.");
       }
-      Some(WmEvent::ApplicationExiting) => {
-        return Ok(());
+
+      if let Err(err) = wm_process.terminate().await {
+        warn!("Failed to terminate WM: {:#}", err);
       }
-      Some(_) => unreachable!(),
-      None => {
-        bail!("IPC connection closed unexpectedly.")
+    }
+
+    let action = supervisor.on_wm_stopped(&exit, Instant::now());
+
+    if action == SupervisorAction::Stop {
+      info!("WM exited successfully. Stopping watcher.");
+      return Ok(());
+    }
+
+    warn!(
+      "{} Restoring {} windows.",
+      exit,
+      session.managed_handles.len()
+    );
+
+    cleanup(&session.managed_handles);
+
+    let restore_layout_path = match &session.layout_snapshot {
+      Some(snapshot) => {
+        match write_layout_snapshot(&layout_snapshot_path, snapshot) {
+          Ok(()) => Some(layout_snapshot_path.as_path()),
+          Err(err) => {
+            warn!("Failed to write layout snapshot: {:#}", err);
+            None
+          }
+        }
       }
+      None => None,
+    };
+
+    let restart_delay = match action {
+      SupervisorAction::Restart { delay } => Some(delay),
+      _ => None,
+    };
+
+    let crash_report = CrashReport {
+      timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+      reason: exit.to_string(),
+      exit_code: wm_process.exit_code().await,
+      restart_count: supervisor.restart_count(),
+      restart_delay_ms: restart_delay
+        .map(|delay| u64::try_from(delay.as_millis()).unwrap_or(u64::MAX)),
+      restored_windows: session.managed_handles.len(),
+      layout_snapshot: session.layout_snapshot,
+    };
+
+    match crash_report.write(&data_dir) {
+      Ok(path) => info!("Crash report written to: {}", path.display()),
+      Err(err) => warn!("Failed to write crash report: {:#}", err),
     }
+
+    let Some(restart_delay) = restart_delay else {
+      let message = format!(
+        "WM was restarted {} times within {} seconds. Stopping restarts.",
+        supervisor.restart_count(),
+        args.restart_window_secs
+      );
+
+      error!("{}", message);
+      Platform::show_error_dialog("Fatal error", &message);
+      return Ok(());
+    };
+
+    info!("Restarting WM in {:?}.", restart_delay);
+    tokio::time::sleep(restart_delay).await;
+
+    wm_process = WmProcess::spawn(
+      &args.wm_command,
+      args.config_path.as_deref(),
+      restore_layout_path,
+    )?;
+  }
+}
+
+/// Writes the layout snapshot to be restored by the restarted WM.
+fn write_layout_snapshot(
+  path: &Path,
+  snapshot: &[ContainerDto],
+) -> anyhow::Result<()> {
+  let snapshot = serde_json::to_string(snapshot)?;
+
+  fs::write(path, snapshot)
+    .with_context(|| format!("Unable to write to {}.", path.display()))
+}
+
+/// Restores windows that were hidden by the WM.
+fn cleanup(managed_handles: &[isize]) {
+  for handle in managed_handles {
+    NativeWindow::new(*handle).cleanup();
+  }
+}
+
+/// Gets the directory to write crash reports and layout snapshots to,
+/// which is the directory of the user config.
+fn data_dir(config_path: Option<&Path>) -> anyhow::Result<PathBuf> {
+  match config_path.and_then(Path::parent) {
+    Some(dir) => Ok(dir.to_path_buf()),
+    None => Ok(
+      home::home_dir()
+        .context("Unable to get home directory.")?
+        .join(".glzr/lavawm/"),
+    ),
   }
 }
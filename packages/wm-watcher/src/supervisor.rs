@@ -0,0 +1,215 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  collections::VecDeque,
+  fmt,
+  time::{Duration, Instant},
+};
+
+use wm_common::WatcherConfig;
+
+/// How a watched WM instance stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WmExit {
+  /// The WM exited normally (e.g. via `wm-exit`).
+  Exited,
+
+  /// The WM stopped without exiting normally, e.g. the IPC connection was
+  /// lost or the WM couldn't be connected to after starting it.
+  Crashed(String),
+
+  /// The WM didn't respond to a heartbeat in time.
+  Hung,
+}
+
+impl fmt::Display for WmExit {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      WmExit::Exited => write!(f, "WM exited normally."),
+      WmExit::Crashed(err) => write!(f, "WM crashed: {err}"),
+      WmExit::Hung => write!(f, "WM stopped responding."),
+    }
+  }
+}
+
+/// What to do after a watched WM instance stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SupervisorAction {
+  /// Stop watching, since the WM exited normally.
+  Stop,
+
+  /// Restart the WM after the given delay.
+  Restart { delay: Duration },
+
+  /// Stop restarting, since the WM has been restarted too often within
+  /// the restart window.
+  GiveUp,
+}
+
+/// Decides whether to restart the WM after it stops, with an exponential
+/// backoff and a limit on the number of restarts within a period.
+///
+/// The current time is passed in rather than read from the clock, which
+/// keeps the decisions independent of the platform and the WM process.
+#[derive(Debug)]
+pub struct Supervisor {
+  config: WatcherConfig,
+
+  /// Times of restarts within the restart window, oldest first.
+  restarts: VecDeque<Instant>,
+}
+
+impl Supervisor {
+  pub fn new(config: WatcherConfig) -> Self {
+    Self {
+      config,
+      restarts: VecDeque::new(),
+    }
+  }
+
+  /// Gets the action to take after the WM stopped at the given time.
+  pub fn on_wm_stopped(
+    &mut self,
+    exit: &WmExit,
+    now: Instant,
+  ) -> SupervisorAction {
+    if *exit == WmExit::Exited {
+      return SupervisorAction::Stop;
+    }
+
+    // Forget about restarts that are outside of the restart window.
+    let restart_window =
+      Duration::from_secs(self.config.restart_window_secs);
+
+    while self
+      .restarts
+      .front()
+      .is_some_and(|restart| now.duration_since(*restart) > restart_window)
+    {
+      self.restarts.pop_front();
+    }
+
+    let restart_count = self.restart_count();
+
+    if restart_count >= self.config.max_restarts {
+      return SupervisorAction::GiveUp;
+    }
+
+    self.restarts.push_back(now);
+
+    let delay_ms = self
+      .config
+      .restart_delay_ms
+      .saturating_mul(2u64.saturating_pow(restart_count))
+      .min(self.config.max_restart_delay_ms);
+
+    SupervisorAction::Restart {
+      delay: Duration::from_millis(delay_ms),
+    }
+  }
+
+  /// Number of restarts within the restart window.
+  pub fn restart_count(&self) -> u32 {
+    u32::try_from(self.restarts.len()).unwrap_or(u32::MAX)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn supervisor() -> Supervisor {
+    Supervisor::new(WatcherConfig {
+      max_restarts: 3,
+      restart_window_secs: 60,
+      restart_delay_ms: 1000,
+      max_restart_delay_ms: 3000,
+      ..WatcherConfig::default()
+    })
+  }
+
+  fn restart_delay_ms(action: &SupervisorAction) -> u128 {
+    match action {
+      SupervisorAction::Restart { delay } => delay.as_millis(),
+      action => panic!("expected a restart, got {action:?}"),
+    }
+  }
+
+  #[test]
+  fn stops_when_wm_exits_normally() {
+    let mut supervisor = supervisor();
+
+    assert_eq!(
+      supervisor.on_wm_stopped(&WmExit::Exited, Instant::now()),
+      SupervisorAction::Stop
+    );
+    assert_eq!(supervisor.restart_count(), 0);
+  }
+
+  #[test]
+  fn doubles_delay_up_to_max_restart_delay() {
+    let mut supervisor = supervisor();
+    let now = Instant::now();
+
+    let delays = [0, 1, 2]
+      .map(|secs| {
+        supervisor
+          .on_wm_stopped(&WmExit::Hung, now + Duration::from_secs(secs))
+      })
+      .map(|action| restart_delay_ms(&action));
+
+    // The third delay of 4000ms is clamped to `max_restart_delay_ms`.
+    assert_eq!(delays, [1000, 2000, 3000]);
+  }
+
+  #[test]
+  fn gives_up_after_max_restarts_within_window() {
+    let mut supervisor = supervisor();
+    let now = Instant::now();
+    let exit = WmExit::Crashed("IPC connection lost.".to_string());
+
+    for secs in 0..3 {
+      supervisor.on_wm_stopped(&exit, now + Duration::from_secs(secs));
+    }
+
+    assert_eq!(
+      supervisor.on_wm_stopped(&exit, now + Duration::from_secs(3)),
+      SupervisorAction::GiveUp
+    );
+    assert_eq!(supervisor.restart_count(), 3);
+  }
+
+  #[test]
+  fn forgets_restarts_outside_of_window() {
+    let mut supervisor = supervisor();
+    let now = Instant::now();
+
+    for secs in 0..3 {
+      supervisor
+        .on_wm_stopped(&WmExit::Hung, now + Duration::from_secs(secs));
+    }
+
+    // Only the restart at 2s is still within the window at 62s.
+    let action = supervisor
+      .on_wm_stopped(&WmExit::Hung, now + Duration::from_secs(62));
+
+    assert_eq!(restart_delay_ms(&action), 2000);
+    assert_eq!(supervisor.restart_count(), 2);
+  }
+}
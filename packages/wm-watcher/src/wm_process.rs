@@ -0,0 +1,118 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{env, path::Path, time::Duration};
+
+use anyhow::Context;
+use tokio::{process::Command, time};
+use wm_common::SUPERVISED_ENV_VAR;
+use wm_platform::Platform;
+
+/// How long to wait for the WM to exit after it crashed or was
+/// terminated.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A WM process that is supervised by the watcher.
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum WmProcess {
+  /// WM that was started independently of the watcher (e.g. the WM that
+  /// started the watcher), which is only known by its process ID.
+  External { process_id: Option<u32> },
+
+  /// WM that was started by the watcher.
+  Child(tokio::process::Child),
+}
+
+impl WmProcess {
+  /// Starts the WM with the given command, or with `lavawm start` if the
+  /// command is empty.
+  pub fn spawn(
+    wm_command: &[String],
+    config_path: Option<&Path>,
+    restore_layout_path: Option<&Path>,
+  ) -> anyhow::Result<Self> {
+    let mut command = if wm_command.is_empty() {
+      default_wm_command(config_path, restore_layout_path)?
+    } else {
+      let mut command = Command::new(&wm_command[0]);
+      command.args(&wm_command[1..]);
+      command
+    };
+
+    // Prevent the WM from starting a watcher of its own.
+    command.env(SUPERVISED_ENV_VAR, "1");
+
+    let child = command.spawn().context("Failed to start WM.")?;
+
+    Ok(WmProcess::Child(child))
+  }
+
+  /// Forcefully terminates the WM, e.g. if it has stopped responding.
+  pub async fn terminate(&mut self) -> anyhow::Result<()> {
+    match self {
+      WmProcess::External { process_id } => match process_id {
+        Some(process_id) => Platform::terminate_process(*process_id),
+        None => anyhow::bail!("Process ID of the WM is unknown."),
+      },
+      WmProcess::Child(child) => {
+        child.kill().await.context("Failed to terminate WM.")
+      }
+    }
+  }
+
+  /// Waits for the WM to exit and gets its exit code. This is only known
+  /// for a WM that was started by the watcher.
+  pub async fn exit_code(&mut self) -> Option<i32> {
+    match self {
+      WmProcess::External { .. } => None,
+      WmProcess::Child(child) => time::timeout(EXIT_TIMEOUT, child.wait())
+        .await
+        .ok()?
+        .ok()?
+        .code(),
+    }
+  }
+}
+
+/// Gets the command for starting the WM with `lavawm start`.
+///
+/// This assumes the WM binary exists in the same directory as the watcher
+/// binary.
+fn default_wm_command(
+  config_path: Option<&Path>,
+  restore_layout_path: Option<&Path>,
+) -> anyhow::Result<Command> {
+  let wm_path = env::current_exe()?
+    .parent()
+    .context("Failed to resolve path to the WM.")?
+    .join("lavawm");
+
+  let mut command = Command::new(wm_path);
+  command.arg("start");
+
+  if let Some(config_path) = config_path {
+    command.arg("--config").arg(config_path);
+  }
+
+  if let Some(restore_layout_path) = restore_layout_path {
+    command.arg("--restore-layout").arg(restore_layout_path);
+  }
+
+  Ok(command)
+}
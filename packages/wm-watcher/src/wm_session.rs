@@ -0,0 +1,333 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  future,
+  time::{Duration, Instant},
+};
+
+use futures_util::StreamExt;
+use tokio::time;
+use tracing::{debug, info};
+use wm_common::{ContainerDto, SubscribableEvent, WmEvent};
+use wm_ipc_client::{IpcClient, IpcClientOptions};
+
+use crate::supervisor::WmExit;
+
+/// How long to wait for the IPC server of a starting WM.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Delay between attempts to connect to the IPC server.
+const CONNECT_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Heartbeat settings for detecting a WM that has stopped responding.
+#[derive(Clone, Debug)]
+pub struct Heartbeat {
+  pub interval: Duration,
+  pub timeout: Duration,
+}
+
+/// State of a watched WM instance, which is needed for cleaning up and
+/// restarting after it stops.
+#[derive(Debug, Default)]
+pub struct WmSession {
+  /// Handles of the windows that are managed by the WM.
+  pub managed_handles: Vec<isize>,
+
+  /// Container tree as of the last heartbeat (i.e. the output of `query
+  /// monitors`).
+  pub layout_snapshot: Option<Vec<ContainerDto>>,
+}
+
+impl WmSession {
+  /// Connects to the WM's IPC server on the given port and watches it
+  /// until it stops.
+  ///
+  /// The heartbeat also refreshes the layout snapshot, so a snapshot is
+  /// only taken if a heartbeat is given.
+  pub async fn watch(
+    port: u32,
+    heartbeat: Option<&Heartbeat>,
+  ) -> (Self, WmExit) {
+    let mut session = Self::default();
+
+    let client = match connect(port, heartbeat).await {
+      Ok(client) => client,
+      Err(err) => return (session, WmExit::Crashed(format!("{err:#}"))),
+    };
+
+    let exit = match session.watch_client(&client, heartbeat).await {
+      Ok(exit) => exit,
+      Err(err) => WmExit::Crashed(format!("{err:#}")),
+    };
+
+    (session, exit)
+  }
+
+  async fn watch_client(
+    &mut self,
+    client: &IpcClient,
+    heartbeat: Option<&Heartbeat>,
+  ) -> anyhow::Result<WmExit> {
+    // Get handles to windows that are already open on watcher launch.
+    self.managed_handles = client
+      .query_windows()
+      .await?
+      .into_iter()
+      .filter_map(|container| match container {
+        ContainerDto::Window(window) => Some(window.handle),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+
+    // Update window handles on window manage/unmanage events.
+    let events = client
+      .subscribe(&[
+        SubscribableEvent::WindowManaged,
+        SubscribableEvent::WindowUnmanaged,
+        SubscribableEvent::ApplicationExiting,
+      ])
+      .await?;
+
+    let mut events = Box::pin(events);
+
+    let mut heartbeat_interval = heartbeat.map(|heartbeat| {
+      let mut interval = time::interval(heartbeat.interval);
+      interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+      interval
+    });
+
+    loop {
+      tokio::select! {
+        event = events.next() => match event {
+          Some(WmEvent::WindowManaged { managed_window }) => {
+            if let ContainerDto::Window(window) = managed_window {
+              info!("Watcher added handle: {}.", window.handle);
+              self.managed_handles.push(window.handle);
+            }
+          }
+          Some(WmEvent::WindowUnmanaged {
+            unmanaged_handle, ..
+          }) => {
+            info!("Watcher removed handle: {}.", unmanaged_handle);
+            self
+              .managed_handles
+              .retain(|&handle| handle != unmanaged_handle);
+          }
+          Some(WmEvent::ApplicationExiting) => {
+            return Ok(WmExit::Exited);
+          }
+          Some(_) => {}
+          None => {
+            return Ok(WmExit::Crashed(
+              "IPC connection closed unexpectedly.".to_string(),
+            ));
+          }
+        },
+        () = tick(heartbeat_interval.as_mut()) => {
+          let Some(heartbeat) = heartbeat else {
+            continue;
+          };
+
+          match time::timeout(heartbeat.timeout, client.query_monitors())
+            .await
+          {
+            Ok(Ok(monitors)) => self.layout_snapshot = Some(monitors),
+            Ok(Err(err)) => return Ok(WmExit::Crashed(format!("{err:#}"))),
+            Err(_) => return Ok(WmExit::Hung),
+          }
+        }
+      }
+    }
+  }
+}
+
+/// Connects to the IPC server, retrying while the WM is starting up.
+async fn connect(
+  port: u32,
+  heartbeat: Option<&Heartbeat>,
+) -> anyhow::Result<IpcClient> {
+  let started_at = Instant::now();
+
+  loop {
+    // The watcher cleans up after the WM exits, so it shouldn't
+    // reconnect. Heartbeats time out on their own, so requests are given
+    // longer to respond.
+    let res = IpcClient::connect_with_options(IpcClientOptions {
+      port,
+      reconnect: false,
+      timeout: heartbeat
+        .map_or(IpcClientOptions::default().timeout, |heartbeat| {
+          heartbeat.timeout * 2
+        }),
+    })
+    .await;
+
+    match res {
+      Ok(client) => return Ok(client),
+      Err(err) if started_at.elapsed() >= CONNECT_TIMEOUT => {
+        return Err(err)
+      }
+      Err(err) => {
+        debug!("Retrying connection to IPC server: {:#}", err);
+        time::sleep(CONNECT_RETRY_DELAY).await;
+      }
+    }
+  }
+}
+
+/// Waits for the next tick of the interval, or forever if there is none.
+async fn tick(interval: Option<&mut time::Interval>) {
+  match interval {
+    Some(interval) => {
+      interval.tick().await;
+    }
+    None => future::pending().await,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use futures_util::SinkExt;
+  use tokio::net::TcpListener;
+  use tokio_tungstenite::{accept_async, tungstenite::Message};
+  use uuid::Uuid;
+  use wm_common::{
+    ClientResponseData, ClientResponseMessage, EventSubscribeData,
+    EventSubscriptionMessage, MonitorsData, ServerMessage, WindowsData,
+  };
+
+  use super::*;
+
+  /// What the mock IPC server does once the watcher has subscribed to
+  /// events.
+  #[derive(Clone, Copy)]
+  enum Scenario {
+    /// Closes the connection.
+    Close,
+    /// Stops answering, so that heartbeats time out.
+    StopAnswering,
+    /// Emits an `application_exiting` event.
+    Exit,
+  }
+
+  /// Starts a mock IPC server for a single connection, and gets its
+  /// port.
+  async fn mock_ipc_server(scenario: Scenario) -> u32 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    tokio::spawn(async move {
+      let (stream, _) = listener.accept().await.unwrap();
+      let mut stream = accept_async(stream).await.unwrap();
+
+      while let Some(Ok(Message::Text(message))) = stream.next().await {
+        let data = match message.as_str() {
+          "query windows" => ClientResponseData::Windows(WindowsData {
+            windows: Vec::new(),
+          }),
+          "query monitors" => ClientResponseData::Monitors(MonitorsData {
+            monitors: Vec::new(),
+          }),
+          message if message.starts_with("sub ") => {
+            ClientResponseData::EventSubscribe(EventSubscribeData {
+              subscription_id: Uuid::nil(),
+            })
+          }
+          _ => continue,
+        };
+
+        let is_subscription =
+          matches!(data, ClientResponseData::EventSubscribe(_));
+
+        let response =
+          ServerMessage::ClientResponse(ClientResponseMessage {
+            client_message: message.to_string(),
+            data: Some(data),
+            error: None,
+            success: true,
+          });
+
+        stream
+          .send(Message::Text(
+            serde_json::to_string(&response).unwrap().into(),
+          ))
+          .await
+          .unwrap();
+
+        if !is_subscription {
+          continue;
+        }
+
+        match scenario {
+          Scenario::Close => return,
+          Scenario::StopAnswering => future::pending::<()>().await,
+          Scenario::Exit => {
+            let event =
+              ServerMessage::EventSubscription(EventSubscriptionMessage {
+                data: Some(WmEvent::ApplicationExiting),
+                error: None,
+                subscription_id: Uuid::nil(),
+                success: true,
+              });
+
+            stream
+              .send(Message::Text(
+                serde_json::to_string(&event).unwrap().into(),
+              ))
+              .await
+              .unwrap();
+          }
+        }
+      }
+    });
+
+    u32::from(port)
+  }
+
+  fn heartbeat() -> Heartbeat {
+    Heartbeat {
+      interval: Duration::from_millis(50),
+      timeout: Duration::from_millis(200),
+    }
+  }
+
+  #[tokio::test]
+  async fn closed_connection_is_crash() {
+    let port = mock_ipc_server(Scenario::Close).await;
+    let (_, exit) = WmSession::watch(port, Some(&heartbeat())).await;
+
+    assert!(matches!(exit, WmExit::Crashed(_)), "got {exit:?}");
+  }
+
+  #[tokio::test]
+  async fn missing_heartbeat_answer_is_hang() {
+    let port = mock_ipc_server(Scenario::StopAnswering).await;
+    let (_, exit) = WmSession::watch(port, Some(&heartbeat())).await;
+
+    assert_eq!(exit, WmExit::Hung);
+  }
+
+  #[tokio::test]
+  async fn application_exiting_is_exit() {
+    let port = mock_ipc_server(Scenario::Exit).await;
+    let (_, exit) = WmSession::watch(port, Some(&heartbeat())).await;
+
+    assert_eq!(exit, WmExit::Exited);
+  }
+}
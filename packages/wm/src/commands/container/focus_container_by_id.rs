@@ -1,8 +1,29 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use uuid::Uuid;
 
 use super::set_focused_descendant;
-use crate::wm_state::WmState;
+use crate::{
+  commands::workspace::focus_workspace, models::WorkspaceTarget,
+  traits::CommonGetters, user_config::UserConfig, wm_state::WmState,
+};
 
 pub fn focus_container_by_id(
   container_id: &Uuid,
@@ -18,3 +39,28 @@ pub fn focus_container_by_id(
 
   Ok(())
 }
+
+/// Focuses the container with the given ID, and displays its workspace
+/// if it's currently hidden.
+pub fn focus_container_and_workspace(
+  container_id: &Uuid,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let workspace = state
+    .container_by_id(*container_id)
+    .context("No container with given id")?
+    .workspace();
+
+  if let Some(workspace) = workspace {
+    if !workspace.is_displayed() {
+      focus_workspace(
+        WorkspaceTarget::Name(workspace.config().name),
+        state,
+        config,
+      )?;
+    }
+  }
+
+  focus_container_by_id(container_id, state)
+}
@@ -1,10 +1,31 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
-use wm_common::{Direction, TilingDirection, WindowState};
+use wm_common::{
+  Direction, DirectionalNavigation, TilingDirection, WindowState,
+};
 
 use super::set_focused_descendant;
 use crate::{
   models::{Container, TilingContainer},
   traits::{CommonGetters, TilingDirectionGetters, WindowGetters},
+  user_config::UserConfig,
   wm_state::WmState,
 };
 
@@ -12,7 +33,32 @@ pub fn focus_in_direction(
   origin_container: &Container,
   direction: &Direction,
   state: &mut WmState,
+  config: &UserConfig,
 ) -> anyhow::Result<()> {
+  let focus_target = match config.value.general.directional_navigation {
+    DirectionalNavigation::Geometric => {
+      state.container_in_direction(origin_container, direction)?
+    }
+    DirectionalNavigation::Tree => {
+      tree_focus_target(origin_container, direction, state)?
+    }
+  };
+
+  // Set focus to the target container.
+  if let Some(focus_target) = focus_target {
+    set_focused_descendant(&focus_target, None);
+    state.pending_sync.queue_focus_change().queue_cursor_jump();
+  }
+
+  Ok(())
+}
+
+/// Gets a focus target by traversing the container tree.
+fn tree_focus_target(
+  origin_container: &Container,
+  direction: &Direction,
+  state: &WmState,
+) -> anyhow::Result<Option<Container>> {
   let focus_target = match origin_container {
     Container::TilingWindow(_) => {
       // If a suitable focus target isn't found in the current workspace,
@@ -39,13 +85,7 @@ pub fn focus_in_direction(
     _ => None,
   };
 
-  // Set focus to the target container.
-  if let Some(focus_target) = focus_target {
-    set_focused_descendant(&focus_target, None);
-    state.pending_sync.queue_focus_change().queue_cursor_jump();
-  }
-
-  Ok(())
+  Ok(focus_target)
 }
 
 fn floating_focus_target(
@@ -0,0 +1,68 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+
+use crate::{
+  models::TilingContainer,
+  traits::{CommonGetters, TilingSizeGetters},
+};
+
+/// Swaps the positions of two tiling containers in the tree, including
+/// their tiling sizes.
+///
+/// Unlike `move_container_within_tree`, the surrounding tree is left
+/// unchanged, so no split containers get flattened. Focus order within
+/// each parent is kept by position, meaning focus needs to be reassigned
+/// afterwards if a focused container is swapped across parents.
+pub fn swap_tiling_containers(
+  container_a: &TilingContainer,
+  container_b: &TilingContainer,
+) -> anyhow::Result<()> {
+  let parent_a = container_a.parent().context("No parent.")?;
+  let parent_b = container_b.parent().context("No parent.")?;
+  let index_a = container_a.index();
+  let index_b = container_b.index();
+
+  if parent_a == parent_b {
+    parent_a.borrow_children_mut().swap(index_a, index_b);
+  } else {
+    parent_a.borrow_children_mut()[index_a] = container_b.clone().into();
+    parent_b.borrow_children_mut()[index_b] = container_a.clone().into();
+
+    *container_a.borrow_parent_mut() = Some(parent_b.clone());
+    *container_b.borrow_parent_mut() = Some(parent_a.clone());
+
+    for (parent, old_id, new_id) in [
+      (&parent_a, container_a.id(), container_b.id()),
+      (&parent_b, container_b.id(), container_a.id()),
+    ] {
+      for id in parent.borrow_child_focus_order_mut().iter_mut() {
+        if *id == old_id {
+          *id = new_id;
+        }
+      }
+    }
+  }
+
+  let tiling_size_a = container_a.tiling_size();
+  container_a.set_tiling_size(container_b.tiling_size());
+  container_b.set_tiling_size(tiling_size_a);
+
+  Ok(())
+}
@@ -0,0 +1,103 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  fs::{self, File},
+  path::{Path, PathBuf},
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use flate2::{write::GzEncoder, Compression};
+use serde_json::json;
+use tar::{Builder, Header};
+use tracing::info;
+use wm_platform::{NativeMonitor, Platform};
+
+use crate::{logging, user_config::UserConfig, wm_state::WmState};
+
+/// Writes a `.tar.gz` bundle for bug reports, with the container tree,
+/// the user config, monitor metadata, recent logs and the WM version.
+pub fn dump_diagnostics(
+  path: Option<&Path>,
+  state: &WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let timestamp = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|duration| duration.as_secs())
+    .unwrap_or_default();
+
+  let path = path.map_or_else(
+    || {
+      config
+        .path
+        .with_file_name(format!("diagnostics-{timestamp}.tar.gz"))
+    },
+    PathBuf::from,
+  );
+
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+
+  let file = File::create(&path).with_context(|| {
+    format!("Unable to create diagnostics file at {}.", path.display())
+  })?;
+
+  let metadata = json!({
+    "version": env!("VERSION_NUMBER"),
+    "timestamp": timestamp,
+    "isPaused": state.is_paused,
+    "bindingModes": state.binding_modes,
+  });
+
+  let monitors = Platform::sorted_monitors()?
+    .iter()
+    .map(NativeMonitor::snapshot)
+    .try_collect::<Vec<_>>()?;
+
+  let mut builder =
+    Builder::new(GzEncoder::new(file, Compression::default()));
+
+  let mut append = |name: &str, contents: &[u8]| {
+    let mut header = Header::new_gnu();
+    header.set_size(contents.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(timestamp);
+    header.set_cksum();
+
+    builder
+      .append_data(&mut header, name, contents)
+      .with_context(|| format!("Failed to write {name} to diagnostics."))
+  };
+
+  append("metadata.json", &serde_json::to_vec_pretty(&metadata)?)?;
+  append(
+    "tree.json",
+    &serde_json::to_vec_pretty(&state.root_container.to_dto()?)?,
+  )?;
+  append("monitors.json", &serde_json::to_vec_pretty(&monitors)?)?;
+  append("config.yaml", config.value_str.as_bytes())?;
+  append("logs.txt", logging::recent_logs().join("\n").as_bytes())?;
+
+  builder.into_inner()?.finish()?;
+  info!("Wrote diagnostics to {}.", path.display());
+
+  Ok(())
+}
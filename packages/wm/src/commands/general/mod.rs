@@ -1,15 +1,37 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 mod cycle_focus;
 mod disable_binding_mode;
+mod dump_diagnostics;
 mod enable_binding_mode;
 mod platform_sync;
 mod reload_config;
+mod restore_layout;
 mod shell_exec;
 mod toggle_pause;
 
 pub use cycle_focus::*;
 pub use disable_binding_mode::*;
+pub use dump_diagnostics::*;
 pub use enable_binding_mode::*;
 pub use platform_sync::*;
 pub use reload_config::*;
+pub use restore_layout::*;
 pub use shell_exec::*;
 pub use toggle_pause::*;
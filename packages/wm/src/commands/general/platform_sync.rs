@@ -1,17 +1,43 @@
-use std::time::Duration;
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  collections::{hash_map::Entry, HashMap},
+  time::{Duration, Instant},
+};
 
 use anyhow::Context;
 use tokio::task;
-use tracing::{info, warn};
+use tracing::{debug_span, info, warn};
+use uuid::Uuid;
 use wm_common::{
-  CornerStyle, CursorJumpTrigger, DisplayState, HideMethod, OpacityValue,
-  UniqueExt, WindowEffectConfig, WindowState, WmEvent,
+  Color, CornerStyle, CursorJumpTrigger, DisplayState, HideMethod,
+  OpacityValue, Rect, TitleBarVisibility, UniqueExt, WindowEffectConfig,
+  WindowState, WmEvent,
 };
 use wm_platform::{Platform, ZOrder};
 
 use crate::{
+  commands::window::set_window_urgency,
   models::{Container, WindowContainer},
-  traits::{CommonGetters, PositionGetters, WindowGetters},
+  traits::{
+    tiling_child_rects, CommonGetters, PositionGetters, WindowGetters,
+  },
   user_config::UserConfig,
   wm_state::WmState,
 };
@@ -20,6 +46,15 @@ pub fn platform_sync(
   state: &mut WmState,
   config: &UserConfig,
 ) -> anyhow::Result<()> {
+  let _span = debug_span!(
+    "platform_sync",
+    containers_to_redraw = state.pending_sync.containers_to_redraw().len(),
+  )
+  .entered();
+
+  let started_at = Instant::now();
+  let mut windows_redrawn = 0;
+
   let focused_container =
     state.focused_container().context("No focused container.")?;
 
@@ -27,10 +62,23 @@ pub fn platform_sync(
     sync_focus(&focused_container, state)?;
   }
 
+  if let Ok(window) = focused_container.as_window_container() {
+    state.focus_history.record(window.id());
+
+    // Focusing a window clears its urgency.
+    set_window_urgency(&window, false, state)?;
+  }
+
   if !state.pending_sync.containers_to_redraw().is_empty()
     || !state.pending_sync.workspaces_to_reorder().is_empty()
   {
-    redraw_containers(&focused_container, state, config)?;
+    let redraw_started_at = Instant::now();
+    windows_redrawn =
+      redraw_containers(&focused_container, state, config)?;
+
+    state
+      .metrics
+      .record_redraw_containers(redraw_started_at.elapsed());
   }
 
   if state.pending_sync.needs_cursor_jump()
@@ -71,8 +119,20 @@ pub fn platform_sync(
     }
   }
 
+  // Apply effects to windows where the effect overrides have changed.
+  for window in state.pending_sync.windows_to_update_effects().values() {
+    if !window.is_detached() {
+      let is_focused = window.id() == focused_container.id();
+      apply_window_effects(window, is_focused, config);
+    }
+  }
+
   state.pending_sync.clear();
 
+  state
+    .metrics
+    .record_platform_sync(windows_redrawn, started_at.elapsed());
+
   Ok(())
 }
 
@@ -165,12 +225,48 @@ fn windows_to_bring_to_front(
   Ok(windows_to_bring_to_front)
 }
 
+/// Gets the rect of a window.
+///
+/// For tiling windows, the rects of all tiling siblings are computed at
+/// once and cached, so that size constraints are only retrieved once per
+/// parent when redrawing.
+fn window_rect(
+  window: &WindowContainer,
+  tiling_rects_by_parent: &mut HashMap<Uuid, Vec<Rect>>,
+) -> anyhow::Result<Rect> {
+  let WindowContainer::TilingWindow(tiling_window) = window else {
+    return window.to_rect();
+  };
+
+  let parent = tiling_window
+    .direction_container()
+    .context("Parent does not have a tiling direction.")?;
+
+  let index = parent
+    .tiling_children()
+    .position(|child| child.id() == window.id())
+    .context("Container is not a tiling child of its parent.")?;
+
+  let rects = match tiling_rects_by_parent.entry(parent.id()) {
+    Entry::Occupied(entry) => entry.into_mut(),
+    Entry::Vacant(entry) => entry.insert(tiling_child_rects(&parent)?),
+  };
+
+  rects
+    .get(index)
+    .cloned()
+    .context("Container is not a tiling child of its parent.")
+}
+
+/// Updates the position and z-order of windows that need to be redrawn.
+///
+/// Returns the number of updated windows.
 #[allow(clippy::too_many_lines)]
 fn redraw_containers(
   focused_container: &Container,
   state: &mut WmState,
   config: &UserConfig,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<usize> {
   let windows_to_redraw = state.windows_to_redraw();
   let windows_to_bring_to_front =
     windows_to_bring_to_front(focused_container, state)?;
@@ -198,6 +294,9 @@ fn redraw_containers(
     windows
   };
 
+  // Rects of tiling children, keyed by the ID of their parent.
+  let mut tiling_rects_by_parent = HashMap::new();
+
   for window in windows_to_update.iter().rev() {
     let should_bring_to_front = windows_to_bring_to_front.contains(window);
 
@@ -243,10 +342,22 @@ fn redraw_containers(
       continue;
     }
 
+    // On monocle workspaces, the zoomed window fills the workspace and
+    // other tiling windows are hidden.
+    let monocle_window_id =
+      workspace.monocle_window().map(|window| window.id());
+    let is_monocle_window = monocle_window_id == Some(window.id());
+    let is_hidden_by_monocle = monocle_window_id.is_some()
+      && !is_monocle_window
+      && window.state() == WindowState::Tiling;
+
     // Transition display state depending on whether window will be
-    // shown or hidden.
+    // shown or hidden. Sticky windows are never hidden.
+    let should_show = (workspace.is_displayed() && !is_hidden_by_monocle)
+      || window.sticky().is_some();
+
     window.set_display_state(
-      match (window.display_state(), workspace.is_displayed()) {
+      match (window.display_state(), should_show) {
         (DisplayState::Hidden | DisplayState::Hiding, true) => {
           DisplayState::Showing
         }
@@ -257,15 +368,21 @@ fn redraw_containers(
       },
     );
 
-    let rect = window
-      .to_rect()?
-      .apply_delta(&window.total_border_delta()?, None);
+    let rect = if is_monocle_window {
+      workspace.to_rect()?
+    } else {
+      window_rect(window, &mut tiling_rects_by_parent)?
+    }
+    .apply_delta(&window.total_border_delta()?, None);
 
     let is_visible = matches!(
       window.display_state(),
       DisplayState::Showing | DisplayState::Shown
     );
 
+    let hide_method =
+      config.hide_method_for_workspace(window.workspace().as_ref());
+
     info!("Updating window position: {window}");
 
     if let Err(err) = window.native().set_position(
@@ -273,7 +390,7 @@ fn redraw_containers(
       &rect,
       &z_order,
       is_visible,
-      &config.value.general.hide_method,
+      &hide_method,
       window.has_pending_dpi_adjustment(),
     ) {
       warn!("Failed to set window position: {}", err);
@@ -302,7 +419,7 @@ fn redraw_containers(
     // effect). Since cloaked windows are normally always visible in the
     // taskbar, we only need to set visibility if `show_all_in_taskbar` is
     // `false`.
-    if config.value.general.hide_method == HideMethod::Cloak
+    if hide_method == HideMethod::Cloak
       && !config.value.general.show_all_in_taskbar
       && matches!(
         window.display_state(),
@@ -316,7 +433,7 @@ fn redraw_containers(
     }
   }
 
-  Ok(())
+  Ok(windows_to_update.len())
 }
 
 fn jump_cursor(
@@ -360,6 +477,7 @@ fn apply_window_effects(
   config: &UserConfig,
 ) {
   let window_effects = &config.value.window_effects;
+  let overrides = window.effect_overrides();
 
   let effect_config = if is_focused {
     &window_effects.focused_window
@@ -367,37 +485,61 @@ fn apply_window_effects(
     &window_effects.other_windows
   };
 
-  // Skip if both focused + non-focused window effects are disabled.
+  // Skip if both focused + non-focused window effects are disabled, and
+  // the effect isn't overridden for the window.
   if window_effects.focused_window.border.enabled
     || window_effects.other_windows.border.enabled
+    || overrides.border_color.is_some()
   {
-    apply_border_effect(window, effect_config);
+    apply_border_effect(
+      window,
+      effect_config,
+      overrides.border_color.as_ref(),
+    );
   }
 
   if window_effects.focused_window.hide_title_bar.enabled
     || window_effects.other_windows.hide_title_bar.enabled
+    || overrides.title_bar.is_some()
   {
-    apply_hide_title_bar_effect(window, effect_config);
+    apply_hide_title_bar_effect(
+      window,
+      effect_config,
+      overrides.title_bar.as_ref(),
+    );
   }
 
   if window_effects.focused_window.corner_style.enabled
     || window_effects.other_windows.corner_style.enabled
+    || overrides.corner_style.is_some()
   {
-    apply_corner_effect(window, effect_config);
+    apply_corner_effect(
+      window,
+      effect_config,
+      overrides.corner_style.as_ref(),
+    );
   }
 
   if window_effects.focused_window.transparency.enabled
     || window_effects.other_windows.transparency.enabled
+    || overrides.transparency.is_some()
   {
-    apply_transparency_effect(window, effect_config);
+    apply_transparency_effect(
+      window,
+      effect_config,
+      overrides.transparency.as_ref(),
+    );
   }
 }
 
 fn apply_border_effect(
   window: &WindowContainer,
   effect_config: &WindowEffectConfig,
+  color_override: Option<&Color>,
 ) {
-  let border_color = if effect_config.border.enabled {
+  let border_color = if color_override.is_some() {
+    color_override
+  } else if effect_config.border.enabled {
     Some(&effect_config.border.color)
   } else {
     None
@@ -419,17 +561,24 @@ fn apply_border_effect(
 fn apply_hide_title_bar_effect(
   window: &WindowContainer,
   effect_config: &WindowEffectConfig,
+  visibility_override: Option<&TitleBarVisibility>,
 ) {
-  _ = window
-    .native()
-    .set_title_bar_visibility(!effect_config.hide_title_bar.enabled);
+  let is_visible = match visibility_override {
+    Some(visibility) => *visibility == TitleBarVisibility::Shown,
+    None => !effect_config.hide_title_bar.enabled,
+  };
+
+  _ = window.native().set_title_bar_visibility(is_visible);
 }
 
 fn apply_corner_effect(
   window: &WindowContainer,
   effect_config: &WindowEffectConfig,
+  style_override: Option<&CornerStyle>,
 ) {
-  let corner_style = if effect_config.corner_style.enabled {
+  let corner_style = if let Some(style) = style_override {
+    style
+  } else if effect_config.corner_style.enabled {
     &effect_config.corner_style.style
   } else {
     &CornerStyle::Default
@@ -441,8 +590,11 @@ fn apply_corner_effect(
 fn apply_transparency_effect(
   window: &WindowContainer,
   effect_config: &WindowEffectConfig,
+  opacity_override: Option<&OpacityValue>,
 ) {
-  let transparency = if effect_config.transparency.enabled {
+  let transparency = if let Some(opacity) = opacity_override {
+    opacity
+  } else if effect_config.transparency.enabled {
     &effect_config.transparency.opacity
   } else {
     // Reset the transparency to default.
@@ -1,9 +1,28 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use tracing::{info, warn};
 use wm_common::{HideMethod, ParsedConfig, WindowRuleEvent, WmEvent};
 
 use crate::{
   commands::{window::run_window_rules, workspace::sort_workspaces},
+  runtime_workspaces::merge_workspace_configs,
   traits::{CommonGetters, TilingSizeGetters, WindowGetters},
   user_config::UserConfig,
   wm::WindowManager,
@@ -28,7 +47,10 @@ pub fn reload_config(
     run_window_rules(window, &WindowRuleEvent::Manage, state, config)?;
   }
 
-  update_workspace_configs(state, config)?;
+  state.workspace_configs =
+    merge_workspace_configs(&state.workspace_configs, config);
+
+  update_workspace_configs(state)?;
 
   update_container_gaps(state, config);
 
@@ -70,7 +92,7 @@ pub fn reload_config(
       .context("Invalid config path.")?
       .to_string(),
     config_string: config.value_str.clone(),
-    parsed_config: config.value.clone(),
+    parsed_config: Box::new(config.value.clone()),
   });
 
   // Run config reload commands.
@@ -85,28 +107,23 @@ pub fn reload_config(
 }
 
 /// Update configs of active workspaces.
-fn update_workspace_configs(
-  state: &mut WmState,
-  config: &UserConfig,
-) -> anyhow::Result<()> {
+fn update_workspace_configs(state: &WmState) -> anyhow::Result<()> {
   let workspaces = state.workspaces();
 
   for workspace in &workspaces {
     let monitor = workspace.monitor().context("No monitor.")?;
 
-    let workspace_config = config
-      .value
-      .workspaces
-      .iter()
-      .find(|config| config.name == workspace.config().name)
+    let workspace_config = state
+      .workspace_config(&workspace.config().name)
       .or_else(|| {
         // When the workspace config is not found, the current name of the
         // workspace has been removed. So, we reassign the first suitable
         // workspace config to the workspace.
-        config
-          .workspace_config_for_monitor(&monitor, &workspaces)
-          .or_else(|| config.next_inactive_workspace_config(&workspaces))
-      });
+        state
+          .workspace_config_for_monitor(&monitor)
+          .or_else(|| state.next_inactive_workspace_config())
+      })
+      .cloned();
 
     match workspace_config {
       None => {
@@ -115,10 +132,10 @@ fn update_workspace_configs(
         );
       }
       Some(workspace_config) => {
-        if *workspace_config != workspace.config() {
-          workspace.set_config(workspace_config.clone());
+        if workspace_config != workspace.config() {
+          workspace.set_config(workspace_config);
 
-          sort_workspaces(&monitor, config)?;
+          sort_workspaces(&monitor, state)?;
 
           state.emit_event(WmEvent::WorkspaceUpdated {
             updated_workspace: workspace.to_dto()?,
@@ -133,17 +150,18 @@ fn update_workspace_configs(
 
 /// Updates outer gap of workspaces and inner gaps of tiling containers.
 fn update_container_gaps(state: &mut WmState, config: &UserConfig) {
-  let tiling_containers = state
-    .root_container
-    .self_and_descendants()
-    .filter_map(|container| container.as_tiling_container().ok());
+  for workspace in state.workspaces() {
+    let gaps_config = config.gaps_for_workspace(&workspace.config());
 
-  for container in tiling_containers {
-    container.set_gaps_config(config.value.gaps.clone());
-  }
+    let tiling_containers = workspace
+      .descendants()
+      .filter_map(|container| container.as_tiling_container().ok());
 
-  for workspace in state.workspaces() {
-    workspace.set_gaps_config(config.value.gaps.clone());
+    for container in tiling_containers {
+      container.set_gaps_config(gaps_config.clone());
+    }
+
+    workspace.set_gaps_config(gaps_config);
   }
 }
 
@@ -0,0 +1,139 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use tracing::{info, warn};
+use wm_common::{ContainerDto, WindowDto};
+use wm_platform::NativeWindow;
+
+use crate::{
+  commands::{
+    container::focus_container_and_workspace,
+    window::{move_window_to_workspace, update_window_state},
+  },
+  models::WorkspaceTarget,
+  traits::CommonGetters,
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Reads a layout snapshot, which is the output of `query monitors` from
+/// a previous instance.
+pub fn read_layout_snapshot(
+  path: &Path,
+) -> anyhow::Result<Vec<ContainerDto>> {
+  let snapshot = fs::read_to_string(path)
+    .with_context(|| format!("Unable to read {}.", path.display()))?;
+
+  serde_json::from_str::<Vec<ContainerDto>>(&snapshot)
+    .context("Invalid layout snapshot.")
+}
+
+/// Moves windows back to the workspaces and states of a layout snapshot.
+///
+/// Windows are matched by their handle, since handles stay the same when
+/// the WM is restarted. Windows that no longer exist are skipped, and the
+/// order of tiling windows is only restored on a best-effort basis.
+pub fn restore_layout(
+  monitors: &[ContainerDto],
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let mut snapshot_windows = Vec::new();
+
+  for monitor in monitors {
+    if let ContainerDto::Monitor(monitor) = monitor {
+      for workspace in &monitor.children {
+        if let ContainerDto::Workspace(workspace) = workspace {
+          collect_windows(
+            &workspace.name,
+            &workspace.children,
+            &mut snapshot_windows,
+          );
+        }
+      }
+    }
+  }
+
+  info!("Restoring layout of {} windows.", snapshot_windows.len());
+
+  let mut focused_window_id = None;
+
+  for (workspace_name, window_dto) in snapshot_windows {
+    let Some(window) =
+      state.window_from_native(&NativeWindow::new(window_dto.handle))
+    else {
+      continue;
+    };
+
+    let is_on_workspace = window
+      .workspace()
+      .is_some_and(|workspace| workspace.config().name == workspace_name);
+
+    if !is_on_workspace {
+      if let Err(err) = move_window_to_workspace(
+        window.clone(),
+        WorkspaceTarget::Name(workspace_name.to_string()),
+        state,
+        config,
+      ) {
+        warn!("Failed to restore workspace of window: {:?}", err);
+        continue;
+      }
+    }
+
+    let window = update_window_state(
+      window,
+      window_dto.state.clone(),
+      state,
+      config,
+    )?;
+
+    if window_dto.has_focus {
+      focused_window_id = Some(window.id());
+    }
+  }
+
+  if let Some(window_id) = focused_window_id {
+    focus_container_and_workspace(&window_id, state, config)?;
+  }
+
+  Ok(())
+}
+
+/// Collects the windows within a workspace in depth-first order, along
+/// with the name of the workspace.
+fn collect_windows<'a>(
+  workspace_name: &'a str,
+  children: &'a [ContainerDto],
+  windows: &mut Vec<(&'a str, &'a WindowDto)>,
+) {
+  for child in children {
+    match child {
+      ContainerDto::Window(window) => {
+        windows.push((workspace_name, window));
+      }
+      ContainerDto::Split(split) => {
+        collect_windows(workspace_name, &split.children, windows);
+      }
+      _ => {}
+    }
+  }
+}
@@ -68,15 +68,16 @@ pub fn move_bounded_workspaces_to_new_monitor(
   state: &mut WmState,
   config: &UserConfig,
 ) -> anyhow::Result<()> {
-  let bound_workspace_configs = config
-    .value
-    .workspaces
+  let bound_workspace_configs = state
+    .workspace_configs
     .iter()
+    .map(|workspace_config| &workspace_config.value)
     .filter(|config| {
       config.bind_to_monitor.is_some_and(|monitor_index| {
         monitor.index() == monitor_index as usize
       })
     })
+    .cloned()
     .collect::<Vec<_>>();
 
   for workspace_config in bound_workspace_configs {
@@ -168,7 +169,7 @@ pub fn move_workspace_to_monitor(
     }
   }
 
-  sort_workspaces(target_monitor, config)?;
+  sort_workspaces(target_monitor, state)?;
 
   state.emit_event(WmEvent::WorkspaceUpdated {
     updated_workspace: workspace.to_dto()?,
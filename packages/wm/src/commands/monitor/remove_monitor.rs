@@ -27,7 +27,6 @@ use crate::{
   },
   models::Monitor,
   traits::CommonGetters,
-  user_config::UserConfig,
   wm_state::WmState,
 };
 
@@ -35,7 +34,6 @@ use crate::{
 pub fn remove_monitor(
   monitor: Monitor,
   state: &mut WmState,
-  config: &UserConfig,
 ) -> anyhow::Result<()> {
   info!("Removing monitor: {monitor}");
 
@@ -60,7 +58,7 @@ pub fn remove_monitor(
       state,
     )?;
 
-    sort_workspaces(&target_monitor, config)?;
+    sort_workspaces(&target_monitor, state)?;
 
     state.emit_event(WmEvent::WorkspaceUpdated {
       updated_workspace: workspace.to_dto()?,
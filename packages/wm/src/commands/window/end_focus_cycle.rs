@@ -0,0 +1,35 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{traits::CommonGetters, wm_state::WmState};
+
+/// Ends an in-progress cycle through recent windows, and adds the window
+/// that was cycled to into the focus history.
+pub fn end_focus_cycle(state: &mut WmState) {
+  if !state.focus_history.end_cycle() {
+    return;
+  }
+
+  let focused_window = state
+    .focused_container()
+    .and_then(|focused| focused.as_window_container().ok());
+
+  if let Some(window) = focused_window {
+    state.focus_history.record(window.id());
+  }
+}
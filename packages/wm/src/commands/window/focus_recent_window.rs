@@ -0,0 +1,109 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+use wm_common::WindowState;
+
+use crate::{
+  commands::container::focus_container_and_workspace,
+  models::WindowContainer,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Target window to focus from the focus history.
+#[derive(Clone, Debug)]
+pub enum RecentWindowTarget {
+  /// Previous window in the focus history.
+  Back,
+  /// Next window in the focus history.
+  Forward,
+  /// Next window when cycling through recently focused windows.
+  CycleRecent,
+}
+
+/// Focuses a window from the focus history.
+///
+/// Minimized windows are skipped, as are windows on hidden workspaces
+/// unless `general.focus_history.include_hidden_workspaces` is enabled.
+pub fn focus_recent_window(
+  target: &RecentWindowTarget,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let include_hidden_workspaces =
+    config.value.general.focus_history.include_hidden_workspaces;
+
+  let is_focusable = |window: &WindowContainer| {
+    window.state() != WindowState::Minimized
+      && (include_hidden_workspaces
+        || window
+          .workspace()
+          .is_some_and(|workspace| workspace.is_displayed()))
+  };
+
+  let window_id = match target {
+    RecentWindowTarget::Back | RecentWindowTarget::Forward => {
+      let focusable_ids = state
+        .windows()
+        .into_iter()
+        .filter(is_focusable)
+        .map(|window| window.id())
+        .collect::<HashSet<_>>();
+
+      let is_valid = |id: &Uuid| focusable_ids.contains(id);
+
+      match target {
+        RecentWindowTarget::Back => state.focus_history.back(is_valid),
+        _ => state.focus_history.forward(is_valid),
+      }
+    }
+    RecentWindowTarget::CycleRecent => {
+      if !state.focus_history.is_cycling() {
+        let focused_id = state
+          .focused_container()
+          .and_then(|focused| focused.as_window_container().ok())
+          .map(|window| window.id());
+
+        // Cycle through the other windows first, and end up back at the
+        // focused window.
+        let mut window_ids = state
+          .recent_windows()
+          .into_iter()
+          .filter(|window| Some(window.id()) != focused_id)
+          .filter(is_focusable)
+          .map(|window| window.id())
+          .collect::<Vec<_>>();
+
+        window_ids.extend(focused_id);
+        state.focus_history.start_cycle(window_ids);
+      }
+
+      state.focus_history.cycle_next()
+    }
+  };
+
+  if let Some(window_id) = window_id {
+    focus_container_and_workspace(&window_id, state, config)?;
+  }
+
+  Ok(())
+}
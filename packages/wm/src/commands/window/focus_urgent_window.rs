@@ -0,0 +1,50 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+  commands::container::focus_container_and_workspace,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Focuses the window that has been requesting attention the longest.
+///
+/// The window's workspace is displayed if it's currently hidden. Does
+/// nothing if no window is urgent.
+pub fn focus_urgent_window(
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let urgent_window = state
+    .windows()
+    .into_iter()
+    .filter_map(|window| {
+      window
+        .urgent_since()
+        .map(|urgent_since| (urgent_since, window))
+    })
+    .min_by_key(|(urgent_since, _)| *urgent_since)
+    .map(|(_, window)| window);
+
+  if let Some(window) = urgent_window {
+    focus_container_and_workspace(&window.id(), state, config)?;
+  }
+
+  Ok(())
+}
@@ -1,19 +1,45 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use tracing::info;
 use wm_common::{
-  try_warn, LengthValue, RectDelta, WindowRuleEvent, WindowState, WmEvent,
+  try_warn, FloatingPlacementStrategy, LengthValue, Rect, RectDelta,
+  SizeConstraints, WindowEffectOverrides, WindowRuleEvent, WindowState,
+  WmEvent, WorkspaceLayout,
 };
-use wm_platform::NativeWindow;
+use wm_platform::{NativeWindow, Platform};
 
 use crate::{
   commands::{
-    container::{attach_container, set_focused_descendant},
-    window::run_window_rules,
+    container::{
+      attach_container, set_focused_descendant, wrap_in_split_container,
+    },
+    window::{run_window_rules, swallow_window},
+    workspace::CASCADE_STEP,
   },
   models::{
-    Container, Monitor, NonTilingWindow, TilingWindow, WindowContainer,
+    Container, Monitor, NonTilingWindow, SplitContainer, TilingWindow,
+    WindowContainer, Workspace,
+  },
+  traits::{
+    CommonGetters, PositionGetters, TilingDirectionGetters, WindowGetters,
   },
-  traits::{CommonGetters, PositionGetters, WindowGetters},
   user_config::UserConfig,
   wm_state::WmState,
 };
@@ -42,6 +68,8 @@ pub fn manage_window(
   )?;
 
   if let Some(window) = updated_window {
+    swallow_window(&window, state, config)?;
+
     info!("New window managed: {window}");
 
     state.emit_event(WmEvent::WindowManaged {
@@ -88,9 +116,23 @@ fn create_window(
     .displayed_workspace()
     .context("No nearest workspace.")?;
 
-  let gaps_config = config.value.gaps.clone();
-  let window_state =
-    window_state_to_create(&native_window, &nearest_monitor, config)?;
+  // Get the workspace the window will be added to. This is resolved
+  // upfront since workspace overrides affect the initial window state.
+  let target_workspace = match &target_parent {
+    Some(parent) => parent.workspace(),
+    None => state
+      .focused_container()
+      .and_then(|focused| focused.workspace()),
+  }
+  .context("No target workspace.")?;
+
+  let gaps_config = target_workspace.gaps_config();
+  let window_state = window_state_to_create(
+    &native_window,
+    &nearest_monitor,
+    &target_workspace,
+    config,
+  )?;
 
   // Attach the new window as the first child of the target parent (if
   // provided), otherwise, add as a sibling of the focused container.
@@ -99,36 +141,15 @@ fn create_window(
     None => insertion_target(&window_state, state)?,
   };
 
-  let target_workspace =
-    target_parent.workspace().context("No target workspace.")?;
-
-  let prefers_centered = config
-    .value
-    .window_behavior
-    .state_defaults
-    .floating
-    .centered;
-
-  // Calculate where window should be placed when floating is enabled. Use
-  // the original width/height of the window and optionally position it in
-  // the center of the workspace.
+  // Calculate where window should be placed when floating is enabled.
   let is_same_workspace = nearest_workspace.id() == target_workspace.id();
-  let floating_placement = {
-    let placement = if !is_same_workspace || prefers_centered {
-      native_window
-        .frame_position()?
-        .translate_to_center(&target_workspace.to_rect()?)
-    } else {
-      native_window.frame_position()?
-    };
-
-    // Clamp the window size to 90% of the workspace size.
-    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
-    placement.clamp_size(
-      (target_workspace.to_rect()?.width() as f32 * 0.9) as i32,
-      (target_workspace.to_rect()?.height() as f32 * 0.9) as i32,
-    )
-  };
+  let floating_placement = floating_placement_to_create(
+    &native_window,
+    &target_workspace,
+    is_same_workspace,
+    state,
+    config,
+  )?;
 
   // Window has no border delta unless it's later changed via the
   // `adjust_borders` command.
@@ -139,6 +160,10 @@ fn create_window(
     LengthValue::from_px(0),
   );
 
+  // Size constraints are retrieved in the background once the window is
+  // attached, so the window starts out unconstrained.
+  let size_constraints = SizeConstraints::default();
+
   let window_container: WindowContainer = match window_state {
     WindowState::Tiling => TilingWindow::new(
       None,
@@ -150,6 +175,10 @@ fn create_window(
       gaps_config,
       Vec::new(),
       None,
+      None,
+      None,
+      size_constraints,
+      WindowEffectOverrides::default(),
     )
     .into(),
     _ => NonTilingWindow::new(
@@ -163,6 +192,10 @@ fn create_window(
       false,
       Vec::new(),
       None,
+      None,
+      None,
+      size_constraints,
+      WindowEffectOverrides::default(),
     )
     .into(),
   };
@@ -173,6 +206,11 @@ fn create_window(
     Some(target_index),
   )?;
 
+  state.query_size_constraints(
+    &window_container.native(),
+    window_container.id(),
+  );
+
   // The OS might spawn the window on a different monitor to the target
   // parent, so adjustments might need to be made because of DPI.
   if nearest_monitor
@@ -184,12 +222,183 @@ fn create_window(
   Ok(window_container)
 }
 
+/// Gets where a new window should be placed when floating, based on the
+/// `window_behavior.floating_placement` strategy.
+///
+/// Uses the original width/height of the window, clamped to 90% of the
+/// workspace size.
+fn floating_placement_to_create(
+  native_window: &NativeWindow,
+  target_workspace: &Workspace,
+  is_same_workspace: bool,
+  state: &WmState,
+  config: &UserConfig,
+) -> anyhow::Result<Rect> {
+  let workspace_rect = target_workspace.to_rect()?;
+
+  #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+  let initial_placement =
+    native_window.initial_frame_position()?.clamp_size(
+      (workspace_rect.width() as f32 * 0.9) as i32,
+      (workspace_rect.height() as f32 * 0.9) as i32,
+    );
+
+  let centered_placement =
+    initial_placement.translate_to_center(&workspace_rect);
+
+  // Placements of the existing floating windows on the workspace.
+  let floating_placements = target_workspace
+    .descendants()
+    .filter_map(|container| container.as_window_container().ok())
+    .filter(|window| matches!(window.state(), WindowState::Floating(_)))
+    .map(|window| window.floating_placement())
+    .collect::<Vec<_>>();
+
+  let window_behavior = &config.value.window_behavior;
+
+  let placement = match window_behavior.floating_placement {
+    FloatingPlacementStrategy::Center => {
+      if is_same_workspace
+        && !window_behavior.state_defaults.floating.centered
+      {
+        initial_placement
+      } else {
+        centered_placement
+      }
+    }
+    FloatingPlacementStrategy::Cascade => cascade_placement(
+      &centered_placement,
+      &floating_placements,
+      target_workspace,
+    )?,
+    FloatingPlacementStrategy::UnderCursor => {
+      let mouse_pos = Platform::mouse_position()?;
+
+      initial_placement.translate_to_coordinates(
+        mouse_pos.x - initial_placement.width() / 2,
+        mouse_pos.y - initial_placement.height() / 2,
+      )
+    }
+    FloatingPlacementStrategy::LeastOverlap => least_overlap_placement(
+      &centered_placement,
+      &floating_placements,
+      &workspace_rect,
+    ),
+    FloatingPlacementStrategy::RememberPerApp => {
+      match state
+        .remembered_placements
+        .get(native_window, &workspace_rect)
+      {
+        Some(placement) => placement,
+        None => cascade_placement(
+          &centered_placement,
+          &floating_placements,
+          target_workspace,
+        )?,
+      }
+    }
+  };
+
+  Ok(placement.translate_within(&workspace_rect))
+}
+
+/// Offsets a placement diagonally until its top-left no longer coincides
+/// with that of an existing floating window.
+fn cascade_placement(
+  placement: &Rect,
+  floating_placements: &[Rect],
+  target_workspace: &Workspace,
+) -> anyhow::Result<Rect> {
+  let workspace_rect = target_workspace.to_rect()?;
+  let scale_factor = target_workspace
+    .monitor()
+    .context("No monitor.")?
+    .native()
+    .scale_factor()?;
+
+  #[allow(clippy::cast_possible_truncation)]
+  let step = (CASCADE_STEP * scale_factor) as i32;
+  let mut placement = placement.clone();
+
+  for _ in 0..floating_placements.len() {
+    let is_occupied = floating_placements.iter().any(|other| {
+      (other.x() - placement.x()).abs() < step / 2
+        && (other.y() - placement.y()).abs() < step / 2
+    });
+
+    if !is_occupied {
+      break;
+    }
+
+    placement = placement.translate_to_coordinates(
+      placement.x() + step,
+      placement.y() + step,
+    );
+
+    // Start again from the top-left once the workspace is overflowed.
+    if placement.right > workspace_rect.right
+      || placement.bottom > workspace_rect.bottom
+    {
+      placement = placement
+        .translate_to_coordinates(workspace_rect.x(), workspace_rect.y());
+    }
+  }
+
+  Ok(placement)
+}
+
+/// Gets the placement that overlaps the least with existing floating
+/// windows, out of a grid of candidate positions within the workspace.
+///
+/// Ties are broken by the distance to the center of the workspace.
+fn least_overlap_placement(
+  centered_placement: &Rect,
+  floating_placements: &[Rect],
+  workspace_rect: &Rect,
+) -> Rect {
+  const STEPS: i32 = 8;
+
+  let free_width =
+    (workspace_rect.width() - centered_placement.width()).max(0);
+  let free_height =
+    (workspace_rect.height() - centered_placement.height()).max(0);
+
+  let candidates = (0..=STEPS).flat_map(|column| {
+    (0..=STEPS).map(move |row| {
+      centered_placement.translate_to_coordinates(
+        workspace_rect.x() + free_width * column / STEPS,
+        workspace_rect.y() + free_height * row / STEPS,
+      )
+    })
+  });
+
+  let workspace_center = workspace_rect.center_point();
+
+  std::iter::once(centered_placement.clone())
+    .chain(candidates)
+    .min_by_key(|candidate| {
+      let overlap = floating_placements
+        .iter()
+        .map(|other| candidate.overlap_area(other))
+        .sum::<i64>();
+
+      let center = candidate.center_point();
+      let distance = (center.x - workspace_center.x).abs()
+        + (center.y - workspace_center.y).abs();
+
+      (overlap, distance)
+    })
+    .unwrap_or_else(|| centered_placement.clone())
+}
+
 /// Gets the initial state for a window based on its native state.
 ///
-/// Note that maximized windows are initialized as tiling.
+/// Note that maximized windows are initialized as tiling, unless the
+/// target workspace overrides the initial window state.
 fn window_state_to_create(
   native_window: &NativeWindow,
   nearest_monitor: &Monitor,
+  target_workspace: &Workspace,
   config: &UserConfig,
 ) -> anyhow::Result<WindowState> {
   if native_window.is_minimized()? {
@@ -200,10 +409,7 @@ fn window_state_to_create(
     .displayed_workspace()
     .context("No Workspace.")?;
 
-  let monitor_rect = if config
-    .outer_gaps_for_workspace(&nearest_workspace)
-    .is_significant()
-  {
+  let monitor_rect = if nearest_workspace.outer_gaps().is_significant() {
     nearest_monitor.native().working_rect()?.clone()
   } else {
     nearest_monitor.to_rect()?
@@ -227,7 +433,7 @@ fn window_state_to_create(
     ));
   }
 
-  Ok(WindowState::default_from_config(&config.value))
+  Ok(config.initial_window_state(target_workspace))
 }
 
 /// Gets where to insert a new window in the container tree.
@@ -240,6 +446,10 @@ fn window_state_to_create(
 ///      tiling window found.
 ///   3. If no tiling windows exist, append to the workspace.
 ///
+/// On workspaces with the `dwindle` layout, the tiling window to insert
+/// next to is first wrapped in a split container of the opposite
+/// direction (unless it's the only child of its parent).
+///
 /// Returns tuple of (parent container, insertion index).
 fn insertion_target(
   window_state: &WindowState,
@@ -262,6 +472,27 @@ fn insertion_target(
     };
 
     if let Some(sibling) = sibling {
+      if focused_workspace.config().layout == WorkspaceLayout::Dwindle
+        && sibling.tiling_siblings().count() > 0
+      {
+        let parent = sibling
+          .direction_container()
+          .context("No direction container.")?;
+
+        let split_container = SplitContainer::new(
+          parent.tiling_direction().inverse(),
+          focused_workspace.gaps_config(),
+        );
+
+        wrap_in_split_container(
+          &split_container,
+          &parent.into(),
+          &[sibling.as_tiling_container()?],
+        )?;
+
+        return Ok((split_container.into(), 1));
+      }
+
       return Ok((
         sibling.parent().context("No parent.")?,
         sibling.index() + 1,
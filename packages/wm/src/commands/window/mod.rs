@@ -1,21 +1,53 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+mod end_focus_cycle;
+mod focus_recent_window;
+mod focus_urgent_window;
 mod ignore_window;
 mod manage_window;
 mod move_window_in_direction;
 mod move_window_to_workspace;
 mod resize_window;
 mod run_window_rules;
+mod set_window_effects;
 mod set_window_position;
 mod set_window_size;
+mod set_window_urgency;
+mod swallow_window;
+mod sync_sticky_windows;
 mod unmanage_window;
 mod update_window_state;
 
+pub use end_focus_cycle::*;
+pub use focus_recent_window::*;
+pub use focus_urgent_window::*;
 pub use ignore_window::*;
 pub use manage_window::*;
 pub use move_window_in_direction::*;
 pub use move_window_to_workspace::*;
 pub use resize_window::*;
 pub use run_window_rules::*;
+pub use set_window_effects::*;
 pub use set_window_position::*;
 pub use set_window_size::*;
+pub use set_window_urgency::*;
+pub use swallow_window::*;
+pub use sync_sticky_windows::*;
 pub use unmanage_window::*;
 pub use update_window_state::*;
@@ -1,15 +1,40 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
-use wm_common::{Direction, Rect, TilingDirection, WindowState};
+use wm_common::{
+  Direction, DirectionalNavigation, Rect, TilingDirection, WindowState,
+  WmEvent,
+};
 
 use crate::{
-  commands::container::{
-    flatten_child_split_containers, flatten_split_container,
-    move_container_within_tree, resize_tiling_container,
-    set_focused_descendant, wrap_in_split_container,
+  commands::{
+    container::{
+      flatten_child_split_containers, flatten_split_container,
+      move_container_within_tree, resize_tiling_container,
+      set_focused_descendant, swap_tiling_containers,
+      wrap_in_split_container,
+    },
+    window::queue_window_move_rules,
   },
   models::{
-    DirectionContainer, Monitor, NonTilingWindow, SplitContainer,
-    TilingContainer, TilingWindow, WindowContainer,
+    Container, DirectionContainer, Monitor, NonTilingWindow,
+    SplitContainer, TilingContainer, TilingWindow, WindowContainer,
   },
   traits::{
     CommonGetters, PositionGetters, TilingDirectionGetters, WindowGetters,
@@ -29,6 +54,19 @@ pub fn move_window_in_direction(
 ) -> anyhow::Result<()> {
   match window {
     WindowContainer::TilingWindow(window) => {
+      // With geometric navigation, swap with the nearest tiling window in
+      // the given direction. Otherwise, fall back to moving the window
+      // within the tree (e.g. to an empty workspace).
+      if config.value.general.directional_navigation
+        == DirectionalNavigation::Geometric
+      {
+        if let Some(Container::TilingWindow(target_window)) = state
+          .container_in_direction(&window.clone().into(), direction)?
+        {
+          return swap_tiling_windows(window, target_window, state);
+        }
+      }
+
       move_tiling_window(window, direction, state, config)
     }
     WindowContainer::NonTilingWindow(non_tiling_window) => {
@@ -125,6 +163,75 @@ fn move_tiling_window(
   }
 }
 
+/// Swaps a tiling window with another tiling window, which can be on a
+/// different workspace.
+fn swap_tiling_windows(
+  window_to_move: TilingWindow,
+  target_window: TilingWindow,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let workspace = window_to_move.workspace().context("No workspace.")?;
+  let target_workspace =
+    target_window.workspace().context("No workspace.")?;
+
+  let has_focus = window_to_move.has_focus(None);
+
+  swap_tiling_containers(
+    &window_to_move.clone().into(),
+    &target_window.clone().into(),
+  )?;
+
+  if workspace.id() != target_workspace.id() {
+    for (window, origin_workspace, new_workspace) in [
+      (&window_to_move, &workspace, &target_workspace),
+      (&target_window, &target_workspace, &workspace),
+    ] {
+      // Since the window is crossing workspaces, adjustments might need
+      // to be made because of DPI.
+      let monitor = origin_workspace.monitor().context("No monitor.")?;
+      if monitor.has_dpi_difference(&new_workspace.clone().into())? {
+        window.set_has_pending_dpi_adjustment(true);
+      }
+
+      window.set_floating_placement(
+        window
+          .floating_placement()
+          .translate_to_center(&new_workspace.to_rect()?),
+      );
+
+      queue_window_move_rules(
+        &window.clone().into(),
+        origin_workspace,
+        &monitor,
+        state,
+      );
+    }
+
+    state
+      .pending_sync
+      .queue_workspace_to_reorder(workspace)
+      .queue_workspace_to_reorder(target_workspace);
+  }
+
+  // Focus order is kept by position when swapping across parents, so
+  // focus needs to be reassigned to the moved window.
+  if has_focus {
+    set_focused_descendant(&window_to_move.clone().into(), None);
+
+    state.emit_event(WmEvent::FocusedContainerMoved {
+      focused_container: window_to_move.to_dto()?,
+    });
+  }
+
+  state
+    .pending_sync
+    .queue_container_to_redraw(window_to_move)
+    .queue_container_to_redraw(target_window)
+    .queue_cursor_jump();
+
+  Ok(())
+}
+
 /// Gets the next sibling `TilingWindow` or `SplitContainer` in the given
 /// direction.
 fn tiling_sibling_in_direction(
@@ -256,6 +363,8 @@ fn move_to_workspace_in_direction(
       state,
     )?;
 
+    queue_window_move_rules(window_to_move, &workspace, &monitor, state);
+
     if let Some(focus_target) = focus_target {
       set_focused_descendant(
         &focus_target,
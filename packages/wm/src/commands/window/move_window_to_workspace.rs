@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use tracing::info;
 use wm_common::WindowState;
@@ -5,6 +23,7 @@ use wm_common::WindowState;
 use crate::{
   commands::{
     container::{move_container_within_tree, set_focused_descendant},
+    window::queue_window_move_rules,
     workspace::activate_workspace,
   },
   models::{WindowContainer, WorkspaceTarget},
@@ -13,6 +32,7 @@ use crate::{
   wm_state::WmState,
 };
 
+#[allow(clippy::too_many_lines)]
 pub fn move_window_to_workspace(
   window: WindowContainer,
   target: WorkspaceTarget,
@@ -109,6 +129,13 @@ pub fn move_window_to_workspace(
       }
     }
 
+    queue_window_move_rules(
+      &window,
+      &current_workspace,
+      &current_monitor,
+      state,
+    );
+
     // When moving a focused window within the tree to another workspace,
     // the target workspace will get displayed. If moving the window e.g.
     // from monitor 1 -> 2, and the target workspace is hidden on that
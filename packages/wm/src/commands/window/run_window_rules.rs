@@ -1,8 +1,29 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+use anyhow::Context;
 use tracing::info;
 use wm_common::WindowRuleEvent;
 
 use crate::{
-  models::WindowContainer,
+  models::{Monitor, WindowContainer, Workspace},
   traits::{CommonGetters, WindowGetters},
   user_config::UserConfig,
   wm::WindowManager,
@@ -20,6 +41,26 @@ pub fn run_window_rules(
   let pending_window_rules =
     config.pending_window_rules(&window, event_type)?;
 
+  // Windows that are no longer managed (e.g. on `Unmanage`) can't be the
+  // subject of commands, so the focused container is used instead.
+  if window.is_detached() {
+    for rule in pending_window_rules {
+      info!("Running window rule with commands: {:?}.", rule.commands);
+
+      let subject_container =
+        state.focused_container().context("No focused container.")?;
+
+      WindowManager::run_commands(
+        &rule.commands,
+        subject_container,
+        state,
+        config,
+      )?;
+    }
+
+    return Ok(None);
+  }
+
   let mut subject_window = window;
 
   for rule in pending_window_rules {
@@ -58,3 +99,65 @@ pub fn run_window_rules(
 
   Ok(Some(subject_window))
 }
+
+/// Runs window rules that were queued while processing an event or
+/// command (e.g. `StateChange` rules when a window's state is updated).
+///
+/// Running rules can queue further events, so each event runs at most
+/// once per window per call.
+pub fn run_queued_window_rules(
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let mut ran_events = HashSet::new();
+
+  while !state.pending_window_rule_events.is_empty() {
+    let pending_events =
+      std::mem::take(&mut state.pending_window_rule_events);
+
+    for (window_id, event) in pending_events {
+      if !ran_events.insert((window_id, event.clone())) {
+        continue;
+      }
+
+      // Skip windows that have since been unmanaged.
+      let Some(window) = state
+        .container_by_id(window_id)
+        .and_then(|container| container.as_window_container().ok())
+      else {
+        continue;
+      };
+
+      run_window_rules(window, &event, state, config)?;
+    }
+  }
+
+  Ok(())
+}
+
+/// Queues `WorkspaceChange` and `MonitorChange` window rules for a window
+/// that was moved from the given workspace and monitor.
+pub fn queue_window_move_rules(
+  window: &WindowContainer,
+  origin_workspace: &Workspace,
+  origin_monitor: &Monitor,
+  state: &mut WmState,
+) {
+  if window
+    .workspace()
+    .is_some_and(|workspace| workspace.id() != origin_workspace.id())
+  {
+    state
+      .pending_window_rule_events
+      .push((window.id(), WindowRuleEvent::WorkspaceChange));
+  }
+
+  if window
+    .monitor()
+    .is_some_and(|monitor| monitor.id() != origin_monitor.id())
+  {
+    state
+      .pending_window_rule_events
+      .push((window.id(), WindowRuleEvent::MonitorChange));
+  }
+}
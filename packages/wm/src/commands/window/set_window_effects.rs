@@ -0,0 +1,102 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use wm_common::{CornerStyle, Delta, OpacityValue, WindowEffectOverrides};
+
+use crate::{
+  models::WindowContainer, traits::WindowGetters, wm_state::WmState,
+};
+
+/// Overrides window effects for a window. Overridden effects take
+/// precedence over the focused and unfocused window effects from the
+/// user config, and so persist across focus changes.
+///
+/// If `reset` is set, all existing overrides of the window are removed
+/// before the given overrides are added.
+pub fn set_window_effects(
+  window: &WindowContainer,
+  overrides: &WindowEffectOverrides,
+  reset: bool,
+  state: &mut WmState,
+) {
+  let current_overrides = if reset {
+    revert_overridden_effects(window);
+    WindowEffectOverrides::default()
+  } else {
+    window.effect_overrides()
+  };
+
+  window.set_effect_overrides(current_overrides.with_overrides(overrides));
+  state
+    .pending_sync
+    .queue_window_effect_update(window.clone());
+}
+
+/// Gets the opacity override to set for a window. The delta is relative
+/// to the given opacity if set, and otherwise to the window's current
+/// opacity.
+pub fn transparency_override(
+  window: &WindowContainer,
+  opacity: Option<&OpacityValue>,
+  opacity_delta: Option<&Delta<OpacityValue>>,
+) -> OpacityValue {
+  let opacity = opacity
+    .cloned()
+    .or_else(|| window.effect_overrides().transparency)
+    .or_else(|| window.native().transparency().ok())
+    .unwrap_or_else(|| OpacityValue::from_alpha(u8::MAX));
+
+  let Some(opacity_delta) = opacity_delta else {
+    return opacity;
+  };
+
+  let alpha = opacity.to_alpha();
+  let delta_alpha = opacity_delta.inner.to_alpha();
+
+  OpacityValue::from_alpha(if opacity_delta.is_negative {
+    alpha.saturating_sub(delta_alpha)
+  } else {
+    alpha.saturating_add(delta_alpha)
+  })
+}
+
+/// Reverts the overridden effects of a window to their native defaults.
+///
+/// Effects from the user config are only applied when they're enabled,
+/// so this ensures that effects don't linger once their override is
+/// removed.
+fn revert_overridden_effects(window: &WindowContainer) {
+  let overrides = window.effect_overrides();
+  let native = window.native();
+
+  if overrides.border_color.is_some() {
+    _ = native.set_border_color(None);
+  }
+
+  if overrides.corner_style.is_some() {
+    _ = native.set_corner_style(&CornerStyle::Default);
+  }
+
+  if overrides.transparency.is_some() {
+    _ = native.set_transparency(&OpacityValue::from_alpha(u8::MAX));
+  }
+
+  if overrides.title_bar.is_some() {
+    _ = native.set_title_bar_visibility(true);
+  }
+}
@@ -1,5 +1,23 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
-use wm_common::{Rect, WindowState};
+use wm_common::{GridPlacement, Rect, WindowState};
 
 use crate::{
   models::WindowContainer,
@@ -10,6 +28,9 @@ use crate::{
 pub enum WindowPositionTarget {
   Centered,
   Coordinates(Option<i32>, Option<i32>),
+  /// Positions and sizes the window to cover an area of a grid over its
+  /// workspace.
+  Grid(GridPlacement),
 }
 
 pub fn set_window_position(
@@ -32,6 +53,13 @@ pub fn set_window_position(
           placement.height(),
         )
       }
+      WindowPositionTarget::Grid(grid_placement) => {
+        let workspace = window.workspace().context("No workspace.")?;
+
+        workspace
+          .to_rect()?
+          .grid_cell(grid_placement, workspace.inner_gap()?)
+      }
     };
 
     window.set_floating_placement(new_placement);
@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use wm_common::{LengthValue, Rect, WindowState};
 
@@ -81,8 +99,32 @@ fn set_tiling_window_length(
         - vertical_gap * window.tiling_siblings().count() as i32
     };
 
+    // Keep the target length within the size constraints of the
+    // container, and leave room for the minimum lengths of its siblings.
+    let (min_length, max_length) =
+      container_to_resize.length_constraints(is_width_resize)?;
+
+    let siblings_min_length = container_to_resize
+      .tiling_siblings()
+      .map(|sibling| sibling.length_constraints(is_width_resize))
+      .try_collect::<Vec<_>>()?
+      .into_iter()
+      .filter_map(|(min_length, _)| min_length)
+      .sum::<i32>();
+
+    let lower_bound = min_length.unwrap_or(0);
+    let upper_bound = max_length
+      .unwrap_or(i32::MAX)
+      .min(parent_length - siblings_min_length)
+      .max(lower_bound);
+
+    let target_px = target_length
+      .to_px(parent_length, None)
+      .clamp(lower_bound, upper_bound);
+
     // Convert the target length to a tiling size.
-    let tiling_size = target_length.to_percentage(parent_length);
+    let tiling_size =
+      LengthValue::from_px(target_px).to_percentage(parent_length);
 
     // Skip the resize if the window is already at the target size.
     if container_to_resize.tiling_size() - tiling_size != 0. {
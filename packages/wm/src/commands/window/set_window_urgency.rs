@@ -0,0 +1,56 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Instant;
+
+use wm_common::WmEvent;
+
+use crate::{
+  models::WindowContainer,
+  traits::{CommonGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Marks a window as requesting attention, or clears its urgency.
+///
+/// Emits a `WindowUrgencyChanged` event, as well as a `WorkspaceUpdated`
+/// event for the window's workspace.
+pub fn set_window_urgency(
+  window: &WindowContainer,
+  is_urgent: bool,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  if window.is_urgent() == is_urgent {
+    return Ok(());
+  }
+
+  window.set_urgent_since(is_urgent.then(Instant::now));
+
+  state.emit_event(WmEvent::WindowUrgencyChanged {
+    updated_window: window.to_dto()?,
+    is_urgent,
+  });
+
+  if let Some(workspace) = window.workspace() {
+    state.emit_event(WmEvent::WorkspaceUpdated {
+      updated_workspace: workspace.to_dto()?,
+    });
+  }
+
+  Ok(())
+}
@@ -0,0 +1,176 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+use tracing::info;
+use wm_common::{try_warn, DisplayState, WindowState};
+use wm_platform::Platform;
+
+use crate::{
+  commands::container::{
+    attach_container, detach_container, flatten_child_split_containers,
+    replace_container, set_focused_descendant,
+  },
+  models::WindowContainer,
+  traits::{CommonGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Moves a newly managed window into the place of the swallower window
+/// (e.g. a terminal) that launched it, and hides the swallower.
+///
+/// The swallower is restored via `restore_swallowed_window` once the
+/// launched window is unmanaged.
+pub fn swallow_window(
+  window: &WindowContainer,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let swallowing_config = &config.value.window_swallowing;
+
+  if !swallowing_config.enabled
+    || window.state() != WindowState::Tiling
+    || UserConfig::is_window_match(window, &swallowing_config.exclusions)?
+  {
+    return Ok(());
+  }
+
+  let Some(swallower) = try_warn!(swallower_for_window(window, config))
+  else {
+    return Ok(());
+  };
+
+  info!("Window {window} swallowed window: {swallower}");
+
+  let workspace = window.workspace().context("No workspace.")?;
+  let ancestors = window.ancestors().take(3).collect::<Vec<_>>();
+
+  // Detach the window from where it was initially added, and flatten any
+  // split containers that were created to hold it.
+  detach_container(window.clone().into())?;
+
+  for ancestor in ancestors.iter().rev() {
+    flatten_child_split_containers(ancestor)?;
+  }
+
+  let target_parent = swallower.parent().context("No parent.")?;
+
+  replace_container(
+    &window.clone().into(),
+    &target_parent,
+    swallower.index(),
+  )?;
+
+  set_focused_descendant(&window.clone().into(), None);
+
+  // The swallower is no longer in the tree, so it needs to be hidden
+  // directly instead of on the next platform sync.
+  swallower.set_display_state(DisplayState::Hidden);
+  swallower.native().set_visible(
+    false,
+    &config.hide_method_for_workspace(Some(&workspace)),
+  )?;
+
+  state.focus_history.remove(swallower.id());
+  state.swallowed_windows.insert(window.id(), swallower);
+
+  state.pending_sync.queue_container_to_redraw(target_parent);
+
+  Ok(())
+}
+
+/// Restores the window that was swallowed by the given window, either in
+/// its place in the tree or, if the window is no longer tiling, on its
+/// workspace.
+///
+/// Returns the restored window, or `None` if the given window hadn't
+/// swallowed a window or the swallowed window has since been destroyed.
+pub fn restore_swallowed_window(
+  window: &WindowContainer,
+  state: &mut WmState,
+) -> anyhow::Result<Option<WindowContainer>> {
+  let Some(swallowed) = state
+    .swallowed_windows
+    .remove(&window.id())
+    .filter(|swallowed| swallowed.native().is_valid())
+  else {
+    return Ok(None);
+  };
+
+  info!("Restoring swallowed window: {swallowed}");
+
+  if window.state() == WindowState::Tiling {
+    let target_parent = window.parent().context("No parent.")?;
+
+    replace_container(
+      &swallowed.clone().into(),
+      &target_parent,
+      window.index(),
+    )?;
+  } else {
+    let workspace = window.workspace().context("No workspace.")?;
+
+    detach_container(window.clone().into())?;
+    attach_container(&swallowed.clone().into(), &workspace.into(), None)?;
+  }
+
+  state
+    .pending_sync
+    .queue_container_to_redraw(swallowed.parent().context("No parent.")?);
+
+  Ok(Some(swallowed))
+}
+
+/// Gets the swallower window that launched the given window.
+///
+/// Windows of the nearest ancestor process are preferred, and the most
+/// recently focused window is used if the process has several windows
+/// (e.g. a terminal with multiple windows).
+fn swallower_for_window(
+  window: &WindowContainer,
+  config: &UserConfig,
+) -> anyhow::Result<Option<WindowContainer>> {
+  let workspace = window.workspace().context("No workspace.")?;
+  let ancestor_ids =
+    Platform::process_ancestors(window.native().process_id())?;
+
+  let candidates = workspace
+    .descendant_focus_order()
+    .filter_map(|descendant| descendant.as_window_container().ok())
+    .filter(|candidate| {
+      candidate.state() == WindowState::Tiling
+        && candidate.id() != window.id()
+    })
+    .collect::<Vec<_>>();
+
+  for ancestor_id in ancestor_ids {
+    for candidate in &candidates {
+      if candidate.native().process_id() == ancestor_id
+        && UserConfig::is_window_match(
+          candidate,
+          &config.value.window_swallowing.swallowers,
+        )?
+      {
+        return Ok(Some(candidate.clone()));
+      }
+    }
+  }
+
+  Ok(None)
+}
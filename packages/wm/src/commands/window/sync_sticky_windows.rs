@@ -0,0 +1,127 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+use tracing::info;
+use wm_common::StickyScope;
+
+use crate::{
+  commands::{
+    container::move_container_within_tree, workspace::deactivate_workspace,
+  },
+  models::WindowContainer,
+  traits::{CommonGetters, PositionGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Moves sticky windows to the workspace they should currently be on.
+///
+/// Windows with a `Monitor` scope follow the displayed workspace of their
+/// monitor, and windows with an `AllMonitors` scope follow the focused
+/// workspace.
+pub fn sync_sticky_windows(state: &mut WmState) -> anyhow::Result<()> {
+  let focused_workspace = state
+    .focused_container()
+    .and_then(|focused| focused.workspace());
+
+  let sticky_windows = state
+    .windows()
+    .into_iter()
+    .filter(|window| window.sticky().is_some());
+
+  for window in sticky_windows {
+    let current_workspace = window.workspace().context("No workspace.")?;
+
+    let target_workspace = match window.sticky() {
+      Some(StickyScope::AllMonitors) => focused_workspace.clone(),
+      _ => current_workspace
+        .monitor()
+        .and_then(|monitor| monitor.displayed_workspace()),
+    };
+
+    let Some(target_workspace) = target_workspace
+      .filter(|workspace| workspace.id() != current_workspace.id())
+    else {
+      continue;
+    };
+
+    info!(
+      "Moving sticky window to workspace: '{}'.",
+      target_workspace.config().name
+    );
+
+    let current_monitor =
+      current_workspace.monitor().context("No monitor.")?;
+    let target_monitor =
+      target_workspace.monitor().context("No monitor.")?;
+
+    // Adjust for DPI and floating placement if the window has to cross
+    // monitors.
+    if target_monitor.id() != current_monitor.id() {
+      if current_monitor
+        .has_dpi_difference(&target_monitor.clone().into())?
+      {
+        window.set_has_pending_dpi_adjustment(true);
+      }
+
+      window.set_floating_placement(
+        window
+          .floating_placement()
+          .translate_to_center(&target_workspace.to_rect()?),
+      );
+    }
+
+    if let WindowContainer::NonTilingWindow(window) = &window {
+      window.set_insertion_target(None);
+    }
+
+    move_container_within_tree(
+      &window.clone().into(),
+      &target_workspace.clone().into(),
+      target_workspace.child_count(),
+      state,
+    )?;
+
+    match &window {
+      WindowContainer::NonTilingWindow(_) => {
+        state.pending_sync.queue_container_to_redraw(window.clone());
+      }
+      WindowContainer::TilingWindow(_) => {
+        state
+          .pending_sync
+          .queue_containers_to_redraw(current_workspace.tiling_children())
+          .queue_containers_to_redraw(target_workspace.tiling_children());
+      }
+    }
+
+    state
+      .pending_sync
+      .queue_workspace_to_reorder(target_workspace);
+
+    // Clean up the previous workspace if the sticky window was the only
+    // thing keeping it alive.
+    if !current_workspace.config().keep_alive
+      && !current_workspace.has_children()
+      && !current_workspace.is_displayed()
+    {
+      deactivate_workspace(current_workspace, state)?;
+    }
+  }
+
+  Ok(())
+}
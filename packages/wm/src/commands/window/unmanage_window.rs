@@ -1,10 +1,31 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use wm_common::{WindowState, WmEvent};
 
 use crate::{
-  commands::container::{
-    detach_container, flatten_child_split_containers,
-    set_focused_descendant,
+  commands::{
+    container::{
+      detach_container, flatten_child_split_containers,
+      set_focused_descendant,
+    },
+    window::restore_swallowed_window,
   },
   models::WindowContainer,
   traits::{CommonGetters, WindowGetters},
@@ -22,7 +43,15 @@ pub fn unmanage_window(
   // Get container to switch focus to after the window has been removed.
   let focus_target = state.focus_target_after_removal(&window.clone());
 
-  detach_container(window.clone().into())?;
+  // Restore the window that this window swallowed (if any), which then
+  // takes its place and focus. Otherwise, detach the window as normal.
+  let focus_target =
+    if let Some(swallowed) = restore_swallowed_window(&window, state)? {
+      Some(swallowed.into())
+    } else {
+      detach_container(window.clone().into())?;
+      focus_target
+    };
 
   // After detaching the container, flatten any redundant split containers.
   // For example, in the layout V[1 H[2]] where container 1 is detached to
@@ -31,6 +60,8 @@ pub fn unmanage_window(
     flatten_child_split_containers(ancestor)?;
   }
 
+  state.focus_history.remove(window.id());
+
   state.emit_event(WmEvent::WindowUnmanaged {
     unmanaged_id: window.id(),
     unmanaged_handle: window.native().handle,
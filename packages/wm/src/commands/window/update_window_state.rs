@@ -20,7 +20,7 @@ use std::time::Instant;
 
 use anyhow::Context;
 use tracing::{info, warn};
-use wm_common::WindowState;
+use wm_common::{WindowRuleEvent, WindowState};
 
 use crate::{
   commands::container::{
@@ -34,7 +34,8 @@ use crate::{
 
 /// Updates the state of a window.
 ///
-/// Adds the window for redraw if there is a state change.
+/// Adds the window for redraw and queues its `StateChange` window rules if
+/// there is a state change.
 ///
 /// Returns the window after the state change.
 pub fn update_window_state(
@@ -61,10 +62,22 @@ pub fn update_window_state(
 
   info!("Updating window state: {:?}.", target_state);
 
-  match target_state {
+  let prev_state = window.state();
+
+  let window = match target_state {
     WindowState::Tiling => set_tiling(&window, state, config),
     _ => set_non_tiling(window, target_state, state),
+  }?;
+
+  // Minimizing a window that isn't natively minimized leaves its state
+  // as is until the window gets minimized.
+  if window.state() != prev_state {
+    state
+      .pending_window_rule_events
+      .push((window.id(), WindowRuleEvent::StateChange));
   }
+
+  Ok(window)
 }
 
 /// Updates the state of a window to be `WindowState::Tiling`.
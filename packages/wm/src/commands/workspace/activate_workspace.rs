@@ -1,8 +1,26 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use tracing::info;
 use wm_common::{TilingDirection, WmEvent, WorkspaceConfig};
 
-use super::sort_workspaces;
+use super::{sort_workspaces, PendingWorkspaceCommands, WorkspaceHook};
 use crate::{
   commands::container::attach_container,
   models::{Monitor, Workspace},
@@ -24,12 +42,8 @@ pub fn activate_workspace(
   state: &mut WmState,
   config: &UserConfig,
 ) -> anyhow::Result<()> {
-  let workspace_config = workspace_config(
-    workspace_name,
-    target_monitor.clone(),
-    state,
-    config,
-  )?;
+  let workspace_config =
+    workspace_config(workspace_name, target_monitor.clone(), state)?;
 
   let target_monitor = target_monitor
     .or_else(|| {
@@ -51,15 +65,20 @@ pub fn activate_workspace(
 
   let monitor_rect = target_monitor.to_rect()?;
 
-  let tiling_direction = if monitor_rect.height() > monitor_rect.width() {
-    TilingDirection::Vertical
-  } else {
-    TilingDirection::Horizontal
-  };
+  let tiling_direction = workspace_config
+    .tiling_direction
+    .clone()
+    .unwrap_or_else(|| {
+      if monitor_rect.height() > monitor_rect.width() {
+        TilingDirection::Vertical
+      } else {
+        TilingDirection::Horizontal
+      }
+    });
 
   let workspace = Workspace::new(
     workspace_config.clone(),
-    config.value.gaps.clone(),
+    config.gaps_for_workspace(&workspace_config),
     tiling_direction,
   );
 
@@ -70,7 +89,7 @@ pub fn activate_workspace(
     None,
   )?;
 
-  sort_workspaces(&target_monitor, config)?;
+  sort_workspaces(&target_monitor, state)?;
 
   info!("Activating workspace: {workspace}");
 
@@ -78,6 +97,17 @@ pub fn activate_workspace(
     activated_workspace: workspace.to_dto()?,
   });
 
+  if !workspace_config.on_activate.is_empty() {
+    state
+      .pending_workspace_commands
+      .push(PendingWorkspaceCommands {
+        workspace_id: workspace.id(),
+        workspace_name: workspace_config.name.clone(),
+        hook: WorkspaceHook::Activate,
+        commands: workspace_config.on_activate,
+      });
+  }
+
   Ok(())
 }
 
@@ -85,12 +115,11 @@ pub fn activate_workspace(
 fn workspace_config(
   workspace_name: Option<&str>,
   target_monitor: Option<Monitor>,
-  state: &mut WmState,
-  config: &UserConfig,
+  state: &WmState,
 ) -> anyhow::Result<WorkspaceConfig> {
   let found_config = match workspace_name {
-    Some(workspace_name) => config
-      .inactive_workspace_configs(&state.workspaces())
+    Some(workspace_name) => state
+      .inactive_workspace_configs()
       .into_iter()
       .find(|config| config.name == workspace_name)
       .with_context(|| {
@@ -100,14 +129,9 @@ fn workspace_config(
       }),
     None => target_monitor
       .and_then(|target_monitor| {
-        config.workspace_config_for_monitor(
-          &target_monitor,
-          &state.workspaces(),
-        )
-      })
-      .or_else(|| {
-        config.next_inactive_workspace_config(&state.workspaces())
+        state.workspace_config_for_monitor(&target_monitor)
       })
+      .or_else(|| state.next_inactive_workspace_config())
       .context("No workspace config available to activate workspace."),
   };
 
@@ -0,0 +1,156 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+use wm_common::{GridCell, GridPlacement, GridSize, Rect, WindowState};
+
+use crate::{
+  models::{WindowContainer, Workspace},
+  traits::{CommonGetters, PositionGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Offset in pixels between windows when cascading.
+pub const CASCADE_STEP: f32 = 32.;
+
+pub enum FloatingArrangement {
+  /// Stacks windows diagonally from the top-left of the workspace, with
+  /// the focused window placed last.
+  Cascade,
+  /// Sizes and positions windows to fill the workspace in a grid.
+  Tile,
+}
+
+/// Arranges the floating windows of a workspace.
+pub fn arrange_floating_windows(
+  workspace: &Workspace,
+  arrangement: &FloatingArrangement,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  let workspace_rect = workspace.to_rect()?;
+
+  let is_floating = |window: &WindowContainer| {
+    matches!(window.state(), WindowState::Floating(_))
+  };
+
+  match arrangement {
+    FloatingArrangement::Cascade => {
+      let windows = workspace
+        .descendant_focus_order()
+        .filter_map(|container| container.as_window_container().ok())
+        .filter(is_floating)
+        .collect::<Vec<_>>();
+
+      let scale_factor = workspace
+        .monitor()
+        .context("No monitor.")?
+        .native()
+        .scale_factor()?;
+
+      #[allow(clippy::cast_possible_truncation)]
+      let step = (CASCADE_STEP * scale_factor) as i32;
+      let (mut x, mut y) = (workspace_rect.x(), workspace_rect.y());
+
+      // Least recently focused windows are placed first.
+      for window in windows.into_iter().rev() {
+        let placement = window.floating_placement().clamp_size(
+          workspace_rect.width() - step,
+          workspace_rect.height() - step,
+        );
+
+        // Start again from the top-left once the workspace is overflowed.
+        if x + placement.width() > workspace_rect.right
+          || y + placement.height() > workspace_rect.bottom
+        {
+          (x, y) = (workspace_rect.x(), workspace_rect.y());
+        }
+
+        set_floating_placement(
+          &window,
+          placement.translate_to_coordinates(x, y),
+          state,
+        );
+
+        x += step;
+        y += step;
+      }
+    }
+    FloatingArrangement::Tile => {
+      let windows = workspace
+        .descendants()
+        .filter_map(|container| container.as_window_container().ok())
+        .filter(is_floating)
+        .collect::<Vec<_>>();
+
+      if windows.is_empty() {
+        return Ok(());
+      }
+
+      let window_count = u32::try_from(windows.len())?;
+
+      // Use the smallest square grid that fits all windows, and drop any
+      // unused rows.
+      let columns = (1..=window_count)
+        .find(|columns| columns * columns >= window_count)
+        .context("No grid size.")?;
+      let rows = window_count.div_ceil(columns);
+      let inner_gap = workspace.inner_gap()?;
+
+      for (index, window) in (0..).zip(windows) {
+        let column = index % columns;
+
+        // The last window spans the remaining columns of its row.
+        let column_span = if index + 1 == window_count {
+          columns - column
+        } else {
+          1
+        };
+
+        let placement = GridPlacement {
+          size: GridSize { columns, rows },
+          cell: GridCell {
+            column,
+            row: index / columns,
+          },
+          span: GridCell {
+            column: column_span,
+            row: 1,
+          },
+        };
+
+        set_floating_placement(
+          &window,
+          workspace_rect.grid_cell(&placement, inner_gap),
+          state,
+        );
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn set_floating_placement(
+  window: &WindowContainer,
+  placement: Rect,
+  state: &mut WmState,
+) {
+  window.set_floating_placement(placement);
+  window.set_has_custom_floating_placement(true);
+  state.pending_sync.queue_container_to_redraw(window.clone());
+}
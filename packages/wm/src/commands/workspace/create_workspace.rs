@@ -0,0 +1,71 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{bail, Context};
+use tracing::info;
+
+use super::focus_workspace;
+use crate::{
+  models::WorkspaceTarget,
+  runtime_workspaces::{
+    persist_workspace_configs, RuntimeWorkspaceConfig,
+  },
+  traits::CommonGetters,
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// Creates a workspace that isn't in the user config and focuses it.
+///
+/// The workspace is ordered directly after the focused workspace.
+pub fn create_workspace(
+  workspace_name: &str,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  if workspace_name.trim().is_empty() {
+    bail!("Workspace name cannot be empty.");
+  }
+
+  if state.workspace_config(workspace_name).is_some() {
+    bail!("Workspace with name '{workspace_name}' already exists.");
+  }
+
+  let focused_workspace = state
+    .focused_container()
+    .and_then(|focused| focused.workspace())
+    .context("No focused workspace.")?;
+
+  let target_index = state
+    .workspace_config_index(&focused_workspace.config().name)
+    .map_or(state.workspace_configs.len(), |index| index + 1);
+
+  info!("Creating workspace: {workspace_name}");
+
+  state
+    .workspace_configs
+    .insert(target_index, RuntimeWorkspaceConfig::new(workspace_name));
+
+  persist_workspace_configs(state, config)?;
+
+  focus_workspace(
+    WorkspaceTarget::Name(workspace_name.to_string()),
+    state,
+    config,
+  )
+}
@@ -1,6 +1,25 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use tracing::info;
 use wm_common::WmEvent;
 
+use super::{PendingWorkspaceCommands, WorkspaceHook};
 use crate::{
   commands::container::detach_container, models::Workspace,
   traits::CommonGetters, wm_state::WmState,
@@ -11,16 +30,31 @@ use crate::{
 #[allow(clippy::needless_pass_by_value)]
 pub fn deactivate_workspace(
   workspace: Workspace,
-  state: &WmState,
+  state: &mut WmState,
 ) -> anyhow::Result<()> {
   info!("Deactivating workspace: {workspace}");
 
   detach_container(workspace.clone().into())?;
 
+  let config = workspace.config();
+
   state.emit_event(WmEvent::WorkspaceDeactivated {
     deactivated_id: workspace.id(),
-    deactivated_name: workspace.config().name,
+    deactivated_name: config.name.clone(),
   });
 
+  state.occupied_workspace_ids.remove(&workspace.id());
+
+  if !config.on_deactivate.is_empty() {
+    state
+      .pending_workspace_commands
+      .push(PendingWorkspaceCommands {
+        workspace_id: workspace.id(),
+        workspace_name: config.name,
+        hook: WorkspaceHook::Deactivate,
+        commands: config.on_deactivate,
+      });
+  }
+
   Ok(())
 }
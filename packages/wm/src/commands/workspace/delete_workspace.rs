@@ -0,0 +1,93 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{bail, Context};
+use tracing::info;
+
+use super::{activate_workspace, deactivate_workspace};
+use crate::{
+  commands::container::set_focused_descendant,
+  runtime_workspaces::persist_workspace_configs, traits::CommonGetters,
+  user_config::UserConfig, wm_state::WmState,
+};
+
+/// Deletes an empty workspace and removes its config from state.
+///
+/// If the workspace is the only one on its monitor, another workspace is
+/// activated in its place. Workspaces from the user config are restored
+/// on the next config reload.
+pub fn delete_workspace(
+  workspace_name: &str,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let workspace_config_index = state
+    .workspace_config_index(workspace_name)
+    .with_context(|| {
+      format!("Workspace with name '{workspace_name}' doesn't exist.")
+    })?;
+
+  if let Some(workspace) = state.workspace_by_name(workspace_name) {
+    if workspace.has_children() {
+      bail!(
+        "Cannot delete workspace '{workspace_name}' since it isn't empty."
+      );
+    }
+
+    let monitor = workspace.monitor().context("No monitor.")?;
+
+    let is_focused = state
+      .focused_container()
+      .and_then(|focused| focused.workspace())
+      .is_some_and(|focused| focused.id() == workspace.id());
+
+    // Monitors need to have at least one workspace.
+    if monitor.child_count() == 1 {
+      activate_workspace(None, Some(monitor.clone()), state, config)?;
+    }
+
+    deactivate_workspace(workspace, state)?;
+
+    let displayed_workspace = monitor
+      .displayed_workspace()
+      .context("No displayed workspace.")?;
+
+    if is_focused {
+      let container_to_focus = displayed_workspace
+        .descendant_focus_order()
+        .next()
+        .unwrap_or_else(|| displayed_workspace.clone().into());
+
+      set_focused_descendant(&container_to_focus, None);
+      state.pending_sync.queue_focus_change();
+    }
+
+    state
+      .pending_sync
+      .queue_container_to_redraw(displayed_workspace);
+  }
+
+  info!("Deleting workspace: {workspace_name}");
+  state.workspace_configs.remove(workspace_config_index);
+
+  if state.recent_workspace_name.as_deref() == Some(workspace_name) {
+    state.recent_workspace_name = None;
+  }
+
+  persist_workspace_configs(state, config)
+}
@@ -1,11 +1,45 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 mod activate_workspace;
+mod arrange_floating_windows;
+mod create_workspace;
 mod deactivate_workspace;
+mod delete_workspace;
 mod focus_workspace;
 mod move_workspace_in_direction;
+mod rename_workspace;
+mod reorder_workspace;
+mod run_workspace_commands;
+mod set_workspace_monocle;
 mod sort_workspaces;
+mod sync_monocle_workspaces;
 
 pub use activate_workspace::*;
+pub use arrange_floating_windows::*;
+pub use create_workspace::*;
 pub use deactivate_workspace::*;
+pub use delete_workspace::*;
 pub use focus_workspace::*;
 pub use move_workspace_in_direction::*;
+pub use rename_workspace::*;
+pub use reorder_workspace::*;
+pub use run_workspace_commands::*;
+pub use set_workspace_monocle::*;
 pub use sort_workspaces::*;
+pub use sync_monocle_workspaces::*;
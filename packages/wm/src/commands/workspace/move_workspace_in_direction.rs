@@ -1,5 +1,23 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
-use wm_common::{Direction, WmEvent};
+use wm_common::{Direction, WindowRuleEvent, WmEvent};
 
 use super::{activate_workspace, deactivate_workspace, sort_workspaces};
 use crate::{
@@ -45,6 +63,10 @@ pub fn move_workspace_in_direction(
           .floating_placement()
           .translate_to_center(&workspace.to_rect()?),
       );
+
+      state
+        .pending_window_rule_events
+        .push((window.id(), WindowRuleEvent::MonitorChange));
     }
 
     state
@@ -81,7 +103,7 @@ pub fn move_workspace_in_direction(
       deactivate_workspace(workspace, state)?;
     }
 
-    sort_workspaces(&target_monitor, config)?;
+    sort_workspaces(&target_monitor, state)?;
 
     state.emit_event(WmEvent::WorkspaceUpdated {
       updated_workspace: workspace.to_dto()?,
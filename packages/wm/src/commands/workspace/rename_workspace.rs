@@ -0,0 +1,70 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{bail, Context};
+use tracing::info;
+use wm_common::WmEvent;
+
+use crate::{
+  models::Workspace, runtime_workspaces::persist_workspace_configs,
+  user_config::UserConfig, wm_state::WmState,
+};
+
+/// Renames a workspace and emits a `WorkspaceUpdated` event.
+///
+/// The display name of the workspace is cleared, since it would otherwise
+/// take precedence over the new name.
+pub fn rename_workspace(
+  workspace: &Workspace,
+  new_name: &str,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  if new_name.trim().is_empty() {
+    bail!("Workspace name cannot be empty.");
+  }
+
+  if state.workspace_config(new_name).is_some() {
+    bail!("Workspace with name '{new_name}' already exists.");
+  }
+
+  let old_name = workspace.config().name;
+  info!("Renaming workspace '{old_name}' to '{new_name}'.");
+
+  let workspace_config = state
+    .workspace_configs
+    .iter_mut()
+    .find(|workspace_config| workspace_config.value.name == old_name)
+    .context("No workspace config.")?;
+
+  workspace_config.value.name = new_name.to_string();
+  workspace_config.value.display_name = None;
+  workspace.set_config(workspace_config.value.clone());
+
+  if state.recent_workspace_name.as_ref() == Some(&old_name) {
+    state.recent_workspace_name = Some(new_name.to_string());
+  }
+
+  persist_workspace_configs(state, config)?;
+
+  state.emit_event(WmEvent::WorkspaceUpdated {
+    updated_workspace: workspace.to_dto()?,
+  });
+
+  Ok(())
+}
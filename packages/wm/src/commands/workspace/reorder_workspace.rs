@@ -0,0 +1,87 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+use wm_common::WmEvent;
+
+use super::sort_workspaces;
+use crate::{
+  models::Workspace, runtime_workspaces::persist_workspace_configs,
+  traits::CommonGetters, user_config::UserConfig, wm_state::WmState,
+};
+
+/// Moves a workspace by the given number of positions relative to the
+/// other workspaces on its monitor.
+///
+/// The new order is kept for the rest of the session (or persisted if
+/// `general.persist_dynamic_workspaces` is enabled).
+pub fn reorder_workspace(
+  workspace: &Workspace,
+  offset: i32,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let monitor = workspace.monitor().context("No monitor.")?;
+  let monitor_workspaces = monitor.workspaces();
+
+  let origin_index = monitor_workspaces
+    .iter()
+    .position(|other| other.id() == workspace.id())
+    .context("Failed to get index of given workspace.")?;
+
+  let target_index = origin_index
+    .saturating_add_signed(isize::try_from(offset)?)
+    .min(monitor_workspaces.len() - 1);
+
+  if target_index == origin_index {
+    return Ok(());
+  }
+
+  let workspace_config_index = state
+    .workspace_config_index(&workspace.config().name)
+    .context("No workspace config.")?;
+
+  let workspace_config =
+    state.workspace_configs.remove(workspace_config_index);
+
+  // Place the config directly before or after the config of the
+  // workspace it's being moved past.
+  let target_name = monitor_workspaces[target_index].config().name;
+  let target_config_index = state
+    .workspace_config_index(&target_name)
+    .context("No workspace config.")?;
+
+  let insert_index = if target_index > origin_index {
+    target_config_index + 1
+  } else {
+    target_config_index
+  };
+
+  state
+    .workspace_configs
+    .insert(insert_index, workspace_config);
+
+  sort_workspaces(&monitor, state)?;
+  persist_workspace_configs(state, config)?;
+
+  state.emit_event(WmEvent::WorkspaceUpdated {
+    updated_workspace: workspace.to_dto()?,
+  });
+
+  Ok(())
+}
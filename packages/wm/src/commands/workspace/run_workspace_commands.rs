@@ -0,0 +1,144 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+
+use anyhow::Context;
+use tracing::{info, warn};
+use uuid::Uuid;
+use wm_common::InvokeCommand;
+
+use crate::{
+  commands::window::run_queued_window_rules, traits::CommonGetters,
+  user_config::UserConfig, wm::WindowManager, wm_state::WmState,
+};
+
+/// Number of passes over queued workspace commands on top of the number
+/// of workspaces, after which any further commands are dropped.
+const EXTRA_PASSES: usize = 3;
+
+/// A workspace hook that runs commands.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WorkspaceHook {
+  Activate,
+  Deactivate,
+  Empty,
+}
+
+/// Commands of a workspace hook that are queued to run once the current
+/// event or command has been processed.
+#[derive(Clone, Debug)]
+pub struct PendingWorkspaceCommands {
+  pub workspace_id: Uuid,
+  pub workspace_name: String,
+  pub hook: WorkspaceHook,
+  pub commands: Vec<InvokeCommand>,
+}
+
+/// Runs workspace commands (i.e. `on_activate`, `on_deactivate` and
+/// `on_empty`) that were queued while processing an event or command.
+///
+/// Commands are run against the workspace that queued them if it's still
+/// active, and otherwise against the focused container.
+///
+/// Each hook of a workspace runs at most once per call, so that hooks
+/// that switch back and forth between workspaces can't loop forever.
+pub fn run_workspace_commands(
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  queue_on_empty_commands(state);
+
+  let max_passes = state.workspace_configs.len() + EXTRA_PASSES;
+  let mut ran_hooks = HashSet::new();
+  let mut pass_count = 0;
+
+  // Running commands can activate or deactivate other workspaces, which
+  // queues further commands.
+  while !state.pending_workspace_commands.is_empty() {
+    if pass_count == max_passes {
+      warn!(
+        "Workspace commands are still queueing commands after {} passes. Dropping {} queued workspace commands.",
+        max_passes,
+        state.pending_workspace_commands.len()
+      );
+
+      state.pending_workspace_commands.clear();
+      break;
+    }
+
+    pass_count += 1;
+
+    let pending_commands =
+      std::mem::take(&mut state.pending_workspace_commands);
+
+    for pending in pending_commands {
+      if !ran_hooks.insert((pending.workspace_name.clone(), pending.hook))
+      {
+        info!(
+          "Skipping {:?} commands of workspace {} that already ran.",
+          pending.hook, pending.workspace_name
+        );
+
+        continue;
+      }
+
+      info!("Running workspace commands: {:?}.", pending.commands);
+
+      let subject_container = state
+        .container_by_id(pending.workspace_id)
+        .or_else(|| state.focused_container())
+        .context("No subject container for workspace commands.")?;
+
+      WindowManager::run_commands(
+        &pending.commands,
+        subject_container,
+        state,
+        config,
+      )?;
+    }
+
+    run_queued_window_rules(state, config)?;
+    queue_on_empty_commands(state);
+  }
+
+  Ok(())
+}
+
+/// Queues `on_empty` commands for workspaces that no longer have any
+/// windows.
+fn queue_on_empty_commands(state: &mut WmState) {
+  for workspace in state.workspaces() {
+    if workspace.has_children() {
+      state.occupied_workspace_ids.insert(workspace.id());
+    } else if state.occupied_workspace_ids.remove(&workspace.id()) {
+      let workspace_config = workspace.config();
+
+      if !workspace_config.on_empty.is_empty() {
+        state
+          .pending_workspace_commands
+          .push(PendingWorkspaceCommands {
+            workspace_id: workspace.id(),
+            workspace_name: workspace_config.name,
+            hook: WorkspaceHook::Empty,
+            commands: workspace_config.on_empty,
+          });
+      }
+    }
+  }
+}
@@ -0,0 +1,47 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use tracing::info;
+use wm_common::WmEvent;
+
+use crate::{models::Workspace, wm_state::WmState};
+
+/// Enables or disables monocle mode on a workspace, where the focused
+/// tiling window is zoomed to fill the workspace and the other tiling
+/// windows are hidden.
+///
+/// The redraw is handled by `sync_monocle_workspaces` once the zoomed
+/// window changes.
+pub fn set_workspace_monocle(
+  workspace: &Workspace,
+  is_monocle: bool,
+  state: &mut WmState,
+) -> anyhow::Result<()> {
+  if workspace.is_monocle() == is_monocle {
+    return Ok(());
+  }
+
+  info!("Setting monocle to {is_monocle} for workspace: {workspace}");
+  workspace.set_is_monocle(is_monocle);
+
+  state.emit_event(WmEvent::WorkspaceUpdated {
+    updated_workspace: workspace.to_dto()?,
+  });
+
+  Ok(())
+}
@@ -1,17 +1,33 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use wm_common::VecDequeExt;
 
-use crate::{
-  models::Monitor, traits::CommonGetters, user_config::UserConfig,
-};
+use crate::{models::Monitor, traits::CommonGetters, wm_state::WmState};
 
 /// Sorts a monitor's workspaces by config order.
 pub fn sort_workspaces(
   monitor: &Monitor,
-  config: &UserConfig,
+  state: &WmState,
 ) -> anyhow::Result<()> {
   let mut workspaces = monitor.workspaces();
-  config.sort_workspaces(&mut workspaces);
+  state.sort_workspaces(&mut workspaces);
 
   for workspace in &workspaces {
     let target_index = &workspaces
@@ -0,0 +1,59 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::Instant;
+
+use tracing::info;
+
+use crate::{
+  traits::{CommonGetters, WindowGetters},
+  wm_state::WmState,
+};
+
+/// Redraws monocle workspaces where the zoomed window has changed (e.g.
+/// due to a focus change, or the zoomed window changing state).
+pub fn sync_monocle_workspaces(state: &mut WmState) {
+  for workspace in state.workspaces() {
+    let prev_window_id = workspace.monocle_window_id();
+    let window_id = workspace.monocle_window().map(|window| window.id());
+
+    if window_id == prev_window_id {
+      continue;
+    }
+
+    info!("Monocle window changed on workspace: {workspace}");
+    workspace.set_monocle_window_id(window_id);
+
+    // Zoomed windows can momentarily cover the monitor while being
+    // resized, so prevent them from being detected as fullscreen.
+    for window in workspace
+      .descendants()
+      .filter_map(|descendant| descendant.as_window_container().ok())
+      .filter(|window| {
+        Some(window.id()) == window_id
+          || Some(window.id()) == prev_window_id
+      })
+    {
+      state
+        .fullscreen_cooldowns
+        .insert(window.native().handle, Instant::now());
+    }
+
+    state.pending_sync.queue_container_to_redraw(workspace);
+  }
+}
@@ -0,0 +1,506 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  env, fs,
+  path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+use serde_yaml::{Mapping, Value};
+
+/// Resolves the `include` directives, `machine` overrides and
+/// `${ENV_VAR}` references of a user config, and gets the merged config.
+///
+/// Included files are merged in order, followed by the including file
+/// itself and then its matching `machine` overrides, so that later files
+/// take precedence. Mappings are merged recursively, and lists replace
+/// each other apart from:
+/// * `keybindings`, which are appended, and take over any key bindings of
+///   earlier keybindings.
+/// * `window_rules`, which are appended.
+/// * `workspaces` and `binding_modes`, which are merged by their `name`.
+pub fn resolve_config(
+  config_path: &Path,
+  config_str: &str,
+) -> anyhow::Result<Value> {
+  let mut include_stack = vec![canonical_path(config_path)?];
+  resolve_file_value(config_path, config_str, &mut include_stack)
+}
+
+fn resolve_file_value(
+  path: &Path,
+  contents: &str,
+  include_stack: &mut Vec<PathBuf>,
+) -> anyhow::Result<Value> {
+  // Errors include the path, since the same error can come from any of
+  // the included files.
+  let mut value = serde_yaml::from_str::<Value>(contents)
+    .map_err(|err| anyhow!("{err} in {}", path.display()))?;
+
+  let interpolate = |value: &mut Value| {
+    interpolate_env_vars(value)
+      .map_err(|err| anyhow!("{err} ({})", path.display()))
+  };
+
+  let Value::Mapping(mut mapping) = value else {
+    interpolate(&mut value)?;
+    return Ok(value);
+  };
+
+  let mut include = mapping.remove("include");
+  let machine = mapping.remove("machine");
+
+  // Environment variables are interpolated after picking the matching
+  // `machine` blocks, so that blocks of other machines can reference
+  // variables that aren't set on this machine.
+  if let Some(include) = &mut include {
+    interpolate(include)?;
+  }
+
+  let mut base = Value::Mapping(mapping);
+  interpolate(&mut base)?;
+
+  let mut resolved = Value::Mapping(Mapping::new());
+
+  let dir = path.parent().context("Invalid config path.")?;
+
+  for pattern in string_list(include.as_ref(), "include")? {
+    for include_path in glob_files(dir, &pattern)? {
+      let canonical_path = canonical_path(&include_path)?;
+
+      if include_stack.contains(&canonical_path) {
+        bail!(
+          "Config file {} is included recursively.",
+          include_path.display()
+        );
+      }
+
+      let contents =
+        fs::read_to_string(&include_path).with_context(|| {
+          format!("Unable to read {}.", include_path.display())
+        })?;
+
+      include_stack.push(canonical_path);
+      let included =
+        resolve_file_value(&include_path, &contents, include_stack)?;
+      include_stack.pop();
+
+      merge_config(&mut resolved, included);
+    }
+  }
+
+  merge_config(&mut resolved, base);
+
+  for mut overrides in machine_overrides(machine)
+    .map_err(|err| anyhow!("{err} ({})", path.display()))?
+  {
+    interpolate(&mut overrides)?;
+    merge_config(&mut resolved, overrides);
+  }
+
+  Ok(resolved)
+}
+
+fn canonical_path(path: &Path) -> anyhow::Result<PathBuf> {
+  fs::canonicalize(path)
+    .with_context(|| format!("Unable to resolve {}.", path.display()))
+}
+
+/// Gets the strings of a value that is either a string or a list of
+/// strings.
+fn string_list(
+  value: Option<&Value>,
+  key: &str,
+) -> anyhow::Result<Vec<String>> {
+  match value {
+    None => Ok(Vec::new()),
+    Some(Value::String(string)) => Ok(vec![string.clone()]),
+    Some(Value::Sequence(sequence)) => sequence
+      .iter()
+      .map(|value| {
+        value
+          .as_str()
+          .map(ToString::to_string)
+          .with_context(|| format!("Expected a string in `{key}`."))
+      })
+      .collect(),
+    Some(_) => bail!("Expected a string or a list of strings in `{key}`."),
+  }
+}
+
+/// Gets the overrides of `machine` blocks that match the hostname of
+/// this machine.
+///
+/// Either a single block or a list of blocks can be given, e.g.:
+/// ```yaml
+/// machine:
+///   hostname: 'WORK-LAPTOP'
+///   overrides:
+///     gaps:
+///       inner_gap: '0px'
+/// ```
+fn machine_overrides(
+  machine: Option<Value>,
+) -> anyhow::Result<Vec<Value>> {
+  let blocks = match machine {
+    None => return Ok(Vec::new()),
+    Some(Value::Sequence(blocks)) => blocks,
+    Some(block) => vec![block],
+  };
+
+  let hostname = hostname();
+  let mut overrides = Vec::new();
+
+  for mut block in blocks {
+    let block_hostname = block
+      .get("hostname")
+      .and_then(Value::as_str)
+      .context("Expected a `hostname` in `machine` block.")?;
+
+    let is_match = hostname.as_ref().is_some_and(|hostname| {
+      hostname.eq_ignore_ascii_case(block_hostname)
+    });
+
+    if is_match {
+      if let Some(block_overrides) = block
+        .as_mapping_mut()
+        .and_then(|block| block.remove("overrides"))
+      {
+        overrides.push(block_overrides);
+      }
+    }
+  }
+
+  Ok(overrides)
+}
+
+/// Gets the hostname of this machine.
+fn hostname() -> Option<String> {
+  env::var("COMPUTERNAME")
+    .or_else(|_| env::var("HOSTNAME"))
+    .ok()
+}
+
+/// Replaces `${NAME}` and `${NAME:-default}` in all strings of the given
+/// value with the value of the environment variable `NAME`.
+fn interpolate_env_vars(value: &mut Value) -> anyhow::Result<()> {
+  match value {
+    Value::String(string) => *string = interpolate_str(string)?,
+    Value::Sequence(sequence) => {
+      for value in sequence {
+        interpolate_env_vars(value)?;
+      }
+    }
+    Value::Mapping(mapping) => {
+      for (_, value) in mapping.iter_mut() {
+        interpolate_env_vars(value)?;
+      }
+    }
+    Value::Tagged(tagged) => interpolate_env_vars(&mut tagged.value)?,
+    _ => {}
+  }
+
+  Ok(())
+}
+
+/// Replaces environment variable references within a string.
+///
+/// References need to be valid variable names, so that other uses of
+/// `${...}` (e.g. `${env:USERPROFILE}` in PowerShell commands) are kept
+/// as is. `$${NAME}` can be used to escape a reference.
+fn interpolate_str(input: &str) -> anyhow::Result<String> {
+  let mut output = String::with_capacity(input.len());
+  let mut rest = input;
+
+  while let Some(index) = rest.find('$') {
+    output.push_str(&rest[..index]);
+    let remaining = &rest[index..];
+
+    if let Some(after) = remaining.strip_prefix("$${") {
+      output.push_str("${");
+      rest = after;
+      continue;
+    }
+
+    let reference = remaining
+      .strip_prefix("${")
+      .and_then(|after| after.split_once('}'))
+      .and_then(|(reference, after)| {
+        let (name, default) = match reference.split_once(":-") {
+          Some((name, default)) => (name, Some(default)),
+          None => (reference, None),
+        };
+
+        is_env_var_name(name).then_some((name, default, after))
+      });
+
+    let Some((name, default, after)) = reference else {
+      output.push('$');
+      rest = &remaining[1..];
+      continue;
+    };
+
+    let value = match (env::var(name), default) {
+      (Ok(value), _) => value,
+      (Err(_), Some(default)) => default.to_string(),
+      (Err(_), None) => bail!("Environment variable `{name}` is not set."),
+    };
+
+    output.push_str(&value);
+    rest = after;
+  }
+
+  output.push_str(rest);
+  Ok(output)
+}
+
+fn is_env_var_name(name: &str) -> bool {
+  let mut chars = name.chars();
+
+  chars
+    .next()
+    .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+    && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Gets the files matching a path pattern relative to the given
+/// directory, sorted by path.
+///
+/// Any component of the pattern can contain the wildcards `*` and `?`.
+/// Patterns without wildcards need to match an existing file.
+fn glob_files(dir: &Path, pattern: &str) -> anyhow::Result<Vec<PathBuf>> {
+  let pattern_path = dir.join(pattern);
+
+  if !has_wildcards(pattern) {
+    if !pattern_path.is_file() {
+      bail!("Included file {} doesn't exist.", pattern_path.display());
+    }
+
+    return Ok(vec![pattern_path]);
+  }
+
+  let mut matches = vec![PathBuf::new()];
+
+  for component in pattern_path.components() {
+    match component {
+      Component::Normal(name)
+        if name.to_str().is_some_and(has_wildcards) =>
+      {
+        let name_pattern = name.to_string_lossy();
+
+        matches = matches
+          .iter()
+          .filter_map(|parent| fs::read_dir(parent).ok())
+          .flatten()
+          .filter_map(Result::ok)
+          .filter(|entry| {
+            wildcard_match(
+              &name_pattern,
+              &entry.file_name().to_string_lossy(),
+            )
+          })
+          .map(|entry| entry.path())
+          .collect();
+      }
+      component => {
+        for path in &mut matches {
+          path.push(component);
+        }
+      }
+    }
+  }
+
+  matches.retain(|path| path.is_file());
+  matches.sort();
+
+  Ok(matches)
+}
+
+fn has_wildcards(pattern: &str) -> bool {
+  pattern.contains(['*', '?'])
+}
+
+/// Whether a file name matches a pattern with the wildcards `*` (any
+/// number of characters) and `?` (a single character). Case-insensitive,
+/// like file names on Windows.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+  let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+  let name = name.to_lowercase().chars().collect::<Vec<_>>();
+
+  let (mut pattern_index, mut name_index) = (0, 0);
+
+  // Position of the last `*` in the pattern, and the position in the
+  // name that it was matched up to.
+  let mut backtrack = None;
+
+  while name_index < name.len() {
+    match pattern.get(pattern_index) {
+      Some('*') => {
+        backtrack = Some((pattern_index, name_index));
+        pattern_index += 1;
+      }
+      Some(&char) if char == '?' || char == name[name_index] => {
+        pattern_index += 1;
+        name_index += 1;
+      }
+      _ => match backtrack {
+        // Let the last `*` match one more character.
+        Some((star_index, star_name_index)) => {
+          backtrack = Some((star_index, star_name_index + 1));
+          pattern_index = star_index + 1;
+          name_index = star_name_index + 1;
+        }
+        None => return false,
+      },
+    }
+  }
+
+  pattern[pattern_index..].iter().all(|&char| char == '*')
+}
+
+/// Merges a config into another config, with the values of `overlay`
+/// taking precedence.
+fn merge_config(base: &mut Value, overlay: Value) {
+  match (base, overlay) {
+    // Empty config files are parsed as null.
+    (_, Value::Null) => {}
+    (Value::Mapping(base), Value::Mapping(overlay)) => {
+      for (key, value) in overlay {
+        let Some(base_value) = base.get_mut(&key) else {
+          base.insert(key, value);
+          continue;
+        };
+
+        match key.as_str() {
+          Some("keybindings") => merge_keybindings(base_value, value),
+          Some("window_rules") => append_list(base_value, value),
+          Some("workspaces" | "binding_modes") => {
+            merge_named_list(base_value, value);
+          }
+          _ => merge_values(base_value, value),
+        }
+      }
+    }
+    (base, overlay) => *base = overlay,
+  }
+}
+
+/// Merges mappings recursively. Any other value replaces the base value.
+fn merge_values(base: &mut Value, overlay: Value) {
+  match (base, overlay) {
+    (Value::Mapping(base), Value::Mapping(overlay)) => {
+      for (key, value) in overlay {
+        match base.get_mut(&key) {
+          Some(base_value) => merge_values(base_value, value),
+          None => {
+            base.insert(key, value);
+          }
+        }
+      }
+    }
+    (base, overlay) => *base = overlay,
+  }
+}
+
+fn append_list(base: &mut Value, overlay: Value) {
+  match (base, overlay) {
+    (Value::Sequence(base), Value::Sequence(overlay)) => {
+      base.extend(overlay);
+    }
+    (base, overlay) => *base = overlay,
+  }
+}
+
+/// Merges lists of items with a `name` (e.g. workspaces), where items
+/// with the same name are merged and other items are appended.
+fn merge_named_list(base: &mut Value, overlay: Value) {
+  match (base, overlay) {
+    (Value::Sequence(base), Value::Sequence(overlay)) => {
+      for item in overlay {
+        let base_item = item.get("name").and_then(|name| {
+          base
+            .iter_mut()
+            .find(|base_item| base_item.get("name") == Some(name))
+        });
+
+        match base_item {
+          Some(base_item) => merge_values(base_item, item),
+          None => base.push(item),
+        }
+      }
+    }
+    (base, overlay) => *base = overlay,
+  }
+}
+
+/// Appends keybindings, and removes their key bindings from earlier
+/// keybindings. Earlier keybindings that are left without any key
+/// bindings are removed.
+fn merge_keybindings(base: &mut Value, overlay: Value) {
+  match (base, overlay) {
+    (Value::Sequence(base), Value::Sequence(overlay)) => {
+      let overlay_bindings = overlay
+        .iter()
+        .flat_map(keybinding_bindings)
+        .collect::<Vec<_>>();
+
+      base.retain_mut(|keybinding| {
+        let Some(Value::Sequence(bindings)) =
+          keybinding.get_mut("bindings")
+        else {
+          return true;
+        };
+
+        bindings.retain(|binding| {
+          binding.as_str().is_none_or(|binding| {
+            !overlay_bindings.contains(&normalize_binding(binding))
+          })
+        });
+
+        !bindings.is_empty()
+      });
+
+      base.extend(overlay);
+    }
+    (base, overlay) => *base = overlay,
+  }
+}
+
+fn keybinding_bindings(keybinding: &Value) -> Vec<String> {
+  keybinding
+    .get("bindings")
+    .and_then(Value::as_sequence)
+    .map(|bindings| {
+      bindings
+        .iter()
+        .filter_map(Value::as_str)
+        .map(normalize_binding)
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+/// Normalizes a key binding for comparison (e.g. `Alt + H` -> `alt+h`).
+fn normalize_binding(binding: &str) -> String {
+  binding
+    .chars()
+    .filter(|char| !char.is_whitespace())
+    .collect::<String>()
+    .to_lowercase()
+}
@@ -16,14 +16,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use std::collections::HashMap;
+
 use anyhow::Context;
 use tracing::info;
+use wm_common::WindowRuleEvent;
 use wm_platform::Platform;
 
 use crate::{
-  commands::monitor::{
-    add_monitor, move_bounded_workspaces_to_new_monitor, remove_monitor,
-    sort_monitors, update_monitor,
+  commands::{
+    monitor::{
+      add_monitor, move_bounded_workspaces_to_new_monitor, remove_monitor,
+      sort_monitors, update_monitor,
+    },
+    window::run_window_rules,
   },
   models::Monitor,
   traits::{CommonGetters, PositionGetters, WindowGetters},
@@ -31,12 +37,21 @@ use crate::{
   wm_state::WmState,
 };
 
+#[allow(clippy::too_many_lines)]
 pub fn handle_display_settings_changed(
   state: &mut WmState,
-  config: &UserConfig,
+  config: &mut UserConfig,
 ) -> anyhow::Result<()> {
   info!("Display settings changed.");
 
+  // Monitor of each window prior to the change, for running window rules
+  // on windows that end up on a different monitor.
+  let prev_monitor_ids = state
+    .windows()
+    .into_iter()
+    .filter_map(|window| Some((window.id(), window.monitor()?.id())))
+    .collect::<HashMap<_, _>>();
+
   let native_monitors = Platform::sorted_monitors()?;
 
   let hardware_ids = native_monitors
@@ -130,7 +145,7 @@ pub fn handle_display_settings_changed(
   // is connected again.
   for pending_monitor in pending_monitors {
     if state.monitors().len() != 1 {
-      remove_monitor(pending_monitor, state, config)?;
+      remove_monitor(pending_monitor, state)?;
     }
   }
 
@@ -179,5 +194,22 @@ pub fn handle_display_settings_changed(
     .pending_sync
     .queue_container_to_redraw(state.root_container.clone());
 
+  for window in state.windows() {
+    let has_monitor_change = window.monitor().is_some_and(|monitor| {
+      prev_monitor_ids
+        .get(&window.id())
+        .is_some_and(|prev_id| *prev_id != monitor.id())
+    });
+
+    if has_monitor_change {
+      run_window_rules(
+        window,
+        &WindowRuleEvent::MonitorChange,
+        state,
+        config,
+      )?;
+    }
+  }
+
   Ok(())
 }
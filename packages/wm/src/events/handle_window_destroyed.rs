@@ -1,16 +1,40 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
 use tracing::info;
+use wm_common::WindowRuleEvent;
 use wm_platform::NativeWindow;
 
 use crate::{
-  commands::{window::unmanage_window, workspace::deactivate_workspace},
+  commands::{
+    window::{run_window_rules, unmanage_window},
+    workspace::deactivate_workspace,
+  },
   traits::CommonGetters,
+  user_config::UserConfig,
   wm_state::WmState,
 };
 
 pub fn handle_window_destroyed(
   native_window: &NativeWindow,
   state: &mut WmState,
+  config: &mut UserConfig,
 ) -> anyhow::Result<()> {
   let found_window = state.window_from_native(native_window);
 
@@ -19,7 +43,7 @@ pub fn handle_window_destroyed(
     let workspace = window.workspace().context("No workspace.")?;
 
     info!("Window closed: {window}");
-    unmanage_window(window, state)?;
+    unmanage_window(window.clone(), state)?;
 
     // Destroy parent workspace if window was killed while its workspace
     // was not displayed (e.g. via task manager).
@@ -29,6 +53,8 @@ pub fn handle_window_destroyed(
     {
       deactivate_workspace(workspace, state)?;
     }
+
+    run_window_rules(window, &WindowRuleEvent::Unmanage, state, config)?;
   }
 
   Ok(())
@@ -0,0 +1,54 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use tracing::info;
+use wm_common::WindowRuleEvent;
+use wm_platform::{NativeWindow, Platform};
+
+use crate::{
+  commands::window::{run_window_rules, set_window_urgency},
+  traits::WindowGetters,
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+pub fn handle_window_flashed(
+  native_window: &NativeWindow,
+  state: &mut WmState,
+  config: &mut UserConfig,
+) -> anyhow::Result<()> {
+  let found_window = state.window_from_native(native_window);
+
+  // Ignore flashes from the foreground window, since it already has the
+  // user's attention.
+  if let Some(window) = found_window {
+    if window.is_urgent()
+      || Platform::foreground_window() == *native_window
+    {
+      return Ok(());
+    }
+
+    info!("Window requested attention: {window}");
+    set_window_urgency(&window, true, state)?;
+
+    // Run window rules for urgency events.
+    run_window_rules(window, &WindowRuleEvent::Urgent, state, config)?;
+  }
+
+  Ok(())
+}
@@ -1,15 +1,36 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use tracing::info;
-use wm_common::DisplayState;
+use wm_common::{DisplayState, WindowRuleEvent};
 use wm_platform::NativeWindow;
 
 use crate::{
-  commands::window::unmanage_window, traits::WindowGetters,
+  commands::window::{run_window_rules, unmanage_window},
+  traits::WindowGetters,
+  user_config::UserConfig,
   wm_state::WmState,
 };
 
 pub fn handle_window_hidden(
   native_window: &NativeWindow,
   state: &mut WmState,
+  config: &mut UserConfig,
 ) -> anyhow::Result<()> {
   let found_window = state.window_from_native(native_window);
 
@@ -28,7 +49,8 @@ pub fn handle_window_hidden(
     if window.display_state() == DisplayState::Shown
       && !window.native().is_visible().unwrap_or(false)
     {
-      unmanage_window(window, state)?;
+      unmanage_window(window.clone(), state)?;
+      run_window_rules(window, &WindowRuleEvent::Unmanage, state, config)?;
     }
   }
 
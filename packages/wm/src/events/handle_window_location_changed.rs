@@ -29,7 +29,7 @@ use wm_platform::NativeWindow;
 use crate::{
   commands::{
     container::{flatten_split_container, move_container_within_tree},
-    window::update_window_state,
+    window::{queue_window_move_rules, update_window_state},
   },
   models::{TilingWindow, WindowContainer},
   traits::{CommonGetters, PositionGetters, WindowGetters},
@@ -90,16 +90,21 @@ pub fn handle_window_location_changed(
       .displayed_workspace()
       .context("No Workspace")?;
 
-    let monitor_rect = if config
-      .outer_gaps_for_workspace(&nearest_workspace)
-      .is_significant()
-    {
+    let monitor_rect = if nearest_workspace.outer_gaps().is_significant() {
       nearest_monitor.native().working_rect()?.clone()
     } else {
       nearest_monitor.to_rect()?
     };
 
-    let is_fullscreen = window.native().is_fullscreen(&monitor_rect)?;
+    // The zoomed window of a monocle workspace can cover the monitor
+    // without being fullscreen.
+    let is_monocle_window = window
+      .workspace()
+      .and_then(|workspace| workspace.monocle_window())
+      .is_some_and(|monocle_window| monocle_window.id() == window.id());
+
+    let is_fullscreen = !is_monocle_window
+      && window.native().is_fullscreen(&monitor_rect)?;
 
     // Skip fullscreen state transitions if this window changed state
     // recently. Prevents oscillation when windows (e.g. RDP clients)
@@ -146,6 +151,19 @@ pub fn handle_window_location_changed(
           )?;
         }
       }
+      WindowState::Tiling
+        if !in_cooldown
+          && is_fullscreen
+          && !is_maximized
+          && config.value.window_behavior.fake_fullscreen =>
+      {
+        // With fake fullscreen, windows that make themselves fullscreen
+        // are kept within their tile instead.
+        info!("Confining fullscreened window to its tile: {window}");
+        state.fullscreen_cooldowns.insert(handle, Instant::now());
+
+        state.pending_sync.queue_container_to_redraw(window);
+      }
       _ if !in_cooldown => {
         if is_maximized || is_fullscreen {
           info!("Window fullscreened: {window}");
@@ -187,12 +205,16 @@ pub fn handle_window_location_changed(
               window.set_insertion_target(None);
             }
 
+            let workspace = window.workspace().context("No workspace.")?;
+
             move_container_within_tree(
-              &window.into(),
+              &window.clone().into(),
               &updated_workspace.clone().into(),
               updated_workspace.child_count(),
               state,
             )?;
+
+            queue_window_move_rules(&window, &workspace, &monitor, state);
           }
         }
       }
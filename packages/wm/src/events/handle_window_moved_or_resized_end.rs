@@ -1,8 +1,26 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use anyhow::Context;
-use tracing::info;
+use tracing::{info, warn};
 use wm_common::{
-  try_warn, ActiveDragOperation, LengthValue, Point, Rect,
-  TilingDirection, WindowState,
+  try_warn, ActiveDragOperation, FloatingPlacementStrategy, LengthValue,
+  Point, Rect, TilingDirection, WindowState,
 };
 use wm_platform::{NativeWindow, Platform};
 
@@ -57,6 +75,12 @@ pub fn handle_window_moved_or_resized_end(
             // Window is a temporary floating window that should be
             // reverted back to tiling.
             drop_as_tiling_window(window, state, config)?;
+          } else if matches!(window.state(), WindowState::Floating(_)) {
+            if active_drag.operation == Some(ActiveDragOperation::Moving) {
+              snap_to_edges(window, state, config)?;
+            }
+
+            remember_placement(window, state, config)?;
           }
         }
       }
@@ -85,6 +109,82 @@ pub fn handle_window_moved_or_resized_end(
   Ok(())
 }
 
+/// Snaps a dragged floating window to the edges of its workspace and of
+/// other windows within `window_behavior.snap_threshold`.
+fn snap_to_edges(
+  moved_window: &NonTilingWindow,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let threshold =
+    i32::try_from(config.value.window_behavior.snap_threshold)?;
+
+  if threshold == 0 {
+    return Ok(());
+  }
+
+  let workspace = moved_window.workspace().context("No workspace.")?;
+
+  let other_rects = workspace
+    .descendants()
+    .filter_map(|container| container.as_window_container().ok())
+    .filter(|window| {
+      window.id() != moved_window.id()
+        && !matches!(window.state(), WindowState::Minimized)
+    })
+    .map(|window| window.to_rect())
+    .try_collect::<Vec<_>>()?;
+
+  let placement = moved_window.floating_placement();
+  let snapped_placement = placement.snap_to_edges(
+    &[&[workspace.to_rect()?], other_rects.as_slice()].concat(),
+    threshold,
+  );
+
+  if snapped_placement != placement {
+    info!(
+      "Snapping floating window to edges: {}",
+      moved_window.as_window_container()?
+    );
+    moved_window.set_floating_placement(snapped_placement);
+    state
+      .pending_sync
+      .queue_container_to_redraw(moved_window.clone());
+  }
+
+  Ok(())
+}
+
+/// Remembers where the user placed a floating window, for use by the
+/// `remember_per_app` floating placement strategy.
+fn remember_placement(
+  moved_window: &NonTilingWindow,
+  state: &mut WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  if config.value.window_behavior.floating_placement
+    != FloatingPlacementStrategy::RememberPerApp
+  {
+    return Ok(());
+  }
+
+  let workspace_rect = moved_window
+    .workspace()
+    .context("No workspace.")?
+    .to_rect()?;
+
+  if let Err(err) = state.remembered_placements.remember(
+    &moved_window.native(),
+    &moved_window.floating_placement(),
+    &workspace_rect,
+    config,
+  ) {
+    warn!("Failed to remember floating placement: {:?}", err);
+  }
+
+  Ok(())
+}
+
 /// Handles transition from temporary floating window to tiling window on
 /// drag end.
 #[allow(clippy::too_many_lines)]
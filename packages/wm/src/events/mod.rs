@@ -1,6 +1,26 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 mod handle_display_settings_changed;
+mod handle_modifiers_released;
 mod handle_mouse_move;
 mod handle_window_destroyed;
+mod handle_window_flashed;
 mod handle_window_focused;
 mod handle_window_hidden;
 mod handle_window_location_changed;
@@ -12,8 +32,10 @@ mod handle_window_shown;
 mod handle_window_title_changed;
 
 pub use handle_display_settings_changed::*;
+pub use handle_modifiers_released::*;
 pub use handle_mouse_move::*;
 pub use handle_window_destroyed::*;
+pub use handle_window_flashed::*;
 pub use handle_window_focused::*;
 pub use handle_window_hidden::*;
 pub use handle_window_location_changed::*;
@@ -0,0 +1,210 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use uuid::Uuid;
+
+/// Maximum number of entries to keep in the focus history.
+const MAX_ENTRIES: usize = 100;
+
+/// Global history of focused windows, across all workspaces and
+/// monitors.
+///
+/// Works similarly to a browser history: navigating back and forward
+/// moves a cursor through the entries, and focusing a window while
+/// navigated back discards the entries ahead of the cursor.
+#[derive(Debug, Default)]
+pub struct FocusHistory {
+  /// IDs of focused windows, from oldest to newest.
+  entries: Vec<Uuid>,
+
+  /// Index of the entry that was last navigated to via `back` or
+  /// `forward`. Is `None` when at the newest entry.
+  cursor: Option<usize>,
+
+  /// In-progress cycle through recent windows.
+  cycle: Option<FocusCycle>,
+}
+
+/// Snapshot of recent windows that are cycled through while a modifier
+/// key is held.
+#[derive(Debug)]
+struct FocusCycle {
+  window_ids: Vec<Uuid>,
+
+  /// Index of the window that was last cycled to.
+  index: Option<usize>,
+}
+
+impl FocusHistory {
+  /// Creates a history with the given entries, from oldest to newest.
+  pub fn from_entries(entries: Vec<Uuid>) -> Self {
+    Self {
+      entries,
+      ..Default::default()
+    }
+  }
+
+  /// IDs of focused windows, from oldest to newest.
+  pub fn entries(&self) -> &[Uuid] {
+    &self.entries
+  }
+
+  /// Adds a focused window to the history.
+  ///
+  /// Does nothing while cycling through recent windows, or if the window
+  /// is the entry that was just navigated to.
+  pub fn record(&mut self, window_id: Uuid) {
+    if self.cycle.is_some() {
+      return;
+    }
+
+    if let Some(cursor) = self.cursor {
+      if self.entries.get(cursor) == Some(&window_id) {
+        return;
+      }
+
+      // Focusing a different window discards the forward history.
+      self.entries.truncate(cursor + 1);
+      self.cursor = None;
+    }
+
+    if self.entries.last() == Some(&window_id) {
+      return;
+    }
+
+    self.entries.push(window_id);
+
+    if self.entries.len() > MAX_ENTRIES {
+      self.entries.remove(0);
+    }
+  }
+
+  /// Removes all entries of a window (e.g. when it gets unmanaged).
+  pub fn remove(&mut self, window_id: Uuid) {
+    let mut entries = Vec::with_capacity(self.entries.len());
+    let mut cursor = None;
+
+    for (index, &entry) in self.entries.iter().enumerate() {
+      // Skip the removed window, and any entries that would otherwise
+      // become consecutive duplicates.
+      if entry != window_id && entries.last() != Some(&entry) {
+        entries.push(entry);
+      }
+
+      if self.cursor == Some(index) {
+        cursor = entries.len().checked_sub(1);
+      }
+    }
+
+    self.entries = entries;
+    self.cursor = cursor.filter(|&cursor| cursor + 1 < self.entries.len());
+
+    if let Some(cycle) = &mut self.cycle {
+      cycle.window_ids.retain(|&id| id != window_id);
+    }
+  }
+
+  /// Moves the cursor to the previous entry that passes the given
+  /// predicate, and returns its window ID.
+  pub fn back(
+    &mut self,
+    is_valid: impl Fn(&Uuid) -> bool,
+  ) -> Option<Uuid> {
+    let current = self.cursor.or(self.entries.len().checked_sub(1))?;
+    let current_id = self.entries[current];
+
+    let index = (0..current).rev().find(|&index| {
+      let entry = &self.entries[index];
+      *entry != current_id && is_valid(entry)
+    })?;
+
+    self.cursor = Some(index);
+    Some(self.entries[index])
+  }
+
+  /// Moves the cursor to the next entry that passes the given predicate,
+  /// and returns its window ID.
+  pub fn forward(
+    &mut self,
+    is_valid: impl Fn(&Uuid) -> bool,
+  ) -> Option<Uuid> {
+    let current = self.cursor?;
+    let current_id = self.entries[current];
+
+    let index = (current + 1..self.entries.len()).find(|&index| {
+      let entry = &self.entries[index];
+      *entry != current_id && is_valid(entry)
+    })?;
+
+    self.cursor = (index + 1 < self.entries.len()).then_some(index);
+    Some(self.entries[index])
+  }
+
+  /// Unique window IDs in the history, from most to least recently
+  /// focused.
+  pub fn most_recent(&self) -> Vec<Uuid> {
+    let mut window_ids = Vec::new();
+
+    for &entry in self.entries.iter().rev() {
+      if !window_ids.contains(&entry) {
+        window_ids.push(entry);
+      }
+    }
+
+    window_ids
+  }
+
+  /// Whether a cycle through recent windows is in progress.
+  pub fn is_cycling(&self) -> bool {
+    self.cycle.is_some()
+  }
+
+  /// Starts a cycle through the given window IDs.
+  ///
+  /// The IDs should be ordered from most to least recently focused, with
+  /// the currently focused window last.
+  pub fn start_cycle(&mut self, window_ids: Vec<Uuid>) {
+    self.cycle = Some(FocusCycle {
+      window_ids,
+      index: None,
+    });
+  }
+
+  /// Advances the cycle through recent windows and returns the window ID
+  /// to focus.
+  pub fn cycle_next(&mut self) -> Option<Uuid> {
+    let cycle = self.cycle.as_mut()?;
+
+    if cycle.window_ids.is_empty() {
+      return None;
+    }
+
+    let index = cycle
+      .index
+      .map_or(0, |index| (index + 1) % cycle.window_ids.len());
+
+    cycle.index = Some(index);
+    Some(cycle.window_ids[index])
+  }
+
+  /// Ends the cycle through recent windows. Returns whether a cycle was
+  /// in progress.
+  pub fn end_cycle(&mut self) -> bool {
+    self.cycle.take().is_some()
+  }
+}
@@ -0,0 +1,561 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  env, fs,
+  path::{Path, PathBuf},
+  time::Instant,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use wm_common::{
+  ContainerDto, DisplayState, GapsConfig, Rect, RectDelta,
+  SizeConstraints, SplitContainerDto, WindowDto, WindowEffectOverrides,
+  WindowState, WorkspaceDto,
+};
+use wm_platform::{NativeMonitor, NativeWindow};
+
+use crate::{
+  commands::{container::attach_container, monitor::add_monitor},
+  models::{
+    Container, Monitor, NonTilingWindow, SplitContainer, TilingWindow,
+    WindowContainer, Workspace,
+  },
+  runtime_workspaces::{PersistedWorkspace, RuntimeWorkspaceConfig},
+  traits::{CommonGetters, TilingSizeGetters, WindowGetters},
+  user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// State that is handed over to a new WM instance on `wm-restart`, so
+/// that it can adopt the windows of the previous instance as they are.
+///
+/// Windows keep their IDs across the restart, whereas monitors,
+/// workspaces and split containers get new IDs.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Handoff {
+  /// Container tree (i.e. the output of `query monitors`).
+  pub monitors: Vec<ContainerDto>,
+
+  /// Workspace configs in their runtime order.
+  pub workspaces: Vec<PersistedWorkspace>,
+
+  /// Names of the enabled binding modes.
+  pub binding_modes: Vec<String>,
+
+  pub is_paused: bool,
+
+  pub recent_workspace_name: Option<String>,
+
+  /// IDs of focused windows, from oldest to newest.
+  pub focus_history: Vec<Uuid>,
+
+  /// Handles of windows that were ignored via the `ignore` command.
+  pub ignored_handles: Vec<isize>,
+
+  /// Windows that are hidden by window swallowing.
+  pub swallowed_windows: Vec<SwallowedWindow>,
+}
+
+/// A window that is hidden while a window it launched takes its place.
+///
+/// These aren't part of the container tree, so they're saved separately.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwallowedWindow {
+  /// ID of the window that took the place of the swallowed window.
+  pub swallowing_window_id: Uuid,
+  pub id: Uuid,
+  pub handle: isize,
+  pub border_delta: RectDelta,
+  pub floating_placement: Rect,
+  pub effect_overrides: WindowEffectOverrides,
+}
+
+impl Handoff {
+  pub fn new(state: &WmState) -> anyhow::Result<Self> {
+    let monitors = state
+      .monitors()
+      .into_iter()
+      .map(|monitor| monitor.to_dto())
+      .try_collect()?;
+
+    let swallowed_windows = state
+      .swallowed_windows
+      .iter()
+      .map(|(swallowing_window_id, window)| SwallowedWindow {
+        swallowing_window_id: *swallowing_window_id,
+        id: window.id(),
+        handle: window.native().handle,
+        border_delta: window.border_delta(),
+        floating_placement: window.floating_placement(),
+        effect_overrides: window.effect_overrides(),
+      })
+      .collect();
+
+    Ok(Self {
+      monitors,
+      workspaces: state
+        .workspace_configs
+        .iter()
+        .map(PersistedWorkspace::new)
+        .collect(),
+      binding_modes: state
+        .binding_modes
+        .iter()
+        .map(|binding_mode| binding_mode.name.clone())
+        .collect(),
+      is_paused: state.is_paused,
+      recent_workspace_name: state.recent_workspace_name.clone(),
+      focus_history: state.focus_history.entries().to_vec(),
+      ignored_handles: state
+        .ignored_windows
+        .iter()
+        .map(|window| window.handle)
+        .collect(),
+      swallowed_windows,
+    })
+  }
+
+  pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+    fs::write(path, serde_json::to_string(self)?)
+      .with_context(|| format!("Unable to write to {}.", path.display()))
+  }
+
+  /// Reads a handoff file, and removes it so that it only gets adopted
+  /// once.
+  pub fn read(path: &Path) -> anyhow::Result<Self> {
+    let handoff_str = fs::read_to_string(path)
+      .with_context(|| format!("Unable to read {}.", path.display()))?;
+
+    let _ = fs::remove_file(path);
+
+    serde_json::from_str(&handoff_str).context("Invalid handoff file.")
+  }
+
+  /// Handles of all windows in the handoff, including ignored and
+  /// swallowed windows.
+  pub fn window_handles(&self) -> Vec<isize> {
+    let mut handles = Vec::new();
+
+    for monitor in &self.monitors {
+      push_window_handles(monitor, &mut handles);
+    }
+
+    handles.extend(&self.ignored_handles);
+    handles.extend(
+      self
+        .swallowed_windows
+        .iter()
+        .map(|swallowed| swallowed.handle),
+    );
+
+    handles
+  }
+
+  /// Whether the given monitors are the same as when the handoff was
+  /// written, in which case the container tree can be adopted as is.
+  pub fn has_same_monitors(
+    &self,
+    native_monitors: &[NativeMonitor],
+  ) -> bool {
+    self.monitors.len() == native_monitors.len()
+      && self.monitors.iter().zip(native_monitors).all(
+        |(monitor, native_monitor)| match monitor {
+          ContainerDto::Monitor(monitor) => native_monitor
+            .device_name()
+            .is_ok_and(|device_name| *device_name == monitor.device_name),
+          _ => false,
+        },
+      )
+  }
+
+  /// Recreates the container tree on the given monitors, which need to
+  /// match the monitors of the handoff.
+  ///
+  /// Returns the container that had focus, unless it no longer exists.
+  pub fn adopt_container_tree(
+    &self,
+    native_monitors: Vec<NativeMonitor>,
+    state: &mut WmState,
+    config: &UserConfig,
+  ) -> anyhow::Result<Option<Container>> {
+    let mut focused_container = None;
+
+    for (native_monitor, monitor_dto) in
+      native_monitors.into_iter().zip(&self.monitors)
+    {
+      let ContainerDto::Monitor(monitor_dto) = monitor_dto else {
+        continue;
+      };
+
+      let monitor = add_monitor(native_monitor, state)?;
+      let mut adopted = Vec::new();
+
+      for child in &monitor_dto.children {
+        if let ContainerDto::Workspace(workspace_dto) = child {
+          let workspace = adopt_workspace(
+            workspace_dto,
+            &monitor,
+            state,
+            config,
+            &mut focused_container,
+          )?;
+
+          adopted.push((workspace_dto.id, workspace.into()));
+        }
+      }
+
+      restore_focus_order(
+        &monitor.into(),
+        &monitor_dto.child_focus_order,
+        &adopted,
+      );
+    }
+
+    Ok(focused_container)
+  }
+
+  /// Recreates swallowed windows that still exist, keyed by the ID of
+  /// the window that took their place.
+  pub fn adopt_swallowed_windows(
+    &self,
+    state: &mut WmState,
+    config: &UserConfig,
+  ) {
+    for swallowed in &self.swallowed_windows {
+      let native = NativeWindow::new(swallowed.handle);
+
+      if !native.is_valid()
+        || state
+          .container_by_id(swallowed.swallowing_window_id)
+          .is_none()
+      {
+        continue;
+      }
+
+      state.query_size_constraints(&native, swallowed.id);
+
+      let window = TilingWindow::new(
+        Some(swallowed.id),
+        native,
+        None,
+        swallowed.border_delta.clone(),
+        swallowed.floating_placement.clone(),
+        false,
+        config.value.gaps.clone(),
+        Vec::new(),
+        None,
+        None,
+        None,
+        SizeConstraints::default(),
+        swallowed.effect_overrides.clone(),
+      );
+
+      window.set_display_state(DisplayState::Hidden);
+
+      state
+        .swallowed_windows
+        .insert(swallowed.swallowing_window_id, window.into());
+    }
+  }
+}
+
+/// Handoff files are written to the temp directory, since they're only
+/// needed for the duration of a restart.
+pub fn handoff_path() -> PathBuf {
+  env::temp_dir().join("lavawm-handoff.json")
+}
+
+fn adopt_workspace(
+  workspace_dto: &WorkspaceDto,
+  monitor: &Monitor,
+  state: &mut WmState,
+  config: &UserConfig,
+  focused_container: &mut Option<Container>,
+) -> anyhow::Result<Workspace> {
+  // The workspace config might have been removed from the user config
+  // since the handoff was written, in which case the workspace is kept
+  // as if it was created at runtime.
+  let workspace_config = if let Some(workspace_config) =
+    state.workspace_config(&workspace_dto.name)
+  {
+    workspace_config.clone()
+  } else {
+    let workspace_config =
+      RuntimeWorkspaceConfig::new(&workspace_dto.name);
+    state.workspace_configs.push(workspace_config.clone());
+    workspace_config.value
+  };
+
+  let workspace = Workspace::new(
+    workspace_config.clone(),
+    config.gaps_for_workspace(&workspace_config),
+    workspace_dto.tiling_direction.clone(),
+  );
+
+  workspace.set_is_monocle(workspace_dto.is_monocle);
+
+  attach_container(
+    &workspace.clone().into(),
+    &monitor.clone().into(),
+    None,
+  )?;
+
+  if workspace_dto.has_focus {
+    *focused_container = Some(workspace.clone().into());
+  }
+
+  adopt_children(
+    &workspace_dto.children,
+    &workspace_dto.child_focus_order,
+    &workspace.clone().into(),
+    &workspace.gaps_config(),
+    focused_container,
+  )?;
+
+  Ok(workspace)
+}
+
+/// Recreates the given children and attaches them to the parent.
+fn adopt_children(
+  children: &[ContainerDto],
+  child_focus_order: &[Uuid],
+  parent: &Container,
+  gaps_config: &GapsConfig,
+  focused_container: &mut Option<Container>,
+) -> anyhow::Result<()> {
+  let mut adopted = Vec::new();
+
+  for child in children {
+    if let Some((container, tiling_size)) =
+      adopt_container(child, gaps_config, focused_container)?
+    {
+      attach_container(&container, parent, None)?;
+      adopted.push((child.id(), container, tiling_size));
+    }
+  }
+
+  // Tiling sizes are restored once all children are attached, since
+  // attaching a container resizes its siblings. Sizes are scaled in case
+  // windows were closed during the restart.
+  let total_tiling_size = adopted
+    .iter()
+    .filter_map(|(_, _, tiling_size)| *tiling_size)
+    .sum::<f32>();
+
+  for (_, container, tiling_size) in &adopted {
+    if let (Ok(container), Some(tiling_size)) =
+      (container.as_tiling_container(), tiling_size)
+    {
+      container.set_tiling_size(tiling_size / total_tiling_size);
+    }
+  }
+
+  let adopted = adopted
+    .into_iter()
+    .map(|(id, container, _)| (id, container))
+    .collect::<Vec<_>>();
+
+  restore_focus_order(parent, child_focus_order, &adopted);
+
+  Ok(())
+}
+
+/// Recreates a split container or window, along with its tiling size.
+///
+/// Returns `None` if the container no longer has any windows. Split
+/// containers that are left with a single child are replaced by the
+/// child.
+fn adopt_container(
+  container_dto: &ContainerDto,
+  gaps_config: &GapsConfig,
+  focused_container: &mut Option<Container>,
+) -> anyhow::Result<Option<(Container, Option<f32>)>> {
+  match container_dto {
+    ContainerDto::Split(split_dto) => {
+      adopt_split(split_dto, gaps_config, focused_container)
+    }
+    ContainerDto::Window(window_dto) => {
+      let window = adopt_window(window_dto, gaps_config);
+
+      if window_dto.has_focus {
+        *focused_container = window.clone().map(Into::into);
+      }
+
+      Ok(window.map(|window| (window.into(), window_dto.tiling_size)))
+    }
+    _ => Ok(None),
+  }
+}
+
+fn adopt_split(
+  split_dto: &SplitContainerDto,
+  gaps_config: &GapsConfig,
+  focused_container: &mut Option<Container>,
+) -> anyhow::Result<Option<(Container, Option<f32>)>> {
+  let children = split_dto
+    .children
+    .iter()
+    .filter(|child| has_windows(child))
+    .collect::<Vec<_>>();
+
+  match children.as_slice() {
+    [] => Ok(None),
+    [child] => Ok(
+      adopt_container(child, gaps_config, focused_container)?
+        .map(|(container, _)| (container, Some(split_dto.tiling_size))),
+    ),
+    _ => {
+      let split = SplitContainer::new(
+        split_dto.tiling_direction.clone(),
+        gaps_config.clone(),
+      );
+
+      adopt_children(
+        &split_dto.children,
+        &split_dto.child_focus_order,
+        &split.clone().into(),
+        gaps_config,
+        focused_container,
+      )?;
+
+      Ok(Some((split.into(), Some(split_dto.tiling_size))))
+    }
+  }
+}
+
+/// Recreates a window with its previous ID, unless it no longer exists.
+fn adopt_window(
+  window_dto: &WindowDto,
+  gaps_config: &GapsConfig,
+) -> Option<WindowContainer> {
+  let native = NativeWindow::new(window_dto.handle);
+
+  if !native.is_valid() {
+    return None;
+  }
+
+  let urgent_since = window_dto.is_urgent.then(Instant::now);
+
+  let window: WindowContainer = match window_dto.state {
+    WindowState::Tiling => TilingWindow::new(
+      Some(window_dto.id),
+      native,
+      window_dto.prev_state.clone(),
+      window_dto.border_delta.clone(),
+      window_dto.floating_placement.clone(),
+      false,
+      gaps_config.clone(),
+      Vec::new(),
+      None,
+      urgent_since,
+      window_dto.sticky.clone(),
+      window_dto.size_constraints.clone(),
+      window_dto.effect_overrides.clone(),
+    )
+    .into(),
+    _ => NonTilingWindow::new(
+      Some(window_dto.id),
+      native,
+      window_dto.state.clone(),
+      window_dto.prev_state.clone(),
+      window_dto.border_delta.clone(),
+      None,
+      window_dto.floating_placement.clone(),
+      false,
+      Vec::new(),
+      None,
+      urgent_since,
+      window_dto.sticky.clone(),
+      window_dto.size_constraints.clone(),
+      window_dto.effect_overrides.clone(),
+    )
+    .into(),
+  };
+
+  window.set_display_state(window_dto.display_state.clone());
+
+  Some(window)
+}
+
+/// Adds the handles of the container's windows and those of its
+/// descendants.
+fn push_window_handles(
+  container_dto: &ContainerDto,
+  handles: &mut Vec<isize>,
+) {
+  let children = match container_dto {
+    ContainerDto::Root(root_dto) => &root_dto.children,
+    ContainerDto::Monitor(monitor_dto) => &monitor_dto.children,
+    ContainerDto::Workspace(workspace_dto) => &workspace_dto.children,
+    ContainerDto::Split(split_dto) => &split_dto.children,
+    ContainerDto::Window(window_dto) => {
+      handles.push(window_dto.handle);
+      return;
+    }
+  };
+
+  for child in children {
+    push_window_handles(child, handles);
+  }
+}
+
+/// Whether the container is a window that still exists, or has one as a
+/// descendant.
+fn has_windows(container_dto: &ContainerDto) -> bool {
+  match container_dto {
+    ContainerDto::Split(split_dto) => {
+      split_dto.children.iter().any(has_windows)
+    }
+    ContainerDto::Window(window_dto) => {
+      NativeWindow::new(window_dto.handle).is_valid()
+    }
+    _ => false,
+  }
+}
+
+/// Sets the focus order of the parent's children from the focus order of
+/// the handoff, which refers to the children by their previous IDs.
+fn restore_focus_order(
+  parent: &Container,
+  child_focus_order: &[Uuid],
+  adopted: &[(Uuid, Container)],
+) {
+  let mut focus_order = child_focus_order
+    .iter()
+    .filter_map(|prev_id| {
+      adopted
+        .iter()
+        .find(|(id, _)| id == prev_id)
+        .map(|(_, container)| container.id())
+    })
+    .collect::<Vec<_>>();
+
+  // Children that are missing from the focus order are added last.
+  for (_, container) in adopted {
+    if !focus_order.contains(&container.id()) {
+      focus_order.push(container.id());
+    }
+  }
+
+  *parent.borrow_child_focus_order_mut() = focus_order.into();
+}
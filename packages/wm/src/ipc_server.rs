@@ -1,11 +1,36 @@
-use std::{iter, net::SocketAddr};
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  iter,
+  net::SocketAddr,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+  },
+};
 
 use anyhow::{bail, Context};
 use clap::Parser;
 use futures_util::{SinkExt, StreamExt};
 use tokio::{
   net::{TcpListener, TcpStream},
-  sync::{broadcast, mpsc},
+  sync::{broadcast, broadcast::error::RecvError, mpsc},
   task,
 };
 use tokio_tungstenite::{accept_async, tungstenite::Message};
@@ -13,10 +38,11 @@ use tracing::{info, warn};
 use uuid::Uuid;
 use wm_common::{
   AppCommand, AppMetadataData, BindingModesData, ClientResponseData,
-  ClientResponseMessage, CommandData, EventSubscribeData,
-  EventSubscriptionMessage, FocusedData, MonitorsData, QueryCommand,
-  ServerMessage, SubscribableEvent, TilingDirectionData, WindowsData,
-  WmEvent, WorkspacesData, DEFAULT_IPC_PORT,
+  ClientResponseMessage, CommandData, CommandsData, ConfigSchemaData,
+  EventSubscribeData, EventSubscriptionMessage, FocusHistoryData,
+  FocusedData, MetricsData, MetricsDto, MonitorsData, ParsedConfig,
+  QueryCommand, ServerMessage, SubscribableEvent, TilingDirectionData,
+  WindowsData, WmEvent, WorkspacesData, DEFAULT_IPC_PORT,
 };
 
 use crate::{
@@ -36,6 +62,10 @@ pub struct IpcServer {
   event_tx: broadcast::Sender<(SubscribableEvent, WmEvent)>,
   _unsubscribe_rx: broadcast::Receiver<Uuid>,
   unsubscribe_tx: broadcast::Sender<Uuid>,
+
+  /// Number of WM events that were dropped for event subscriptions that
+  /// couldn't keep up.
+  lagged_events: Arc<AtomicU64>,
 }
 
 impl IpcServer {
@@ -71,6 +101,7 @@ impl IpcServer {
       unsubscribe_tx,
       #[allow(clippy::used_underscore_binding)]
       _unsubscribe_rx,
+      lagged_events: Arc::new(AtomicU64::new(0)),
     })
   }
 
@@ -139,6 +170,8 @@ impl IpcServer {
     wm: &mut WindowManager,
     config: &mut UserConfig,
   ) -> anyhow::Result<()> {
+    wm.state.metrics.record_ipc_message(self.message_rx.len());
+
     let app_command = AppCommand::try_parse_from(
       iter::once("").chain(message.split_whitespace()),
     );
@@ -176,7 +209,7 @@ impl IpcServer {
     config: &mut UserConfig,
   ) -> anyhow::Result<ClientResponseData> {
     let response_data = match app_command {
-      AppCommand::Query { command } => match command {
+      AppCommand::Query { command, .. } => match command {
         QueryCommand::Windows => {
           ClientResponseData::Windows(WindowsData {
             windows: wm
@@ -187,6 +220,16 @@ impl IpcServer {
               .try_collect()?,
           })
         }
+        QueryCommand::FocusHistory => {
+          ClientResponseData::FocusHistory(FocusHistoryData {
+            focus_history: wm
+              .state
+              .recent_windows()
+              .into_iter()
+              .map(|window| window.to_dto())
+              .try_collect()?,
+          })
+        }
         QueryCommand::Workspaces => {
           ClientResponseData::Workspaces(WorkspacesData {
             workspaces: wm
@@ -242,10 +285,26 @@ impl IpcServer {
         QueryCommand::Paused => {
           ClientResponseData::Paused(wm.state.is_paused)
         }
+        QueryCommand::Commands => {
+          ClientResponseData::Commands(CommandsData {
+            commands: AppCommand::schema().subcommands,
+          })
+        }
+        QueryCommand::ConfigSchema => {
+          ClientResponseData::ConfigSchema(ConfigSchemaData {
+            config_schema: ParsedConfig::json_schema(),
+          })
+        }
+        QueryCommand::Metrics => {
+          ClientResponseData::Metrics(MetricsData {
+            metrics: self.metrics(wm),
+          })
+        }
       },
       AppCommand::Command {
         subject_container_id,
         command,
+        ..
       } => {
         let subject_container_id = wm.process_commands(
           &vec![command],
@@ -257,7 +316,7 @@ impl IpcServer {
           subject_container_id,
         })
       }
-      AppCommand::Sub { events } => {
+      AppCommand::Sub { events, .. } => {
         let subscription_id = Uuid::new_v4();
         info!("New event subscription {}: {:?}", subscription_id, events);
 
@@ -265,6 +324,7 @@ impl IpcServer {
         let mut event_rx = self.event_tx.subscribe();
         let mut unsubscribe_rx = self.unsubscribe_tx.subscribe();
         let mut disconnection_rx = disconnection_tx.subscribe();
+        let lagged_events = self.lagged_events.clone();
 
         task::spawn(async move {
           loop {
@@ -277,7 +337,21 @@ impl IpcServer {
                   break;
                 }
               }
-              Ok((event_type, event)) = event_rx.recv() => {
+              res = event_rx.recv() => {
+                let (event_type, event) = match res {
+                  Ok(event) => event,
+                  Err(RecvError::Lagged(count)) => {
+                    warn!(
+                      "Event subscription {} skipped {} events.",
+                      subscription_id, count
+                    );
+
+                    lagged_events.fetch_add(count, Ordering::Relaxed);
+                    continue;
+                  }
+                  Err(RecvError::Closed) => break,
+                };
+
                 // Check whether the event is one of the subscribed events.
                 if events.contains(&event_type)
                   || events.contains(&SubscribableEvent::All)
@@ -302,7 +376,9 @@ impl IpcServer {
           subscription_id,
         })
       }
-      AppCommand::Unsub { subscription_id } => {
+      AppCommand::Unsub {
+        subscription_id, ..
+      } => {
         self
           .unsubscribe_tx
           .send(subscription_id)
@@ -310,7 +386,12 @@ impl IpcServer {
 
         ClientResponseData::EventUnsubscribe
       }
-      AppCommand::Start { .. } => bail!("Unsupported IPC command."),
+      AppCommand::Start { .. }
+      | AppCommand::Replay { .. }
+      | AppCommand::Completions { .. }
+      | AppCommand::Watch { .. } => {
+        bail!("Unsupported IPC command.")
+      }
     };
 
     Ok(response_data)
@@ -373,6 +454,9 @@ impl IpcServer {
       WmEvent::WindowUnmanaged { .. } => {
         SubscribableEvent::WindowUnmanaged
       }
+      WmEvent::WindowUrgencyChanged { .. } => {
+        SubscribableEvent::WindowUrgencyChanged
+      }
       WmEvent::WorkspaceActivated { .. } => {
         SubscribableEvent::WorkspaceActivated
       }
@@ -393,6 +477,15 @@ impl IpcServer {
     Ok(())
   }
 
+  /// Gets the metrics of the event loop, including the metrics tracked
+  /// by the IPC server.
+  pub fn metrics(&self, wm: &WindowManager) -> MetricsDto {
+    wm.state.metrics.to_dto(
+      self.message_rx.len(),
+      self.lagged_events.load(Ordering::Relaxed),
+    )
+  }
+
   pub fn stop(&self) {
     info!("Shutting down IPC server.");
     self.abort_handle.abort();
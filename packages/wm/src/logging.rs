@@ -0,0 +1,211 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  collections::VecDeque,
+  io,
+  path::PathBuf,
+  sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use anyhow::Context;
+use tracing::info;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::{
+  filter::{EnvFilter, LevelFilter},
+  fmt::{self, format::FmtSpan, writer::MakeWriterExt},
+  layer::SubscriberExt,
+  reload, Layer, Registry,
+};
+use wm_common::{LoggingConfig, Verbosity};
+
+/// Maximum number of lines kept in the recent logs.
+const RECENT_LOGS_CAPACITY: usize = 2000;
+
+/// Most recent lines written to the log files, which are included in
+/// diagnostics dumps.
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Log output to stdout and to rotating log files.
+///
+/// The level of stdout is set by the verbosity flags, whereas the level of
+/// the log files is set by the `general.logging` section of the user
+/// config.
+pub struct Logging {
+  filter_handle: reload::Handle<EnvFilter, Registry>,
+  log_file: Arc<Mutex<RollingFileAppender>>,
+  max_files: usize,
+}
+
+impl Logging {
+  /// Initializes logging with the default logging config, until the user
+  /// config has been read.
+  ///
+  /// Log files are saved to `~/.glzr/lavawm/logs/`.
+  pub fn init(verbosity: &Verbosity) -> anyhow::Result<Self> {
+    let config = LoggingConfig::default();
+    let log_file = Arc::new(Mutex::new(log_file_appender(&config)?));
+    let (filter, filter_handle) = reload::Layer::new(filter(&config)?);
+
+    let log_file_writer = {
+      let log_file = log_file.clone();
+      move || LogFileWriter(log_file.clone())
+    };
+
+    let subscriber = tracing_subscriber::registry()
+      .with(
+        // Output to log files and recent logs, with the duration of spans
+        // when they're closed.
+        fmt::Layer::new()
+          .with_ansi(false)
+          .with_span_events(FmtSpan::CLOSE)
+          .with_writer(log_file_writer)
+          .and_then(
+            fmt::Layer::new()
+              .with_ansi(false)
+              .with_span_events(FmtSpan::CLOSE)
+              .with_writer(|| RecentLogsWriter),
+          )
+          .with_filter(filter),
+      )
+      .with(
+        // Output to stdout with specified verbosity level.
+        fmt::Layer::new()
+          .with_writer(std::io::stdout.with_max_level(verbosity.level())),
+      );
+
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    info!(
+      "Starting WM with log level {:?}.",
+      verbosity.level().to_string()
+    );
+
+    Ok(Self {
+      filter_handle,
+      log_file,
+      max_files: config.max_files,
+    })
+  }
+
+  /// Updates the levels and the number of kept log files to match the
+  /// given logging config.
+  pub fn update(&mut self, config: &LoggingConfig) -> anyhow::Result<()> {
+    self.filter_handle.reload(filter(config)?)?;
+
+    if config.max_files != self.max_files {
+      *self.log_file.lock().unwrap_or_else(PoisonError::into_inner) =
+        log_file_appender(config)?;
+
+      self.max_files = config.max_files;
+    }
+
+    Ok(())
+  }
+}
+
+/// Gets the most recently logged lines, from oldest to newest.
+pub fn recent_logs() -> Vec<String> {
+  recent_logs_guard().iter().cloned().collect()
+}
+
+fn recent_logs_guard() -> MutexGuard<'static, VecDeque<String>> {
+  RECENT_LOGS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Writer to the current log file appender, which is replaced when the
+/// number of kept log files changes.
+struct LogFileWriter(Arc<Mutex<RollingFileAppender>>);
+
+impl io::Write for LogFileWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self
+      .0
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+      .write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self
+      .0
+      .lock()
+      .unwrap_or_else(PoisonError::into_inner)
+      .flush()
+  }
+}
+
+/// Writer that appends formatted log lines to the recent logs.
+struct RecentLogsWriter;
+
+impl io::Write for RecentLogsWriter {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let mut logs = recent_logs_guard();
+
+    if logs.len() >= RECENT_LOGS_CAPACITY {
+      logs.pop_front();
+    }
+
+    logs.push_back(String::from_utf8_lossy(buf).trim_end().to_string());
+
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Creates a filter for the log files from the given logging config.
+fn filter(config: &LoggingConfig) -> anyhow::Result<EnvFilter> {
+  let directives = config
+    .modules
+    .iter()
+    .map(|(module, level)| format!("{module}={}", level.level()))
+    .collect::<Vec<_>>()
+    .join(",");
+
+  EnvFilter::builder()
+    .with_default_directive(
+      LevelFilter::from_level(config.level.level()).into(),
+    )
+    .parse(directives)
+    .context("Invalid module in logging config.")
+}
+
+/// Creates an appender for log files that are rotated daily.
+fn log_file_appender(
+  config: &LoggingConfig,
+) -> anyhow::Result<RollingFileAppender> {
+  RollingFileAppender::builder()
+    .rotation(Rotation::DAILY)
+    .filename_prefix("lavawm")
+    .filename_suffix("log")
+    .max_log_files(config.max_files.max(1))
+    .build(log_dir()?)
+    .context("Failed to create log file.")
+}
+
+/// Gets the directory that log files are saved to.
+fn log_dir() -> anyhow::Result<PathBuf> {
+  Ok(
+    home::home_dir()
+      .context("Unable to get home directory.")?
+      .join(".glzr/lavawm/logs/"),
+  )
+}
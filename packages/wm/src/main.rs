@@ -27,49 +27,76 @@
 #![warn(clippy::all, clippy::pedantic)]
 #![feature(iterator_try_collect)]
 
-use std::{env, path::PathBuf};
-
-use anyhow::{Context, Error};
-use tokio::{process::Command, signal};
-use tracing::{debug, error, info, warn, Level};
-use tracing_subscriber::{
-  fmt::{self, writer::MakeWriterExt},
-  layer::SubscriberExt,
+use std::{
+  env,
+  path::{Path, PathBuf},
+  process::ExitCode,
+  time::{Duration, Instant},
 };
-use wm_common::{AppCommand, InvokeCommand, Verbosity, WmEvent};
-use wm_platform::Platform;
+
+use anyhow::Context;
+use tokio::{process::Command, signal, time};
+use tracing::{debug, error, info, warn};
+use wm_common::{
+  AppCommand, InvokeCommand, Verbosity, WmEvent, SUPERVISED_ENV_VAR,
+};
+use wm_platform::{NativeWindow, Platform, SingleInstance};
 
 use crate::{
-  ipc_server::IpcServer, sys_tray::SystemTray, user_config::UserConfig,
+  commands::general::read_layout_snapshot,
+  handoff::{handoff_path, Handoff},
+  ipc_server::IpcServer,
+  logging::Logging,
+  metrics_server::MetricsServer,
+  recording::Recorder,
+  sys_tray::SystemTray,
+  user_config::UserConfig,
   wm::WindowManager,
 };
 
 mod commands;
+mod config_resolver;
 mod events;
+mod focus_history;
+mod handoff;
 mod ipc_server;
+mod logging;
+mod metrics;
+mod metrics_server;
 mod models;
 mod pending_sync;
+mod recording;
+mod remembered_placements;
+mod runtime_workspaces;
 mod sys_tray;
 mod traits;
 mod user_config;
 mod wm;
 mod wm_state;
 
+/// How long a restarted instance waits for the previous instance to exit.
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Main entry point for the application.
 ///
 /// Conditionally starts the WM or runs a CLI command based on the given
 /// subcommand.
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
   let args = std::env::args().collect::<Vec<_>>();
   let app_command = AppCommand::parse_with_default(&args);
 
   match app_command {
     AppCommand::Start {
       config_path,
+      record,
+      restore_layout,
+      handoff,
       verbosity,
     } => {
-      let res = start_wm(config_path, verbosity).await;
+      let res =
+        start_wm(config_path, record, restore_layout, handoff, verbosity)
+          .await;
 
       // If unable to start the WM, the error is fatal and a message dialog
       // is shown.
@@ -78,40 +105,103 @@ async fn main() -> anyhow::Result<()> {
         Platform::show_error_dialog("Fatal error", &err.to_string());
       }
 
-      res
+      res.map(|()| ExitCode::SUCCESS)
     }
-    _ => wm_cli::start(args).await,
+    AppCommand::Replay {
+      path,
+      config_path,
+      realtime,
+      verbosity,
+    } => {
+      // The replay summary is printed to stdout, which isn't visible in
+      // release builds unless attached to the console of the caller.
+      Platform::attach_parent_console();
+      recording::replay(&path, config_path, realtime, &verbosity).await
+    }
+    _ => Ok(wm_cli::start(args).await),
   }
 }
 
+#[allow(clippy::too_many_lines)]
 async fn start_wm(
   config_path: Option<PathBuf>,
+  record_path: Option<PathBuf>,
+  restore_layout_path: Option<PathBuf>,
+  handoff_path: Option<PathBuf>,
   verbosity: Verbosity,
 ) -> anyhow::Result<()> {
-  setup_logging(&verbosity)?;
+  let mut logging = Logging::init(&verbosity)?;
 
-  // Ensure that only one instance of the WM is running.
-  let _single_instance = Platform::new_single_instance()?;
+  // Ensure that only one instance of the WM is running. When restarting,
+  // the previous instance might still be shutting down.
+  let _single_instance = match &handoff_path {
+    Some(path) => wait_for_single_instance()
+      .await
+      .inspect_err(|_| restore_handoff_windows(path))?,
+    None => Platform::new_single_instance()?,
+  };
 
   // Parse and validate user config.
-  let mut config = UserConfig::new(config_path)?;
+  let mut config = UserConfig::new(config_path).inspect_err(|_| {
+    if let Some(path) = &handoff_path {
+      restore_handoff_windows(path);
+    }
+  })?;
+  logging.update(&config.value.general.logging)?;
 
   // Start watcher process for restoring hidden windows on crash.
-  start_watcher_process()?;
+  start_watcher_process(&config)?;
 
   // Add application icon to system tray.
   let mut tray = SystemTray::new(&config.path)?;
 
-  let mut wm = WindowManager::new(&mut config)?;
+  // Start recording before populating the initial state, so that it can
+  // be replayed.
+  let mut recorder = record_path
+    .map(|path| Recorder::start(&path, &config))
+    .transpose()?;
+
+  // Adopt the state of the previous instance when restarted via
+  // `wm-restart`.
+  let handoff = handoff_path
+    .map(|path| Handoff::read(&path))
+    .transpose()
+    .unwrap_or_else(|err| {
+      warn!("Failed to read handoff: {:?}", err);
+      None
+    });
+
+  let mut wm = WindowManager::new(&mut config, handoff.as_ref())?;
+
+  // Restore the layout from before a crash when restarted by the watcher.
+  if let Some(path) = restore_layout_path {
+    if let Err(err) = read_layout_snapshot(&path)
+      .and_then(|monitors| wm.restore_layout(&monitors, &mut config))
+    {
+      warn!("Failed to restore layout: {:?}", err);
+    }
+  }
 
   let mut ipc_server = IpcServer::start().await?;
 
+  let mut metrics_server =
+    MetricsServer::start(&config.value.general.metrics_endpoint).await;
+
   // Start listening for platform events after populating initial state.
   let mut event_listener = Platform::start_event_listener(&config.value)?;
 
-  // Run startup commands.
-  let startup_commands = config.value.general.startup_commands.clone();
-  wm.process_commands(&startup_commands, None, &mut config)?;
+  // Startup commands were already run by the previous instance, which
+  // might also have had binding modes enabled or been paused.
+  if handoff.is_some() {
+    event_listener.update(
+      &config.value,
+      &wm.state.binding_modes,
+      wm.state.is_paused,
+    );
+  } else {
+    let startup_commands = config.value.general.startup_commands.clone();
+    wm.process_commands(&startup_commands, None, &mut config)?;
+  }
 
   loop {
     let res = tokio::select! {
@@ -129,6 +219,11 @@ async fn start_wm(
       },
       Some(event) = event_listener.event_rx.recv() => {
         debug!("Received platform event: {:?}", event);
+
+        if let Some(recorder) = &mut recorder {
+          recorder.record_platform_event(&event);
+        }
+
         wm.process_event(event, &mut config)
       },
       Some((
@@ -138,6 +233,10 @@ async fn start_wm(
       )) = ipc_server.message_rx.recv() => {
         info!("Received IPC message: {:?}", message);
 
+        if let Some(recorder) = &mut recorder {
+          recorder.record_ipc_message(&message);
+        }
+
         if let Err(err) = ipc_server.process_message(
           message,
           &response_tx,
@@ -153,6 +252,10 @@ async fn start_wm(
       Some(wm_event) = wm.event_rx.recv() => {
         debug!("Received WM event: {:?}", wm_event);
 
+        if let Some(recorder) = &mut recorder {
+          recorder.record_wm_event(&wm_event);
+        }
+
         // Update event listener when keyboard or mouse listener needs to
         // be changed.
         if matches!(
@@ -168,12 +271,34 @@ async fn start_wm(
           );
         }
 
+        // Update log levels when the user config is reloaded.
+        if matches!(wm_event, WmEvent::UserConfigChanged { .. }) {
+          if let Err(err) = logging.update(&config.value.general.logging) {
+            error!("{:?}", err);
+          }
+        }
+
         if let Err(err) = ipc_server.process_event(wm_event) {
           error!("{:?}", err);
         }
 
         Ok(())
       },
+      Some((
+        window_id,
+        size_constraints
+      )) = wm.size_constraints_rx.recv() => {
+        wm.process_size_constraints(window_id, &size_constraints, &config)
+      },
+      Some(response_tx) = metrics_server.request_rx.recv() => {
+        let metrics = ipc_server.metrics(&wm).to_open_metrics();
+
+        if response_tx.send(metrics).is_err() {
+          warn!("Metrics request was cancelled.");
+        }
+
+        Ok(())
+      },
       Some(()) = tray.config_reload_rx.recv() => {
         wm.process_commands(
           &vec![InvokeCommand::WmReloadConfig],
@@ -183,63 +308,153 @@ async fn start_wm(
       },
     };
 
+    // Cycles through recent windows can also be started via IPC, in which
+    // case the keyboard hook can't know that a cycle is in progress.
+    if wm.state.focus_history.is_cycling() {
+      event_listener.arm_modifier_release();
+    }
+
     if let Err(err) = res {
       error!("{:?}", err);
       Platform::show_error_dialog("Non-fatal error", &err.to_string());
     }
   }
 
+  if let Some(recorder) = recorder {
+    if let Err(err) = recorder.finish(&wm.state) {
+      error!("Failed to finish recording: {:?}", err);
+    }
+  }
+
+  if wm.state.is_restarting {
+    match spawn_next_instance(&wm, &config) {
+      Ok(()) => {
+        // Events aren't emitted while paused, and the watcher needs the
+        // exiting event to not restore the windows.
+        wm.state.is_paused = false;
+        emit_exiting(&mut wm, &mut ipc_server);
+        return Ok(());
+      }
+      Err(err) => {
+        error!("Failed to restart: {:?}", err);
+        wm.state.is_restarting = false;
+      }
+    }
+  }
+
   run_cleanup(&mut wm, &mut config, &mut ipc_server)
 }
 
-/// Initialize logging with the specified verbosity level.
+/// Waits for a previous instance to exit, and then ensures that only one
+/// instance of the WM is running.
+async fn wait_for_single_instance() -> anyhow::Result<SingleInstance> {
+  let started_at = Instant::now();
+
+  loop {
+    match Platform::new_single_instance() {
+      Ok(single_instance) => return Ok(single_instance),
+      Err(err) if started_at.elapsed() >= HANDOFF_TIMEOUT => {
+        return Err(err)
+      }
+      Err(_) => time::sleep(Duration::from_millis(50)).await,
+    }
+  }
+}
+
+/// Shows the windows of the previous instance when unable to start with
+/// its handoff. The previous instance hands over its windows without
+/// restoring them, so they would otherwise stay hidden.
+fn restore_handoff_windows(path: &Path) {
+  match Handoff::read(path) {
+    Ok(handoff) => {
+      for handle in handoff.window_handles() {
+        NativeWindow::new(handle).cleanup();
+      }
+    }
+    Err(err) => warn!("Failed to read handoff: {:?}", err),
+  }
+}
+
+/// Hands over the WM state to a new instance of the WM binary, which
+/// adopts the windows of this instance once it has exited.
 ///
-/// Error logs are saved to `~/.glzr/lavawm/errors.log`.
-fn setup_logging(verbosity: &Verbosity) -> anyhow::Result<()> {
-  let error_log_dir = home::home_dir()
-    .context("Unable to get home directory.")?
-    .join(".glzr/lavawm/");
-
-  let error_writer =
-    tracing_appender::rolling::never(error_log_dir, "errors.log");
-
-  let subscriber = tracing_subscriber::registry()
-    .with(
-      // Output to stdout with specified verbosity level.
-      fmt::Layer::new()
-        .with_writer(std::io::stdout.with_max_level(verbosity.level())),
-    )
-    .with(
-      // Output to error log file.
-      fmt::Layer::new()
-        .with_writer(error_writer.with_max_level(Level::ERROR)),
-    );
+/// The binary is resolved again, so that an upgraded binary at the same
+/// path gets started.
+fn spawn_next_instance(
+  wm: &WindowManager,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  let path = handoff_path();
+  Handoff::new(&wm.state)?.write(&path)?;
 
-  tracing::subscriber::set_global_default(subscriber)?;
+  info!("Restarting with handoff: {}", path.display());
 
-  info!(
-    "Starting WM with log level {:?}.",
-    verbosity.level().to_string()
-  );
+  Command::new(env::current_exe()?)
+    .arg("start")
+    .arg("--config")
+    .arg(&config.path)
+    .arg("--handoff")
+    .arg(&path)
+    .spawn()
+    .context("Failed to start new instance.")?;
 
   Ok(())
 }
 
 /// Launches watcher binary. This is a separate process that is responsible
-/// for restoring hidden windows in case the main WM process crashes.
+/// for restoring hidden windows in case the main WM process crashes, and
+/// optionally for restarting the WM.
 ///
 /// This assumes the watcher binary exists in the same directory as the WM
 /// binary.
-fn start_watcher_process() -> anyhow::Result<tokio::process::Child, Error>
-{
+fn start_watcher_process(config: &UserConfig) -> anyhow::Result<()> {
+  // A supervising watcher that restarts the WM keeps watching the new
+  // instance. The variable is removed so that it isn't inherited by
+  // processes launched from the WM.
+  if env::var_os(SUPERVISED_ENV_VAR).is_some() {
+    env::remove_var(SUPERVISED_ENV_VAR);
+    return Ok(());
+  }
+
   let watcher_path = env::current_exe()?
     .parent()
     .context("Failed to resolve path to the watcher process.")?
     .join("lavawm-watcher");
 
-  Command::new(&watcher_path)
+  let mut command = Command::new(&watcher_path);
+  command.arg("--config").arg(&config.path);
+
+  let watcher_config = &config.value.general.watcher;
+
+  if watcher_config.supervise {
+    command.arg("--supervise").args([
+      format!("--wm-pid={}", std::process::id()),
+      format!("--max-restarts={}", watcher_config.max_restarts),
+      format!(
+        "--restart-window-secs={}",
+        watcher_config.restart_window_secs
+      ),
+      format!("--restart-delay-ms={}", watcher_config.restart_delay_ms),
+      format!(
+        "--max-restart-delay-ms={}",
+        watcher_config.max_restart_delay_ms
+      ),
+      format!(
+        "--heartbeat-interval-ms={}",
+        watcher_config.heartbeat_interval_ms
+      ),
+      format!(
+        "--heartbeat-timeout-ms={}",
+        watcher_config.heartbeat_timeout_ms
+      ),
+    ]);
+  }
+
+  command
     .spawn()
-    .context("Failed to start watcher process.")
+    .context("Failed to start watcher process.")?;
+
+  Ok(())
 }
 
 /// Runs cleanup tasks when the WM is exiting.
@@ -256,6 +471,13 @@ fn run_cleanup(
   let shutdown_commands = config.value.general.shutdown_commands.clone();
   wm.process_commands(&shutdown_commands, None, config)?;
 
+  emit_exiting(wm, ipc_server);
+  Ok(())
+}
+
+/// Emits the `ApplicationExiting` event, along with any remaining WM
+/// events.
+fn emit_exiting(wm: &mut WindowManager, ipc_server: &mut IpcServer) {
   wm.state.emit_event(WmEvent::ApplicationExiting);
 
   // Emit remaining WM events before exiting.
@@ -266,6 +488,4 @@ fn run_cleanup(
       warn!("{:?}", err);
     }
   }
-
-  Ok(())
 }
@@ -0,0 +1,209 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  collections::BTreeMap,
+  time::{Duration, Instant},
+};
+
+use wm_common::{HistogramBucketDto, HistogramDto, MetricsDto};
+
+/// Upper bounds of the latency buckets in seconds.
+const LATENCY_BUCKETS: &[f64] = &[
+  0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0,
+  2.5,
+];
+
+/// Upper bounds of the buckets for the number of redrawn windows.
+const WINDOW_COUNT_BUCKETS: &[f64] =
+  &[0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0];
+
+/// Counters and latency histograms of the event loop, which are exposed
+/// via `query metrics` and the metrics endpoint.
+#[derive(Debug)]
+pub struct Metrics {
+  started_at: Instant,
+  platform_events: BTreeMap<&'static str, u64>,
+  process_event_seconds: BTreeMap<&'static str, Histogram>,
+  commands: u64,
+  run_commands_seconds: Histogram,
+  platform_syncs: u64,
+  platform_sync_seconds: Histogram,
+  redraw_containers_seconds: Histogram,
+  windows_redrawn: u64,
+  windows_redrawn_per_sync: Histogram,
+  ipc_messages: u64,
+  ipc_max_queue_depth: u64,
+}
+
+impl Metrics {
+  pub fn new() -> Self {
+    Self {
+      started_at: Instant::now(),
+      platform_events: BTreeMap::new(),
+      process_event_seconds: BTreeMap::new(),
+      commands: 0,
+      run_commands_seconds: Histogram::new(LATENCY_BUCKETS),
+      platform_syncs: 0,
+      platform_sync_seconds: Histogram::new(LATENCY_BUCKETS),
+      redraw_containers_seconds: Histogram::new(LATENCY_BUCKETS),
+      windows_redrawn: 0,
+      windows_redrawn_per_sync: Histogram::new(WINDOW_COUNT_BUCKETS),
+      ipc_messages: 0,
+      ipc_max_queue_depth: 0,
+    }
+  }
+
+  /// Records a processed platform event of the given type (e.g.
+  /// `window_focused`).
+  pub fn record_platform_event(
+    &mut self,
+    kind: &'static str,
+    duration: Duration,
+  ) {
+    *self.platform_events.entry(kind).or_default() += 1;
+
+    self
+      .process_event_seconds
+      .entry(kind)
+      .or_insert_with(|| Histogram::new(LATENCY_BUCKETS))
+      .observe(duration.as_secs_f64());
+  }
+
+  pub fn record_commands(&mut self, count: usize, duration: Duration) {
+    self.commands += count as u64;
+    self.run_commands_seconds.observe(duration.as_secs_f64());
+  }
+
+  pub fn record_platform_sync(
+    &mut self,
+    windows_redrawn: usize,
+    duration: Duration,
+  ) {
+    self.platform_syncs += 1;
+    self.platform_sync_seconds.observe(duration.as_secs_f64());
+    self.windows_redrawn += windows_redrawn as u64;
+
+    #[allow(clippy::cast_precision_loss)]
+    self
+      .windows_redrawn_per_sync
+      .observe(windows_redrawn as f64);
+  }
+
+  pub fn record_redraw_containers(&mut self, duration: Duration) {
+    self
+      .redraw_containers_seconds
+      .observe(duration.as_secs_f64());
+  }
+
+  /// Records a received IPC message, along with the number of messages
+  /// that are still waiting to be processed.
+  pub fn record_ipc_message(&mut self, queue_depth: usize) {
+    self.ipc_messages += 1;
+    self.ipc_max_queue_depth =
+      self.ipc_max_queue_depth.max(queue_depth as u64);
+  }
+
+  /// Gets the current metrics. Metrics of the IPC server are passed in,
+  /// since they're tracked by the server itself.
+  pub fn to_dto(
+    &self,
+    ipc_queue_depth: usize,
+    ipc_lagged_events: u64,
+  ) -> MetricsDto {
+    MetricsDto {
+      uptime_seconds: self.started_at.elapsed().as_secs(),
+      platform_events: self
+        .platform_events
+        .iter()
+        .map(|(kind, count)| ((*kind).to_string(), *count))
+        .collect(),
+      commands: self.commands,
+      platform_syncs: self.platform_syncs,
+      windows_redrawn: self.windows_redrawn,
+      ipc_messages: self.ipc_messages,
+      ipc_queue_depth: ipc_queue_depth as u64,
+      ipc_max_queue_depth: self.ipc_max_queue_depth,
+      ipc_lagged_events,
+      process_event_seconds: self
+        .process_event_seconds
+        .iter()
+        .map(|(kind, histogram)| ((*kind).to_string(), histogram.to_dto()))
+        .collect(),
+      run_commands_seconds: self.run_commands_seconds.to_dto(),
+      platform_sync_seconds: self.platform_sync_seconds.to_dto(),
+      redraw_containers_seconds: self.redraw_containers_seconds.to_dto(),
+      windows_redrawn_per_sync: self.windows_redrawn_per_sync.to_dto(),
+    }
+  }
+}
+
+/// Histogram with fixed bucket bounds.
+#[derive(Debug)]
+struct Histogram {
+  bounds: &'static [f64],
+  /// Number of observations per bucket. Observations above the highest
+  /// bound are only included in `count`.
+  bucket_counts: Vec<u64>,
+  count: u64,
+  sum: f64,
+}
+
+impl Histogram {
+  fn new(bounds: &'static [f64]) -> Self {
+    Self {
+      bounds,
+      bucket_counts: vec![0; bounds.len()],
+      count: 0,
+      sum: 0.0,
+    }
+  }
+
+  fn observe(&mut self, value: f64) {
+    if let Some(index) =
+      self.bounds.iter().position(|bound| value <= *bound)
+    {
+      self.bucket_counts[index] += 1;
+    }
+
+    self.count += 1;
+    self.sum += value;
+  }
+
+  fn to_dto(&self) -> HistogramDto {
+    let mut cumulative_count = 0;
+
+    HistogramDto {
+      buckets: self
+        .bounds
+        .iter()
+        .zip(&self.bucket_counts)
+        .map(|(bound, count)| {
+          cumulative_count += count;
+
+          HistogramBucketDto {
+            le: *bound,
+            count: cumulative_count,
+          }
+        })
+        .collect(),
+      count: self.count,
+      sum: self.sum,
+    }
+  }
+}
@@ -0,0 +1,161 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{bail, Context};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::{TcpListener, TcpStream},
+  sync::{mpsc, oneshot},
+  task,
+};
+use tracing::{info, warn};
+use wm_common::MetricsEndpointConfig;
+
+/// Maximum size of the head of a request.
+const MAX_REQUEST_SIZE: usize = 8192;
+
+/// HTTP server for metrics in the `OpenMetrics` text format.
+///
+/// Since metrics are part of the WM state, requests are forwarded to the
+/// main loop through `request_rx` and answered via the given sender.
+pub struct MetricsServer {
+  abort_handle: Option<task::AbortHandle>,
+  pub request_rx: mpsc::UnboundedReceiver<oneshot::Sender<String>>,
+  /// Keeps `request_rx` open while the server is disabled.
+  _request_tx: mpsc::UnboundedSender<oneshot::Sender<String>>,
+}
+
+impl MetricsServer {
+  /// Starts serving metrics at `/metrics` if the endpoint is enabled.
+  ///
+  /// If the port can't be bound (e.g. it's already in use), a warning is
+  /// logged and the endpoint stays disabled, since metrics aren't
+  /// essential to running the WM.
+  pub async fn start(config: &MetricsEndpointConfig) -> Self {
+    let (request_tx, request_rx) = mpsc::unbounded_channel();
+
+    let abort_handle = if config.enabled {
+      Self::serve(config.port, request_tx.clone())
+        .await
+        .inspect_err(|err| warn!("{:?}", err))
+        .ok()
+    } else {
+      None
+    };
+
+    Self {
+      abort_handle,
+      request_rx,
+      _request_tx: request_tx,
+    }
+  }
+
+  /// Binds to the given port and forwards metrics requests to
+  /// `request_tx` in a background task.
+  async fn serve(
+    port: u16,
+    request_tx: mpsc::UnboundedSender<oneshot::Sender<String>>,
+  ) -> anyhow::Result<task::AbortHandle> {
+    let server_addr = format!("127.0.0.1:{port}");
+    let server =
+      TcpListener::bind(&server_addr).await.with_context(|| {
+        format!("Failed to start metrics endpoint on '{server_addr}'.")
+      })?;
+
+    info!("Metrics endpoint started on: 'http://{server_addr}/metrics'.");
+
+    let task = task::spawn(async move {
+      while let Ok((stream, _)) = server.accept().await {
+        let request_tx = request_tx.clone();
+
+        task::spawn(async move {
+          if let Err(err) =
+            Self::handle_connection(stream, request_tx).await
+          {
+            warn!("Error handling metrics request: {}", err);
+          }
+        });
+      }
+    });
+
+    Ok(task.abort_handle())
+  }
+
+  async fn handle_connection(
+    mut stream: TcpStream,
+    request_tx: mpsc::UnboundedSender<oneshot::Sender<String>>,
+  ) -> anyhow::Result<()> {
+    let mut request = Vec::new();
+    let mut chunk = [0; 1024];
+
+    // Read until the end of the request head. The body is ignored.
+    while !request.windows(4).any(|bytes| bytes == b"\r\n\r\n") {
+      let len = stream.read(&mut chunk).await?;
+
+      if len == 0 || request.len() > MAX_REQUEST_SIZE {
+        bail!("Incomplete HTTP request.");
+      }
+
+      request.extend_from_slice(&chunk[..len]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line =
+      request.lines().next().unwrap_or_default().split(' ');
+    let method = request_line.next();
+    let path = request_line
+      .next()
+      .and_then(|target| target.split('?').next());
+
+    let response = if method == Some("GET") && path == Some("/metrics") {
+      let (response_tx, response_rx) = oneshot::channel();
+
+      request_tx
+        .send(response_tx)
+        .map_err(|_| anyhow::anyhow!("WM has stopped."))?;
+
+      http_response(
+        "200 OK",
+        "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        &response_rx.await?,
+      )
+    } else {
+      http_response("404 Not Found", "text/plain; charset=utf-8", "")
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+  }
+}
+
+impl Drop for MetricsServer {
+  fn drop(&mut self) {
+    if let Some(abort_handle) = &self.abort_handle {
+      abort_handle.abort();
+    }
+  }
+}
+
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+  format!(
+    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+    body.len()
+  )
+}
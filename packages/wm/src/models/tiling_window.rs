@@ -1,14 +1,34 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::{
   cell::{Ref, RefCell, RefMut},
   collections::VecDeque,
   rc::Rc,
+  time::Instant,
 };
 
 use anyhow::Context;
 use uuid::Uuid;
 use wm_common::{
   ActiveDrag, ContainerDto, DisplayState, GapsConfig, Rect, RectDelta,
-  TilingDirection, WindowDto, WindowRuleConfig, WindowState,
+  SizeConstraints, StickyScope, WindowDto, WindowEffectOverrides,
+  WindowRuleConfig, WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -21,8 +41,7 @@ use crate::{
     TilingContainer, WindowContainer,
   },
   traits::{
-    CommonGetters, PositionGetters, TilingDirectionGetters,
-    TilingSizeGetters, WindowGetters,
+    CommonGetters, PositionGetters, TilingSizeGetters, WindowGetters,
   },
 };
 
@@ -46,6 +65,10 @@ struct TilingWindowInner {
   gaps_config: GapsConfig,
   done_window_rules: Vec<WindowRuleConfig>,
   active_drag: Option<ActiveDrag>,
+  urgent_since: Option<Instant>,
+  sticky: Option<StickyScope>,
+  size_constraints: SizeConstraints,
+  effect_overrides: WindowEffectOverrides,
 }
 
 impl TilingWindow {
@@ -60,6 +83,10 @@ impl TilingWindow {
     gaps_config: GapsConfig,
     done_window_rules: Vec<WindowRuleConfig>,
     active_drag: Option<ActiveDrag>,
+    urgent_since: Option<Instant>,
+    sticky: Option<StickyScope>,
+    size_constraints: SizeConstraints,
+    effect_overrides: WindowEffectOverrides,
   ) -> Self {
     let window = TilingWindowInner {
       id: id.unwrap_or_else(Uuid::new_v4),
@@ -78,6 +105,10 @@ impl TilingWindow {
       gaps_config,
       done_window_rules,
       active_drag,
+      urgent_since,
+      sticky,
+      size_constraints,
+      effect_overrides,
     };
 
     Self(Rc::new(RefCell::new(window)))
@@ -99,6 +130,10 @@ impl TilingWindow {
       self.has_custom_floating_placement(),
       self.done_window_rules(),
       self.active_drag(),
+      self.urgent_since(),
+      self.sticky(),
+      self.size_constraints(),
+      self.effect_overrides(),
     )
   }
 
@@ -124,6 +159,10 @@ impl TilingWindow {
       class_name: self.native().class_name()?,
       process_name: self.native().process_name()?,
       active_drag: self.active_drag(),
+      is_urgent: self.is_urgent(),
+      sticky: self.sticky(),
+      size_constraints: self.size_constraints(),
+      effect_overrides: self.effect_overrides(),
     }))
   }
 }
@@ -1,3 +1,21 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::{
   cell::{Ref, RefCell, RefMut},
   collections::VecDeque,
@@ -7,17 +25,20 @@ use std::{
 use anyhow::Context;
 use uuid::Uuid;
 use wm_common::{
-  ContainerDto, GapsConfig, Rect, TilingDirection, WorkspaceConfig,
-  WorkspaceDto,
+  ContainerDto, GapsConfig, Rect, RectDelta, TilingDirection,
+  WorkspaceConfig, WorkspaceDto,
 };
 
 use crate::{
   impl_common_getters, impl_container_debug,
   impl_tiling_direction_getters,
   models::{
-    Container, DirectionContainer, TilingContainer, WindowContainer,
+    Container, DirectionContainer, TilingContainer, TilingWindow,
+    WindowContainer,
+  },
+  traits::{
+    CommonGetters, PositionGetters, TilingDirectionGetters, WindowGetters,
   },
-  traits::{CommonGetters, PositionGetters, TilingDirectionGetters},
 };
 
 #[derive(Clone)]
@@ -32,6 +53,8 @@ struct WorkspaceInner {
   config: WorkspaceConfig,
   gaps_config: GapsConfig,
   tiling_direction: TilingDirection,
+  is_monocle: bool,
+  monocle_window_id: Option<Uuid>,
 }
 
 impl Workspace {
@@ -48,6 +71,8 @@ impl Workspace {
       config,
       gaps_config,
       tiling_direction,
+      is_monocle: false,
+      monocle_window_id: None,
     };
 
     Self(Rc::new(RefCell::new(workspace)))
@@ -71,10 +96,91 @@ impl Workspace {
       .is_some_and(|workspace| workspace.id() == self.id())
   }
 
+  /// Whether the focused tiling window is zoomed to fill the workspace.
+  pub fn is_monocle(&self) -> bool {
+    self.0.borrow().is_monocle
+  }
+
+  pub fn set_is_monocle(&self, is_monocle: bool) {
+    self.0.borrow_mut().is_monocle = is_monocle;
+  }
+
+  /// Gets the tiling window that should be zoomed to fill the workspace.
+  /// This is the most recently focused tiling window if monocle is
+  /// enabled.
+  pub fn monocle_window(&self) -> Option<TilingWindow> {
+    if !self.is_monocle() {
+      return None;
+    }
+
+    self
+      .descendant_focus_order()
+      .find_map(|descendant| descendant.as_tiling_window().cloned())
+  }
+
+  /// ID of the window that was last zoomed to fill the workspace. Used
+  /// to detect when the zoomed window changes.
+  pub fn monocle_window_id(&self) -> Option<Uuid> {
+    self.0.borrow().monocle_window_id
+  }
+
+  pub fn set_monocle_window_id(&self, window_id: Option<Uuid>) {
+    self.0.borrow_mut().monocle_window_id = window_id;
+  }
+
+  /// Whether any window in the workspace is requesting attention.
+  pub fn has_urgent_windows(&self) -> bool {
+    self
+      .descendants()
+      .filter_map(|descendant| descendant.as_window_container().ok())
+      .any(|window| window.is_urgent())
+  }
+
+  /// Gaps config for the workspace. This is either the workspace's own
+  /// gaps override or the global gaps config.
+  pub fn gaps_config(&self) -> GapsConfig {
+    self.0.borrow().gaps_config.clone()
+  }
+
   pub fn set_gaps_config(&self, gaps_config: GapsConfig) {
     self.0.borrow_mut().gaps_config = gaps_config;
   }
 
+  /// Outer gaps of the workspace, which differ if there is only a single
+  /// tiling window in the workspace.
+  pub fn outer_gaps(&self) -> RectDelta {
+    let gaps_config = &self.0.borrow().gaps_config;
+    let is_single_window = self.tiling_children().nth(1).is_none();
+
+    if is_single_window {
+      gaps_config
+        .single_window_outer_gap
+        .clone()
+        .unwrap_or_else(|| gaps_config.outer_gap.clone())
+    } else {
+      gaps_config.outer_gap.clone()
+    }
+  }
+
+  /// Inner gap of the workspace in pixels. Used for spacing floating
+  /// windows that are arranged in a grid.
+  pub fn inner_gap(&self) -> anyhow::Result<i32> {
+    let monitor = self.monitor().context("No monitor.")?;
+    let gaps_config = &self.0.borrow().gaps_config;
+
+    let scale_factor = if gaps_config.scale_with_dpi {
+      monitor.native().scale_factor()?
+    } else {
+      1.
+    };
+
+    Ok(
+      gaps_config
+        .inner_gap
+        .to_px(monitor.to_rect()?.width(), Some(scale_factor)),
+    )
+  }
+
   pub fn to_dto(&self) -> anyhow::Result<ContainerDto> {
     let rect = self.to_rect()?;
     let config = self.config();
@@ -94,6 +200,8 @@ impl Workspace {
       child_focus_order: self.0.borrow().child_focus_order.clone().into(),
       has_focus: self.has_focus(None),
       is_displayed: self.is_displayed(),
+      is_urgent: self.has_urgent_windows(),
+      is_monocle: self.is_monocle(),
       width: rect.width(),
       height: rect.height(),
       x: rect.x(),
@@ -112,10 +220,10 @@ impl PositionGetters for Workspace {
     let monitor =
       self.monitor().context("Workspace has no parent monitor.")?;
 
-    let gaps_config = &self.0.borrow().gaps_config;
-    let scale_factor = match &gaps_config.scale_with_dpi {
-      true => monitor.native().scale_factor()?,
-      false => 1.,
+    let scale_factor = if self.0.borrow().gaps_config.scale_with_dpi {
+      monitor.native().scale_factor()?
+    } else {
+      1.
     };
 
     // Get delta between monitor bounds and its working area.
@@ -125,22 +233,13 @@ impl PositionGetters for Workspace {
       .context("Failed to get working area of parent monitor.")?
       .delta(&monitor.to_rect()?);
 
-    let is_single_window = self.tiling_children().nth(1).is_none();
-
-    let gaps = if is_single_window {
-      gaps_config
-        .single_window_outer_gap
-        .as_ref()
-        .unwrap_or(&gaps_config.outer_gap)
-    } else {
-      &gaps_config.outer_gap
-    };
+    let gaps = self.outer_gaps();
 
     Ok(
       monitor
         .to_rect()?
         // Scale the gaps if `scale_with_dpi` is enabled.
-        .apply_inverse_delta(gaps, Some(scale_factor))
+        .apply_inverse_delta(&gaps, Some(scale_factor))
         .apply_delta(&working_delta, None),
     )
   }
@@ -1,9 +1,27 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::collections::HashMap;
 
 use uuid::Uuid;
 
 use crate::{
-  models::{Container, Workspace},
+  models::{Container, WindowContainer, Workspace},
   traits::CommonGetters,
 };
 
@@ -27,6 +45,10 @@ pub struct PendingSync {
   /// Whether window effects for all windows should be updated.
   needs_all_effects_update: bool,
 
+  /// Windows where the effect overrides have changed, and whose window
+  /// effects should be updated.
+  windows_to_update_effects: HashMap<Uuid, WindowContainer>,
+
   /// Whether to jump the cursor to the focused container (if enabled in
   /// user config).
   needs_cursor_jump: bool,
@@ -39,6 +61,7 @@ impl PendingSync {
       || self.needs_focus_update
       || self.needs_focused_effect_update
       || self.needs_all_effects_update
+      || !self.windows_to_update_effects.is_empty()
       || self.needs_cursor_jump
   }
 
@@ -48,6 +71,7 @@ impl PendingSync {
     self.needs_focus_update = false;
     self.needs_focused_effect_update = false;
     self.needs_all_effects_update = false;
+    self.windows_to_update_effects.clear();
     self.needs_cursor_jump = false;
     self
   }
@@ -111,6 +135,14 @@ impl PendingSync {
     self
   }
 
+  pub fn queue_window_effect_update(
+    &mut self,
+    window: WindowContainer,
+  ) -> &mut Self {
+    self.windows_to_update_effects.insert(window.id(), window);
+    self
+  }
+
   pub fn queue_cursor_jump(&mut self) -> &mut Self {
     self.needs_cursor_jump = true;
     self
@@ -136,6 +168,12 @@ impl PendingSync {
     &self.containers_to_redraw
   }
 
+  pub fn windows_to_update_effects(
+    &self,
+  ) -> &HashMap<Uuid, WindowContainer> {
+    &self.windows_to_update_effects
+  }
+
   pub fn workspaces_to_reorder(&self) -> &Vec<Workspace> {
     &self.workspaces_to_reorder
   }
@@ -0,0 +1,167 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  fs::{self, File},
+  io::{BufWriter, Write},
+  path::Path,
+  time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use flate2::{write::GzEncoder, Compression};
+use tracing::{info, warn};
+use wm_common::WmEvent;
+use wm_platform::{Platform, PlatformEvent};
+
+use super::{RecordedPlatformEvent, RecordingEntry};
+use crate::{
+  remembered_placements::remembered_placements_path,
+  runtime_workspaces::persisted_workspaces_path, user_config::UserConfig,
+  wm_state::WmState,
+};
+
+/// How often buffered entries are flushed to the recording file.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Records platform events, IPC messages and WM events to a gzipped file
+/// of JSON lines.
+pub struct Recorder {
+  writer: GzEncoder<BufWriter<File>>,
+  started_at: Instant,
+  last_flushed_at: Instant,
+}
+
+impl Recorder {
+  /// Creates the recording file, and writes the user config and the
+  /// current platform state to it.
+  ///
+  /// This needs to be called before the WM is populated, so that the
+  /// initial state of the platform can be replayed.
+  pub fn start(path: &Path, config: &UserConfig) -> anyhow::Result<Self> {
+    let file = File::create(path).with_context(|| {
+      format!("Unable to create recording at {}.", path.display())
+    })?;
+
+    let now = Instant::now();
+
+    let mut recorder = Self {
+      writer: GzEncoder::new(BufWriter::new(file), Compression::default()),
+      started_at: now,
+      last_flushed_at: now,
+    };
+
+    recorder.write(&RecordingEntry::Header {
+      version: env!("VERSION_NUMBER").to_string(),
+      config: config.value_str.clone(),
+      persisted_workspaces: fs::read_to_string(persisted_workspaces_path(
+        config,
+      ))
+      .ok(),
+      floating_placements: fs::read_to_string(remembered_placements_path(
+        config,
+      ))
+      .ok(),
+      platform: Platform::snapshot()?,
+    })?;
+
+    info!("Recording to {}.", path.display());
+
+    Ok(recorder)
+  }
+
+  /// Records a platform event. Needs to be called before the event is
+  /// processed.
+  pub fn record_platform_event(&mut self, event: &PlatformEvent) {
+    let res = RecordedPlatformEvent::from_event(event).and_then(|event| {
+      self.write(&RecordingEntry::PlatformEvent {
+        elapsed_ms: self.elapsed_ms(),
+        foreground_window: Platform::foreground_window().handle,
+        event,
+      })
+    });
+
+    if let Err(err) = res {
+      warn!("Failed to record platform event: {:?}", err);
+    }
+  }
+
+  pub fn record_ipc_message(&mut self, message: &str) {
+    let res = self.write(&RecordingEntry::IpcMessage {
+      elapsed_ms: self.elapsed_ms(),
+      message: message.to_string(),
+    });
+
+    if let Err(err) = res {
+      warn!("Failed to record IPC message: {:?}", err);
+    }
+  }
+
+  pub fn record_wm_event(&mut self, event: &WmEvent) {
+    let res = serde_json::to_value(event)
+      .map_err(anyhow::Error::from)
+      .and_then(|event| {
+        self.write(&RecordingEntry::WmEvent {
+          elapsed_ms: self.elapsed_ms(),
+          event,
+        })
+      });
+
+    if let Err(err) = res {
+      warn!("Failed to record WM event: {:?}", err);
+    }
+  }
+
+  /// Writes the current container tree, and finishes the recording file.
+  pub fn finish(mut self, state: &WmState) -> anyhow::Result<()> {
+    let monitors = state
+      .monitors()
+      .into_iter()
+      .map(|monitor| monitor.to_dto())
+      .try_collect::<Vec<_>>()?;
+
+    self.write(&RecordingEntry::Tree {
+      elapsed_ms: self.elapsed_ms(),
+      monitors: serde_json::to_value(monitors)?,
+    })?;
+
+    self.writer.finish()?.flush()?;
+    info!("Finished recording.");
+
+    Ok(())
+  }
+
+  fn elapsed_ms(&self) -> u64 {
+    u64::try_from(self.started_at.elapsed().as_millis())
+      .unwrap_or(u64::MAX)
+  }
+
+  fn write(&mut self, entry: &RecordingEntry) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut self.writer, entry)?;
+    self.writer.write_all(b"\n")?;
+
+    // Flush periodically, so that most of the recording is readable if
+    // the WM crashes.
+    if self.last_flushed_at.elapsed() >= FLUSH_INTERVAL {
+      self.writer.flush()?;
+      self.last_flushed_at = Instant::now();
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,284 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use wm_common::{BindingModeConfig, ParsedConfig, Point};
+use wm_platform::{
+  MonitorSnapshot, MouseMoveEvent, NativeMonitor, NativeWindow, Platform,
+  PlatformEvent, PlatformSnapshot, SimulatedPlatform, WindowSnapshot,
+};
+
+/// An entry in a recording. Recordings are stored as one JSON entry per
+/// line, starting with a `Header` entry.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(
+  tag = "type",
+  rename_all = "snake_case",
+  rename_all_fields = "camelCase"
+)]
+pub enum RecordingEntry {
+  Header {
+    version: String,
+    /// Unparsed user config.
+    config: String,
+    /// Contents of the persisted workspaces file, if it exists.
+    persisted_workspaces: Option<String>,
+    /// Contents of the remembered floating placements file, if it exists.
+    floating_placements: Option<String>,
+    /// Platform state from before the WM was started.
+    platform: PlatformSnapshot,
+  },
+  PlatformEvent {
+    elapsed_ms: u64,
+    foreground_window: isize,
+    event: RecordedPlatformEvent,
+  },
+  IpcMessage {
+    elapsed_ms: u64,
+    message: String,
+  },
+  WmEvent {
+    elapsed_ms: u64,
+    event: Value,
+  },
+  /// Container tree from when the recording was stopped.
+  Tree {
+    elapsed_ms: u64,
+    monitors: Value,
+  },
+}
+
+/// A platform event along with snapshots of the affected windows and
+/// monitors.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(
+  tag = "kind",
+  rename_all = "snake_case",
+  rename_all_fields = "camelCase"
+)]
+pub enum RecordedPlatformEvent {
+  DisplaySettingsChanged {
+    monitors: Vec<MonitorSnapshot>,
+  },
+  /// Keybindings are looked up by their bindings when replayed, since
+  /// commands are only deserializable from the user config.
+  KeybindingTriggered {
+    bindings: Vec<String>,
+  },
+  ModifiersReleased,
+  MouseMove {
+    point: Point,
+    is_mouse_down: bool,
+  },
+  WindowDestroyed {
+    window: WindowSnapshot,
+  },
+  WindowFlashed {
+    window: WindowSnapshot,
+  },
+  WindowFocused {
+    window: WindowSnapshot,
+  },
+  WindowHidden {
+    window: WindowSnapshot,
+  },
+  WindowLocationChanged {
+    window: WindowSnapshot,
+  },
+  WindowMinimized {
+    window: WindowSnapshot,
+  },
+  WindowMinimizeEnded {
+    window: WindowSnapshot,
+  },
+  WindowMovedOrResizedEnd {
+    window: WindowSnapshot,
+  },
+  WindowMovedOrResizedStart {
+    window: WindowSnapshot,
+  },
+  WindowShown {
+    window: WindowSnapshot,
+  },
+  WindowTitleChanged {
+    window: WindowSnapshot,
+  },
+}
+
+impl RecordedPlatformEvent {
+  /// Snapshots the windows and monitors affected by a platform event.
+  ///
+  /// This needs to be called before the event is processed, since
+  /// processing can change the state of the affected windows.
+  pub fn from_event(event: &PlatformEvent) -> anyhow::Result<Self> {
+    Ok(match event {
+      PlatformEvent::DisplaySettingsChanged => {
+        Self::DisplaySettingsChanged {
+          monitors: Platform::sorted_monitors()?
+            .iter()
+            .map(NativeMonitor::snapshot)
+            .try_collect()?,
+        }
+      }
+      PlatformEvent::KeybindingTriggered(keybinding) => {
+        Self::KeybindingTriggered {
+          bindings: keybinding.bindings.clone(),
+        }
+      }
+      PlatformEvent::ModifiersReleased => Self::ModifiersReleased,
+      PlatformEvent::MouseMove(event) => Self::MouseMove {
+        point: event.point.clone(),
+        is_mouse_down: event.is_mouse_down,
+      },
+      PlatformEvent::WindowDestroyed(window) => Self::WindowDestroyed {
+        window: window.snapshot(),
+      },
+      PlatformEvent::WindowFlashed(window) => Self::WindowFlashed {
+        window: window.snapshot(),
+      },
+      PlatformEvent::WindowFocused(window) => Self::WindowFocused {
+        window: window.snapshot(),
+      },
+      PlatformEvent::WindowHidden(window) => Self::WindowHidden {
+        window: window.snapshot(),
+      },
+      PlatformEvent::WindowLocationChanged(window) => {
+        Self::WindowLocationChanged {
+          window: window.snapshot(),
+        }
+      }
+      PlatformEvent::WindowMinimized(window) => Self::WindowMinimized {
+        window: window.snapshot(),
+      },
+      PlatformEvent::WindowMinimizeEnded(window) => {
+        Self::WindowMinimizeEnded {
+          window: window.snapshot(),
+        }
+      }
+      PlatformEvent::WindowMovedOrResizedEnd(window) => {
+        Self::WindowMovedOrResizedEnd {
+          window: window.snapshot(),
+        }
+      }
+      PlatformEvent::WindowMovedOrResizedStart(window) => {
+        Self::WindowMovedOrResizedStart {
+          window: window.snapshot(),
+        }
+      }
+      PlatformEvent::WindowShown(window) => Self::WindowShown {
+        window: window.snapshot(),
+      },
+      PlatformEvent::WindowTitleChanged(window) => {
+        Self::WindowTitleChanged {
+          window: window.snapshot(),
+        }
+      }
+    })
+  }
+
+  /// Applies the recorded snapshots to the simulated platform, and
+  /// converts back to a platform event.
+  ///
+  /// Keybindings are looked up from the active binding mode, or otherwise
+  /// from the user config.
+  pub fn into_event(
+    self,
+    config: &ParsedConfig,
+    binding_modes: &[BindingModeConfig],
+  ) -> anyhow::Result<PlatformEvent> {
+    let event = match self {
+      Self::DisplaySettingsChanged { monitors } => {
+        SimulatedPlatform::set_monitors(monitors);
+        PlatformEvent::DisplaySettingsChanged
+      }
+      Self::KeybindingTriggered { bindings } => {
+        let keybindings = binding_modes
+          .first()
+          .map_or(&config.keybindings, |mode| &mode.keybindings);
+
+        let keybinding = keybindings
+          .iter()
+          .find(|keybinding| keybinding.bindings == bindings)
+          .with_context(|| {
+            format!("No keybinding for {bindings:?} in user config.")
+          })?;
+
+        PlatformEvent::KeybindingTriggered(keybinding.clone())
+      }
+      Self::ModifiersReleased => PlatformEvent::ModifiersReleased,
+      Self::MouseMove {
+        point,
+        is_mouse_down,
+      } => {
+        SimulatedPlatform::set_mouse_position(point.clone());
+        PlatformEvent::MouseMove(MouseMoveEvent {
+          point,
+          is_mouse_down,
+        })
+      }
+      Self::WindowDestroyed { window } => {
+        PlatformEvent::WindowDestroyed(Self::simulate_window(window))
+      }
+      Self::WindowFlashed { window } => {
+        PlatformEvent::WindowFlashed(Self::simulate_window(window))
+      }
+      Self::WindowFocused { window } => {
+        PlatformEvent::WindowFocused(Self::simulate_window(window))
+      }
+      Self::WindowHidden { window } => {
+        PlatformEvent::WindowHidden(Self::simulate_window(window))
+      }
+      Self::WindowLocationChanged { window } => {
+        PlatformEvent::WindowLocationChanged(Self::simulate_window(window))
+      }
+      Self::WindowMinimized { window } => {
+        PlatformEvent::WindowMinimized(Self::simulate_window(window))
+      }
+      Self::WindowMinimizeEnded { window } => {
+        PlatformEvent::WindowMinimizeEnded(Self::simulate_window(window))
+      }
+      Self::WindowMovedOrResizedEnd { window } => {
+        PlatformEvent::WindowMovedOrResizedEnd(Self::simulate_window(
+          window,
+        ))
+      }
+      Self::WindowMovedOrResizedStart { window } => {
+        PlatformEvent::WindowMovedOrResizedStart(Self::simulate_window(
+          window,
+        ))
+      }
+      Self::WindowShown { window } => {
+        PlatformEvent::WindowShown(Self::simulate_window(window))
+      }
+      Self::WindowTitleChanged { window } => {
+        PlatformEvent::WindowTitleChanged(Self::simulate_window(window))
+      }
+    };
+
+    Ok(event)
+  }
+
+  /// Updates the window on the simulated platform.
+  fn simulate_window(window: WindowSnapshot) -> NativeWindow {
+    let handle = window.handle;
+    SimulatedPlatform::update_window(window);
+    NativeWindow::new(handle)
+  }
+}
@@ -0,0 +1,381 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+  env,
+  fs::{self, File},
+  io::{BufRead, BufReader},
+  iter,
+  path::{Path, PathBuf},
+  process::ExitCode,
+  time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use clap::Parser;
+use flate2::read::MultiGzDecoder;
+use serde_json::Value;
+use tracing::{info, warn};
+use wm_common::{AppCommand, Verbosity};
+use wm_platform::SimulatedPlatform;
+
+use super::RecordingEntry;
+use crate::{
+  remembered_placements::remembered_placements_path,
+  runtime_workspaces::persisted_workspaces_path, user_config::UserConfig,
+  wm::WindowManager,
+};
+
+/// Counts of replayed entries, and the events emitted by the WM.
+#[derive(Default)]
+struct ReplayResult {
+  platform_events: usize,
+  ipc_messages: usize,
+  skipped_messages: usize,
+  errors: usize,
+  recorded_events: Vec<Value>,
+  replayed_events: Vec<Value>,
+  recorded_tree: Option<Value>,
+  replayed_tree: Option<Value>,
+}
+
+/// Replays a recording against a simulated platform, and reports whether
+/// the resulting container tree matches the recorded one.
+///
+/// Exits with code 1 if the container trees differ.
+pub async fn replay(
+  path: &Path,
+  config_path: Option<PathBuf>,
+  realtime: bool,
+  verbosity: &Verbosity,
+) -> anyhow::Result<ExitCode> {
+  tracing_subscriber::fmt()
+    .with_writer(std::io::stderr)
+    .with_max_level(verbosity.level())
+    .init();
+
+  let file = File::open(path).with_context(|| {
+    format!("Unable to open recording at {}.", path.display())
+  })?;
+
+  let mut lines = BufReader::new(MultiGzDecoder::new(file)).lines();
+
+  let header = lines
+    .next()
+    .context("Recording is empty.")??
+    .parse::<Value>()
+    .and_then(serde_json::from_value::<RecordingEntry>)
+    .context("Recording has an invalid header.")?;
+
+  let RecordingEntry::Header {
+    version,
+    config,
+    persisted_workspaces,
+    floating_placements,
+    platform,
+  } = header
+  else {
+    anyhow::bail!("Recording doesn't start with a header.");
+  };
+
+  info!("Replaying recording from v{}.", version);
+
+  // The WM reads and writes its state files next to the user config, so
+  // these are kept in a temporary directory.
+  let replay_dir =
+    env::temp_dir().join(format!("lavawm-replay-{}", std::process::id()));
+
+  fs::create_dir_all(&replay_dir)?;
+
+  let res = run(
+    lines,
+    &replay_dir,
+    ReplayFiles {
+      config: match config_path {
+        Some(config_path) => fs::read_to_string(config_path)?,
+        None => config,
+      },
+      persisted_workspaces,
+      floating_placements,
+    },
+    platform,
+    realtime,
+  )
+  .await;
+
+  SimulatedPlatform::stop();
+
+  if let Err(err) = fs::remove_dir_all(&replay_dir) {
+    warn!("Failed to remove replay directory: {:?}", err);
+  }
+
+  Ok(report(&res?))
+}
+
+/// Contents of the files that are written to the replay directory.
+struct ReplayFiles {
+  config: String,
+  persisted_workspaces: Option<String>,
+  floating_placements: Option<String>,
+}
+
+async fn run(
+  lines: impl Iterator<Item = std::io::Result<String>>,
+  replay_dir: &Path,
+  files: ReplayFiles,
+  platform: wm_platform::PlatformSnapshot,
+  realtime: bool,
+) -> anyhow::Result<ReplayResult> {
+  let config_path = replay_dir.join("config.yaml");
+  fs::write(&config_path, files.config)?;
+
+  SimulatedPlatform::start(platform);
+
+  let mut config = UserConfig::new(Some(config_path))?;
+
+  if let Some(contents) = files.persisted_workspaces {
+    fs::write(persisted_workspaces_path(&config), contents)?;
+  }
+
+  if let Some(contents) = files.floating_placements {
+    fs::write(remembered_placements_path(&config), contents)?;
+  }
+
+  let mut wm = WindowManager::new(&mut config, None)?;
+  let mut result = ReplayResult::default();
+
+  let startup_commands = config.value.general.startup_commands.clone();
+  wm.process_commands(&startup_commands, None, &mut config)?;
+
+  let started_at = Instant::now();
+
+  for line in lines {
+    // Recordings of a crashed WM can end with a truncated entry.
+    let entry = match line.map_err(anyhow::Error::from).and_then(|line| {
+      serde_json::from_str::<RecordingEntry>(&line).map_err(Into::into)
+    }) {
+      Ok(entry) => entry,
+      Err(err) => {
+        warn!("Stopping at unreadable entry: {:?}", err);
+        break;
+      }
+    };
+
+    match entry {
+      RecordingEntry::PlatformEvent {
+        elapsed_ms,
+        foreground_window,
+        event,
+      } => {
+        if realtime {
+          let elapsed = Duration::from_millis(elapsed_ms);
+          tokio::time::sleep(elapsed.saturating_sub(started_at.elapsed()))
+            .await;
+        }
+
+        SimulatedPlatform::set_foreground_window(foreground_window);
+        result.platform_events += 1;
+
+        let res = event
+          .into_event(&config.value, &wm.state.binding_modes)
+          .and_then(|event| wm.process_event(event, &mut config));
+
+        if let Err(err) = res {
+          warn!("Failed to replay platform event: {:?}", err);
+          result.errors += 1;
+        }
+      }
+      RecordingEntry::IpcMessage { message, .. } => {
+        let app_command = AppCommand::try_parse_from(
+          iter::once("").chain(message.split_whitespace()),
+        );
+
+        // Queries and subscriptions don't change the WM state, and
+        // container IDs differ between runs.
+        let Ok(AppCommand::Command {
+          subject_container_id: None,
+          command,
+          ..
+        }) = app_command
+        else {
+          result.skipped_messages += 1;
+          continue;
+        };
+
+        result.ipc_messages += 1;
+
+        if let Err(err) =
+          wm.process_commands(&vec![command], None, &mut config)
+        {
+          warn!("Failed to replay IPC message: {:?}", err);
+          result.errors += 1;
+        }
+      }
+      RecordingEntry::WmEvent { event, .. } => {
+        result.recorded_events.push(event);
+      }
+      RecordingEntry::Tree { monitors, .. } => {
+        result.recorded_tree = Some(monitors);
+      }
+      RecordingEntry::Header { .. } => {
+        warn!("Ignoring unexpected header in recording.");
+      }
+    }
+
+    apply_size_constraints(&mut wm, &config, &mut result);
+
+    while let Ok(wm_event) = wm.event_rx.try_recv() {
+      result.replayed_events.push(serde_json::to_value(wm_event)?);
+    }
+  }
+
+  let monitors = wm
+    .state
+    .monitors()
+    .into_iter()
+    .map(|monitor| monitor.to_dto())
+    .try_collect::<Vec<_>>()?;
+
+  result.replayed_tree = Some(serde_json::to_value(monitors)?);
+
+  Ok(result)
+}
+
+/// Applies size constraints that were queued while replaying an entry.
+///
+/// Size constraints are retrieved synchronously on the simulated
+/// platform, so they're always queued by the time an entry is replayed.
+fn apply_size_constraints(
+  wm: &mut WindowManager,
+  config: &UserConfig,
+  result: &mut ReplayResult,
+) {
+  while let Ok((window_id, size_constraints)) =
+    wm.size_constraints_rx.try_recv()
+  {
+    if let Err(err) =
+      wm.process_size_constraints(window_id, &size_constraints, config)
+    {
+      warn!("Failed to apply size constraints: {:?}", err);
+      result.errors += 1;
+    }
+  }
+}
+
+/// Prints a summary of the replay to stdout.
+fn report(result: &ReplayResult) -> ExitCode {
+  println!(
+    "Replayed {} platform events and {} IPC messages ({} skipped, {} errors).",
+    result.platform_events,
+    result.ipc_messages,
+    result.skipped_messages,
+    result.errors,
+  );
+
+  let recorded_types = event_types(&result.recorded_events);
+  let replayed_types = event_types(&result.replayed_events);
+
+  println!(
+    "WM events: {} recorded, {} replayed.",
+    recorded_types.len(),
+    replayed_types.len(),
+  );
+
+  if let Some((index, (recorded, replayed))) = recorded_types
+    .iter()
+    .zip(&replayed_types)
+    .enumerate()
+    .find(|(_, (recorded, replayed))| recorded != replayed)
+  {
+    println!(
+      "First differing WM event at #{index}: recorded `{recorded}`, replayed `{replayed}`."
+    );
+  }
+
+  let replayed_tree = result
+    .replayed_tree
+    .as_ref()
+    .map(wm_cli::render_tree)
+    .unwrap_or_default();
+
+  let Some(recorded_tree) =
+    result.recorded_tree.as_ref().map(wm_cli::render_tree)
+  else {
+    println!("Recording has no container tree. Replayed tree:");
+    println!("{replayed_tree}");
+    return ExitCode::SUCCESS;
+  };
+
+  if recorded_tree == replayed_tree {
+    println!("Container tree matches the recording.");
+    return ExitCode::SUCCESS;
+  }
+
+  println!("Container tree differs from the recording:");
+  println!("{}", diff_lines(&recorded_tree, &replayed_tree));
+
+  ExitCode::FAILURE
+}
+
+fn event_types(events: &[Value]) -> Vec<&str> {
+  events
+    .iter()
+    .filter_map(|event| event.get("eventType").and_then(Value::as_str))
+    .collect()
+}
+
+/// Line diff of two strings, where removed lines are prefixed with `-`
+/// and added lines with `+`.
+fn diff_lines(old: &str, new: &str) -> String {
+  let old = old.lines().collect::<Vec<_>>();
+  let new = new.lines().collect::<Vec<_>>();
+
+  // Lengths of the longest common subsequences of the remaining lines.
+  let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lengths[i][j] = if old[i] == new[j] {
+        lengths[i + 1][j + 1] + 1
+      } else {
+        lengths[i + 1][j].max(lengths[i][j + 1])
+      };
+    }
+  }
+
+  let mut output = Vec::new();
+  let (mut i, mut j) = (0, 0);
+
+  while i < old.len() || j < new.len() {
+    if i < old.len() && j < new.len() && old[i] == new[j] {
+      output.push(format!("  {}", old[i]));
+      i += 1;
+      j += 1;
+    } else if j < new.len()
+      && (i == old.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+    {
+      output.push(format!("+ {}", new[j]));
+      j += 1;
+    } else {
+      output.push(format!("- {}", old[i]));
+      i += 1;
+    }
+  }
+
+  output.join("\n")
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use anyhow::Context;
+use wm_common::Rect;
+use wm_platform::NativeWindow;
+
+use crate::user_config::UserConfig;
+
+/// Floating placements that the user has moved windows to, keyed by
+/// the window's process and class name.
+///
+/// Used by the `remember_per_app` floating placement strategy. Placements
+/// are stored relative to the top-left of the workspace, so that they can
+/// be restored on any monitor.
+#[derive(Debug, Default)]
+pub struct RememberedPlacements {
+  placements: HashMap<String, Rect>,
+}
+
+impl RememberedPlacements {
+  /// Reads placements saved via `RememberedPlacements::remember`.
+  pub fn read(config: &UserConfig) -> anyhow::Result<Self> {
+    let path = remembered_placements_path(config);
+
+    if !path.exists() {
+      return Ok(Self::default());
+    }
+
+    let placements_str = fs::read_to_string(&path)
+      .with_context(|| format!("Unable to read {}.", path.display()))?;
+
+    Ok(Self {
+      placements: serde_json::from_str(&placements_str)?,
+    })
+  }
+
+  /// Gets the remembered placement of a window, translated to the given
+  /// workspace rect.
+  pub fn get(
+    &self,
+    native_window: &NativeWindow,
+    workspace_rect: &Rect,
+  ) -> Option<Rect> {
+    let key = placement_key(native_window).ok()?;
+
+    self.placements.get(&key).map(|placement| {
+      placement
+        .translate_to_coordinates(
+          workspace_rect.x() + placement.x(),
+          workspace_rect.y() + placement.y(),
+        )
+        .translate_within(workspace_rect)
+    })
+  }
+
+  /// Remembers the placement of a window within the given workspace rect,
+  /// and saves all placements next to the user config file.
+  pub fn remember(
+    &mut self,
+    native_window: &NativeWindow,
+    placement: &Rect,
+    workspace_rect: &Rect,
+    config: &UserConfig,
+  ) -> anyhow::Result<()> {
+    let relative_placement = placement.translate_to_coordinates(
+      placement.x() - workspace_rect.x(),
+      placement.y() - workspace_rect.y(),
+    );
+
+    let key = placement_key(native_window)?;
+
+    if self.placements.get(&key) == Some(&relative_placement) {
+      return Ok(());
+    }
+
+    self.placements.insert(key, relative_placement);
+
+    let path = remembered_placements_path(config);
+
+    fs::write(&path, serde_json::to_string_pretty(&self.placements)?)
+      .with_context(|| format!("Unable to write to {}.", path.display()))
+  }
+}
+
+fn placement_key(native_window: &NativeWindow) -> anyhow::Result<String> {
+  Ok(format!(
+    "{}/{}",
+    native_window.process_name()?,
+    native_window.class_name()?
+  ))
+}
+
+/// Placements are saved next to the user config file.
+pub fn remembered_placements_path(config: &UserConfig) -> PathBuf {
+  config.path.with_file_name("floating_placements.json")
+}
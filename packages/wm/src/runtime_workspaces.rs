@@ -0,0 +1,199 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use wm_common::WorkspaceConfig;
+
+use crate::{user_config::UserConfig, wm_state::WmState};
+
+/// A workspace config held in `WmState`.
+///
+/// Workspace configs are initialized from the user config, but can also
+/// be created, renamed, reordered and deleted at runtime.
+#[derive(Clone, Debug)]
+pub struct RuntimeWorkspaceConfig {
+  pub value: WorkspaceConfig,
+
+  /// Name of the workspace in the user config that this config is
+  /// derived from. Is `None` for workspaces created at runtime.
+  pub config_name: Option<String>,
+}
+
+impl RuntimeWorkspaceConfig {
+  /// Creates a config for a workspace that isn't in the user config.
+  pub fn new(name: &str) -> Self {
+    Self {
+      value: WorkspaceConfig {
+        name: name.to_string(),
+        ..Default::default()
+      },
+      config_name: None,
+    }
+  }
+}
+
+/// Saved form of a `RuntimeWorkspaceConfig`. Only names are saved, since
+/// the remaining settings are resolved from the user config on startup.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PersistedWorkspace {
+  name: String,
+  config_name: Option<String>,
+}
+
+impl PersistedWorkspace {
+  pub fn new(workspace_config: &RuntimeWorkspaceConfig) -> Self {
+    Self {
+      name: workspace_config.value.name.clone(),
+      config_name: workspace_config.config_name.clone(),
+    }
+  }
+
+  /// Gets a config that only contains the workspace name. It should be
+  /// resolved against the user config via `merge_workspace_configs`.
+  pub fn to_config(&self) -> RuntimeWorkspaceConfig {
+    RuntimeWorkspaceConfig {
+      config_name: self.config_name.clone(),
+      ..RuntimeWorkspaceConfig::new(&self.name)
+    }
+  }
+}
+
+/// Resolves workspace configs against the workspaces in the user config.
+///
+/// Existing configs keep their order and take the settings of the user
+/// config workspace they're derived from. Configs derived from workspaces
+/// that have since been removed from the user config are dropped, and
+/// new workspaces in the user config are appended.
+pub fn merge_workspace_configs(
+  existing_configs: &[RuntimeWorkspaceConfig],
+  config: &UserConfig,
+) -> Vec<RuntimeWorkspaceConfig> {
+  let user_config_by_name = |name: &str| {
+    config
+      .value
+      .workspaces
+      .iter()
+      .find(|workspace_config| workspace_config.name == name)
+  };
+
+  let mut merged_configs = existing_configs
+    .iter()
+    .filter_map(|existing| match &existing.config_name {
+      // Workspaces created at runtime get adopted if a workspace with the
+      // same name has since been added to the user config.
+      None => Some(user_config_by_name(&existing.value.name).map_or_else(
+        || existing.clone(),
+        |user_config| RuntimeWorkspaceConfig {
+          value: user_config.clone(),
+          config_name: Some(user_config.name.clone()),
+        },
+      )),
+      Some(config_name) => {
+        user_config_by_name(config_name).map(|user_config| {
+          let mut value = user_config.clone();
+
+          // Display name no longer applies to renamed workspaces.
+          if value.name != existing.value.name {
+            value.name.clone_from(&existing.value.name);
+            value.display_name = None;
+          }
+
+          RuntimeWorkspaceConfig {
+            value,
+            config_name: Some(config_name.clone()),
+          }
+        })
+      }
+    })
+    .collect::<Vec<_>>();
+
+  for user_config in &config.value.workspaces {
+    let is_merged = merged_configs.iter().any(|merged| {
+      merged.config_name.as_ref() == Some(&user_config.name)
+        || merged.value.name == user_config.name
+    });
+
+    if !is_merged {
+      merged_configs.push(RuntimeWorkspaceConfig {
+        value: user_config.clone(),
+        config_name: Some(user_config.name.clone()),
+      });
+    }
+  }
+
+  merged_configs
+}
+
+/// Reads workspace configs saved via `persist_workspace_configs`.
+///
+/// The returned configs only contain workspace names, and should be
+/// resolved against the user config via `merge_workspace_configs`.
+pub fn read_persisted_workspace_configs(
+  config: &UserConfig,
+) -> anyhow::Result<Vec<RuntimeWorkspaceConfig>> {
+  let path = persisted_workspaces_path(config);
+
+  if !path.exists() {
+    return Ok(Vec::new());
+  }
+
+  let persisted_str = fs::read_to_string(&path)
+    .with_context(|| format!("Unable to read {}.", path.display()))?;
+
+  let persisted_workspaces =
+    serde_json::from_str::<Vec<PersistedWorkspace>>(&persisted_str)?;
+
+  Ok(
+    persisted_workspaces
+      .iter()
+      .map(PersistedWorkspace::to_config)
+      .collect(),
+  )
+}
+
+/// Saves the workspace configs in state, so that they can be restored on
+/// the next startup.
+///
+/// Does nothing unless `general.persist_dynamic_workspaces` is enabled.
+pub fn persist_workspace_configs(
+  state: &WmState,
+  config: &UserConfig,
+) -> anyhow::Result<()> {
+  if !config.value.general.persist_dynamic_workspaces {
+    return Ok(());
+  }
+
+  let persisted_workspaces = state
+    .workspace_configs
+    .iter()
+    .map(PersistedWorkspace::new)
+    .collect::<Vec<_>>();
+
+  let path = persisted_workspaces_path(config);
+
+  fs::write(&path, serde_json::to_string_pretty(&persisted_workspaces)?)
+    .with_context(|| format!("Unable to write to {}.", path.display()))
+}
+
+/// Workspaces are saved next to the user config file.
+pub fn persisted_workspaces_path(config: &UserConfig) -> PathBuf {
+  config.path.with_file_name("workspaces.json")
+}
@@ -1,11 +1,123 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use ambassador::delegatable_trait;
-use wm_common::Rect;
+use wm_common::{distribute_length, Rect, TilingDirection};
+
+use crate::{
+  models::DirectionContainer,
+  traits::{CommonGetters, TilingDirectionGetters, TilingSizeGetters},
+};
 
 #[delegatable_trait]
 pub trait PositionGetters {
   fn to_rect(&self) -> anyhow::Result<Rect>;
 }
 
+/// Gets the rects of the tiling children of a direction container, in
+/// the order of its children.
+///
+/// The available length is distributed in proportion to the tiling sizes
+/// of the children, while respecting the size constraints of windows.
+/// Computing all rects at once means the size constraints of siblings
+/// only need to be retrieved once per parent.
+pub fn tiling_child_rects(
+  parent: &DirectionContainer,
+) -> anyhow::Result<Vec<Rect>> {
+  let tiling_children = parent.tiling_children().collect::<Vec<_>>();
+
+  let Some(first_child) = tiling_children.first() else {
+    return Ok(Vec::new());
+  };
+
+  let parent_rect = parent.to_rect()?;
+
+  let (horizontal_gap, vertical_gap) = first_child.inner_gaps()?;
+  let inner_gap = match parent.tiling_direction() {
+    TilingDirection::Vertical => vertical_gap,
+    TilingDirection::Horizontal => horizontal_gap,
+  };
+
+  let is_horizontal =
+    parent.tiling_direction() == TilingDirection::Horizontal;
+
+  let weights = tiling_children
+    .iter()
+    .map(TilingSizeGetters::tiling_size)
+    .collect::<Vec<_>>();
+
+  let constraints = tiling_children
+    .iter()
+    .map(|child| child.length_constraints(is_horizontal))
+    .try_collect::<Vec<_>>()?;
+
+  #[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap
+  )]
+  let available_length = if is_horizontal {
+    parent_rect.width()
+  } else {
+    parent_rect.height()
+  } - inner_gap
+    * (tiling_children.len() - 1) as i32;
+
+  #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+  let lengths =
+    distribute_length(available_length as f32, &weights, &constraints)
+      .into_iter()
+      .map(|length| {
+        if is_horizontal {
+          length.round() as i32
+        } else {
+          length as i32
+        }
+      });
+
+  let mut offset = 0;
+
+  Ok(
+    lengths
+      .map(|length| {
+        let rect = if is_horizontal {
+          Rect::from_xy(
+            parent_rect.x() + offset,
+            parent_rect.y(),
+            length,
+            parent_rect.height(),
+          )
+        } else {
+          Rect::from_xy(
+            parent_rect.x(),
+            parent_rect.y() + offset,
+            parent_rect.width(),
+            length,
+          )
+        };
+
+        offset += length + inner_gap;
+        rect
+      })
+      .collect(),
+  )
+}
+
 /// Implements the `PositionGetters` trait for tiling containers that can
 /// be resized. This is used by `SplitContainer` and `TilingWindow`.
 ///
@@ -21,65 +133,15 @@ macro_rules! impl_position_getters_as_resizable {
           .and_then(|parent| parent.as_direction_container().ok())
           .context("Parent does not have a tiling direction.")?;
 
-        let parent_rect = parent.to_rect()?;
-
-        let (horizontal_gap, vertical_gap) = self.inner_gaps()?;
-        let inner_gap = match parent.tiling_direction() {
-          TilingDirection::Vertical => vertical_gap,
-          TilingDirection::Horizontal => horizontal_gap,
-        };
-
-        #[allow(
-          clippy::cast_precision_loss,
-          clippy::cast_possible_truncation,
-          clippy::cast_possible_wrap
-        )]
-        let (width, height) = match parent.tiling_direction() {
-          TilingDirection::Vertical => {
-            let available_height = parent_rect.height()
-              - inner_gap * self.tiling_siblings().count() as i32;
-
-            let height =
-              (self.tiling_size() * available_height as f32) as i32;
-
-            (parent_rect.width(), height)
-          }
-          TilingDirection::Horizontal => {
-            let available_width = parent_rect.width()
-              - inner_gap * self.tiling_siblings().count() as i32;
-
-            let width =
-              (available_width as f32 * self.tiling_size()).round() as i32;
-
-            (width, parent_rect.height())
-          }
-        };
-
-        let (x, y) = {
-          let mut prev_siblings = self
-            .prev_siblings()
-            .filter_map(|sibling| sibling.as_tiling_container().ok());
-
-          match prev_siblings.next() {
-            None => (parent_rect.x(), parent_rect.y()),
-            Some(sibling) => {
-              let sibling_rect = sibling.to_rect()?;
-
-              match parent.tiling_direction() {
-                TilingDirection::Vertical => (
-                  parent_rect.x(),
-                  sibling_rect.y() + sibling_rect.height() + inner_gap,
-                ),
-                TilingDirection::Horizontal => (
-                  sibling_rect.x() + sibling_rect.width() + inner_gap,
-                  parent_rect.y(),
-                ),
-              }
-            }
-          }
-        };
+        let index = parent
+          .tiling_children()
+          .position(|child| child.id() == self.id())
+          .context("Container is not a tiling child of its parent.")?;
 
-        Ok(Rect::from_xy(x, y, width, height))
+        $crate::traits::tiling_child_rects(&parent)?
+          .into_iter()
+          .nth(index)
+          .context("Container is not a tiling child of its parent.")
       }
     }
   };
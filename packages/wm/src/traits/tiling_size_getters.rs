@@ -1,10 +1,30 @@
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
 use std::cell::Ref;
 
 use ambassador::delegatable_trait;
 use anyhow::Context;
 use wm_common::{GapsConfig, TilingDirection};
 
-use super::{CommonGetters, PositionGetters, TilingDirectionGetters};
+use super::{
+  CommonGetters, PositionGetters, TilingDirectionGetters, WindowGetters,
+};
 use crate::models::{Container, DirectionContainer, TilingContainer};
 
 pub const MIN_TILING_SIZE: f32 = 0.01;
@@ -20,10 +40,16 @@ pub trait TilingSizeGetters: CommonGetters {
   fn set_gaps_config(&self, gaps_config: GapsConfig);
 
   /// Gets the horizontal and vertical gaps between windows in pixels.
+  ///
+  /// Gaps of the parent workspace take precedence, so that per-workspace
+  /// gaps apply to containers that are moved between workspaces.
   fn inner_gaps(&self) -> anyhow::Result<(i32, i32)> {
     let monitor = self.monitor().context("No monitor.")?;
     let monitor_rect = monitor.to_rect()?;
-    let gaps_config = self.gaps_config();
+    let gaps_config = self.workspace().map_or_else(
+      || self.gaps_config().clone(),
+      |workspace| workspace.gaps_config(),
+    );
 
     let scale_factor = if gaps_config.scale_with_dpi {
       monitor.native().scale_factor()?
@@ -41,6 +67,68 @@ pub trait TilingSizeGetters: CommonGetters {
     ))
   }
 
+  /// Gets the minimum and maximum width or height in pixels that the
+  /// container can take, based on the size constraints of its windows.
+  ///
+  /// For split containers, the lengths of children are summed along the
+  /// tiling direction. On the other axis, the largest minimum applies, and
+  /// a maximum only applies if all children have one.
+  fn length_constraints(
+    &self,
+    is_width: bool,
+  ) -> anyhow::Result<(Option<i32>, Option<i32>)> {
+    let split = match self.as_tiling_container()? {
+      TilingContainer::TilingWindow(window) => {
+        let constraints = window.size_constraints();
+
+        return Ok((
+          constraints.min_length(is_width),
+          constraints.max_length(is_width),
+        ));
+      }
+      TilingContainer::Split(split) => split,
+    };
+
+    let child_constraints = split
+      .tiling_children()
+      .map(|child| child.length_constraints(is_width))
+      .try_collect::<Vec<_>>()?;
+
+    let mins = child_constraints.iter().filter_map(|(min, _)| *min);
+    let maxes = child_constraints
+      .iter()
+      .map(|(_, max)| *max)
+      .collect::<Option<Vec<_>>>();
+
+    let is_along_tiling_direction = match split.tiling_direction() {
+      TilingDirection::Horizontal => is_width,
+      TilingDirection::Vertical => !is_width,
+    };
+
+    if !is_along_tiling_direction {
+      return Ok((
+        mins.max(),
+        maxes.and_then(|maxes| maxes.into_iter().max()),
+      ));
+    }
+
+    let (horizontal_gap, vertical_gap) = self.inner_gaps()?;
+    let gap = if is_width {
+      horizontal_gap
+    } else {
+      vertical_gap
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let total_gap = gap * (child_constraints.len().max(1) - 1) as i32;
+
+    let min = mins.reduce(|sum, min| sum + min).map(|min| min + total_gap);
+    let max =
+      maxes.map(|maxes| maxes.into_iter().sum::<i32>() + total_gap);
+
+    Ok((min, max))
+  }
+
   /// Gets the container to resize when resizing a tiling window.
   fn container_to_resize(
     &self,
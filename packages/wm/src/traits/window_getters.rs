@@ -1,9 +1,27 @@
-use std::cell::Ref;
+// Copyright (C) 2024 glzr-io <https://github.com/glzr-io>
+// Copyright (C) 2026 jack-work <https://github.com/jack-work>
+//
+// This file is part of LavaWM, a fork of GlazeWM.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{cell::Ref, time::Instant};
 
 use ambassador::delegatable_trait;
 use wm_common::{
-  ActiveDrag, DisplayState, LengthValue, Rect, RectDelta,
-  WindowRuleConfig, WindowState,
+  ActiveDrag, DisplayState, LengthValue, Rect, RectDelta, SizeConstraints,
+  StickyScope, WindowEffectOverrides, WindowRuleConfig, WindowState,
 };
 use wm_platform::NativeWindow;
 
@@ -120,13 +138,43 @@ pub trait WindowGetters {
   fn active_drag(&self) -> Option<ActiveDrag>;
 
   fn set_active_drag(&self, active_drag: Option<ActiveDrag>);
+
+  /// Time since the window started requesting attention (e.g. by
+  /// flashing its taskbar button). Is `None` if the window isn't urgent.
+  fn urgent_since(&self) -> Option<Instant>;
+
+  fn set_urgent_since(&self, urgent_since: Option<Instant>);
+
+  /// Which workspaces the window follows when it's sticky. Is `None` if
+  /// the window isn't sticky.
+  fn sticky(&self) -> Option<StickyScope>;
+
+  fn set_sticky(&self, sticky: Option<StickyScope>);
+
+  /// Minimum and maximum size of the window, as reported by the platform
+  /// and overridden by window rules.
+  fn size_constraints(&self) -> SizeConstraints;
+
+  fn set_size_constraints(&self, size_constraints: SizeConstraints);
+
+  /// Window effects that take precedence over the window effects from
+  /// the user config.
+  fn effect_overrides(&self) -> WindowEffectOverrides;
+
+  fn set_effect_overrides(&self, effect_overrides: WindowEffectOverrides);
+
+  /// Whether the window is currently requesting attention.
+  fn is_urgent(&self) -> bool {
+    self.urgent_since().is_some()
+  }
 }
 
 /// Implements the `WindowGetters` trait for a given struct.
 ///
 /// Expects that the struct has a wrapping `RefCell` containing a struct
 /// with a `state`, `prev_state`, `native`, `has_pending_dpi_adjustment`,
-/// `border_delta`, `display_state`, and a `done_window_rules` field.
+/// `border_delta`, `display_state`, `done_window_rules`, `urgent_since`,
+/// and a `sticky` field.
 #[macro_export]
 macro_rules! impl_window_getters {
   ($struct_name:ident) => {
@@ -217,6 +265,41 @@ macro_rules! impl_window_getters {
       fn set_active_drag(&self, active_drag: Option<ActiveDrag>) {
         self.0.borrow_mut().active_drag = active_drag;
       }
+
+      fn urgent_since(&self) -> Option<Instant> {
+        self.0.borrow().urgent_since
+      }
+
+      fn set_urgent_since(&self, urgent_since: Option<Instant>) {
+        self.0.borrow_mut().urgent_since = urgent_since;
+      }
+
+      fn sticky(&self) -> Option<StickyScope> {
+        self.0.borrow().sticky.clone()
+      }
+
+      fn set_sticky(&self, sticky: Option<StickyScope>) {
+        self.0.borrow_mut().sticky = sticky;
+      }
+
+      fn size_constraints(&self) -> SizeConstraints {
+        self.0.borrow().size_constraints.clone()
+      }
+
+      fn set_size_constraints(&self, size_constraints: SizeConstraints) {
+        self.0.borrow_mut().size_constraints = size_constraints;
+      }
+
+      fn effect_overrides(&self) -> WindowEffectOverrides {
+        self.0.borrow().effect_overrides.clone()
+      }
+
+      fn set_effect_overrides(
+        &self,
+        effect_overrides: WindowEffectOverrides,
+      ) {
+        self.0.borrow_mut().effect_overrides = effect_overrides;
+      }
     }
   };
 }
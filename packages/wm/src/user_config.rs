@@ -19,13 +19,16 @@
 use std::{collections::HashMap, env, fs, path::PathBuf};
 
 use anyhow::{Context, Result};
+use serde_yaml::Value;
 use wm_common::{
-  InvokeCommand, MatchType, ParsedConfig, WindowMatchConfig,
-  WindowRuleConfig, WindowRuleEvent, WorkspaceConfig,
+  GapsConfig, HideMethod, InitialWindowState, InvokeCommand, MatchType,
+  ParsedConfig, WindowMatchConfig, WindowMatchProperties,
+  WindowRuleConfig, WindowRuleEvent, WindowState, WorkspaceConfig,
 };
 
 use crate::{
-  models::{Monitor, WindowContainer, Workspace},
+  config_resolver::resolve_config,
+  models::{WindowContainer, Workspace},
   traits::{CommonGetters, WindowGetters},
 };
 
@@ -77,6 +80,9 @@ impl UserConfig {
 
   /// Reads and validates the user config from the given path.
   ///
+  /// Includes, machine overrides and environment variables are resolved
+  /// first, in which case the returned string is the merged config.
+  ///
   /// Creates a new config file from sample if it doesn't exist.
   fn read(
     config_path: &PathBuf,
@@ -88,11 +94,25 @@ impl UserConfig {
     let config_str = fs::read_to_string(config_path)
       .context("Unable to read config file.")?;
 
-    // TODO: Improve error formatting of serde_yaml errors. Something
-    // similar to https://github.com/AlexanderThaller/format_serde_error
-    let config_value = serde_yaml::from_str(&config_str)?;
+    let resolved_value = resolve_config(config_path, &config_str)?;
+
+    // Parse the config as written if nothing was resolved, so that errors
+    // refer to its line numbers.
+    if resolved_value == serde_yaml::from_str::<Value>(&config_str)? {
+      // TODO: Improve error formatting of serde_yaml errors. Something
+      // similar to https://github.com/AlexanderThaller/format_serde_error
+      let config_value: ParsedConfig = serde_yaml::from_str(&config_str)?;
+      config_value.validate()?;
+
+      return Ok((config_value, config_str));
+    }
 
-    Ok((config_value, config_str))
+    let resolved_str = serde_yaml::to_string(&resolved_value)?;
+    let config_value: ParsedConfig =
+      serde_yaml::from_value(resolved_value)?;
+    config_value.validate()?;
+
+    Ok((config_value, resolved_str))
   }
 
   /// Initializes a new config file from the sample config resource.
@@ -142,17 +162,20 @@ impl UserConfig {
       }],
       match_window: vec![
         WindowMatchConfig {
-          window_class: Some(MatchType::Equals { equals:
-          // W10/W11 system dialog shown when moving and deleting files.
-          "OperationStatusWindow".to_string(),
-        }),
+          window_class: Some(MatchType::Equals {
+            // W10/W11 system dialog shown when moving and deleting files.
+            equals: "OperationStatusWindow".to_string(),
+            case_insensitive: false,
+          }),
           ..WindowMatchConfig::default()
         },
         WindowMatchConfig {
-          window_class: Some(MatchType::Equals { equals:
-          // W10/W11 system dialogs (e.g. File Explorer save/open dialog).
-          "#32770".to_string(),
-        }),
+          window_class: Some(MatchType::Equals {
+            // W10/W11 system dialogs (e.g. File Explorer save/open
+            // dialog).
+            equals: "#32770".to_string(),
+            case_insensitive: false,
+          }),
           ..WindowMatchConfig::default()
         },
       ],
@@ -167,18 +190,21 @@ impl UserConfig {
         WindowMatchConfig {
           window_process: Some(MatchType::Equals {
             equals: "SearchApp".to_string(),
+            case_insensitive: false,
           }),
           ..WindowMatchConfig::default()
         },
         WindowMatchConfig {
           window_process: Some(MatchType::Equals {
             equals: "SearchHost".to_string(),
+            case_insensitive: false,
           }),
           ..WindowMatchConfig::default()
         },
         WindowMatchConfig {
           window_process: Some(MatchType::Equals {
             equals: "ShellExperienceHost".to_string(),
+            case_insensitive: false,
           }),
           ..WindowMatchConfig::default()
         },
@@ -186,6 +212,7 @@ impl UserConfig {
           window_process: Some(MatchType::Equals {
             // W10/11 start menu.
             equals: "StartMenuExperienceHost".to_string(),
+            case_insensitive: false,
           }),
           ..WindowMatchConfig::default()
         },
@@ -193,6 +220,7 @@ impl UserConfig {
           window_process: Some(MatchType::Equals {
             // W10/11 screen snipping tool.
             equals: "ScreenClippingHost".to_string(),
+            case_insensitive: false,
           }),
           ..WindowMatchConfig::default()
         },
@@ -200,6 +228,7 @@ impl UserConfig {
           window_process: Some(MatchType::Equals {
             // W11 lock screen.
             equals: "LockApp".to_string(),
+            case_insensitive: false,
           }),
           ..WindowMatchConfig::default()
         },
@@ -242,14 +271,16 @@ impl UserConfig {
     window: &WindowContainer,
     event: &WindowRuleEvent,
   ) -> anyhow::Result<Vec<WindowRuleConfig>> {
-    let window_title = window.native().title()?;
-    let window_class = window.native().class_name()?;
-    let window_process = window.native().process_name()?;
-
-    let pending_window_rules = self
-      .window_rules_by_event
-      .get(event)
-      .unwrap_or(&Vec::new())
+    let Some(window_rules) = self.window_rules_by_event.get(event) else {
+      return Ok(Vec::new());
+    };
+
+    let properties = Self::window_match_properties(
+      window,
+      window_rules.iter().flat_map(|rule| &rule.match_window),
+    )?;
+
+    let pending_window_rules = window_rules
       .iter()
       .filter(|rule| {
         // Skip if window has already ran the rule.
@@ -258,24 +289,10 @@ impl UserConfig {
         }
 
         // Check if the window matches the rule.
-        rule.match_window.iter().any(|match_config| {
-          let is_process_match = match_config
-            .window_process
-            .as_ref()
-            .is_none_or(|match_type| match_type.is_match(&window_process));
-
-          let is_class_match = match_config
-            .window_class
-            .as_ref()
-            .is_none_or(|match_type| match_type.is_match(&window_class));
-
-          let is_title_match = match_config
-            .window_title
-            .as_ref()
-            .is_none_or(|match_type| match_type.is_match(&window_title));
-
-          is_process_match && is_class_match && is_title_match
-        })
+        rule
+          .match_window
+          .iter()
+          .any(|match_config| match_config.is_match(&properties))
       })
       .cloned()
       .collect::<Vec<_>>();
@@ -283,88 +300,105 @@ impl UserConfig {
     Ok(pending_window_rules)
   }
 
-  pub fn inactive_workspace_configs(
-    &self,
-    active_workspaces: &[Workspace],
-  ) -> Vec<&WorkspaceConfig> {
-    self
-      .value
-      .workspaces
-      .iter()
-      .filter(|config| {
-        !active_workspaces
-          .iter()
-          .any(|workspace| workspace.config().name == config.name)
-      })
-      .collect()
+  /// Whether the window matches any of the given match configs.
+  pub fn is_window_match(
+    window: &WindowContainer,
+    match_configs: &[WindowMatchConfig],
+  ) -> anyhow::Result<bool> {
+    if match_configs.is_empty() {
+      return Ok(false);
+    }
+
+    let properties = Self::window_match_properties(window, match_configs)?;
+
+    Ok(
+      match_configs
+        .iter()
+        .any(|match_config| match_config.is_match(&properties)),
+    )
   }
 
-  pub fn workspace_config_for_monitor(
-    &self,
-    monitor: &Monitor,
-    active_workspaces: &[Workspace],
-  ) -> Option<&WorkspaceConfig> {
-    let inactive_configs =
-      self.inactive_workspace_configs(active_workspaces);
-
-    inactive_configs.into_iter().find(|&config| {
-      config
-        .bind_to_monitor
-        .as_ref()
-        .is_some_and(|monitor_index| {
-          monitor.index() == *monitor_index as usize
-        })
+  /// Gets the properties of a window that window rules are matched
+  /// against.
+  ///
+  /// The process path and elevation need to open the window's process,
+  /// so they're only retrieved if any of the given match configs use
+  /// them.
+  fn window_match_properties<'a>(
+    window: &WindowContainer,
+    match_configs: impl IntoIterator<Item = &'a WindowMatchConfig>,
+  ) -> anyhow::Result<WindowMatchProperties> {
+    let native = window.native();
+    let initial_position = native.initial_frame_position()?;
+
+    let (uses_process_path, uses_is_elevated) = match_configs
+      .into_iter()
+      .fold((false, false), |(uses_path, uses_elevated), config| {
+        (
+          uses_path || config.uses_process_path(),
+          uses_elevated || config.uses_is_elevated(),
+        )
+      });
+
+    Ok(WindowMatchProperties {
+      process_name: native.process_name()?,
+      class_name: native.class_name()?,
+      title: native.title()?,
+      process_path: uses_process_path
+        .then(|| native.process_path().ok())
+        .flatten(),
+      is_tool_window: native.is_tool_window(),
+      is_dialog: native.is_dialog()?,
+      has_owner: native.has_owner(),
+      initial_width: initial_position.width(),
+      initial_height: initial_position.height(),
+      monitor_index: window.monitor().map(|monitor| monitor.index()),
+      workspace_name: window
+        .workspace()
+        .map(|workspace| workspace.config().name),
+      state: window.state(),
+      is_elevated: uses_is_elevated
+        .then(|| native.is_elevated().ok())
+        .flatten(),
+      is_urgent: window.is_urgent(),
     })
   }
 
-  /// Gets the first inactive workspace config, prioritizing configs that
-  /// don't have a monitor binding.
-  pub fn next_inactive_workspace_config(
+  /// Gets the gaps config for a workspace, which is either the
+  /// workspace's gaps override or the global gaps config.
+  pub fn gaps_for_workspace(
     &self,
-    active_workspaces: &[Workspace],
-  ) -> Option<&WorkspaceConfig> {
-    let inactive_configs =
-      self.inactive_workspace_configs(active_workspaces);
-
-    inactive_configs
-      .iter()
-      .find(|config| config.bind_to_monitor.is_none())
-      .or(inactive_configs.first())
-      .copied()
+    workspace_config: &WorkspaceConfig,
+  ) -> GapsConfig {
+    workspace_config
+      .gaps
+      .clone()
+      .unwrap_or_else(|| self.value.gaps.clone())
   }
 
-  pub fn workspace_config_index(
+  /// Gets the hide method for windows in a workspace, which is either the
+  /// workspace's hide method override or the global hide method.
+  pub fn hide_method_for_workspace(
     &self,
-    workspace_name: &str,
-  ) -> Option<usize> {
-    self
-      .value
-      .workspaces
-      .iter()
-      .position(|config| config.name == workspace_name)
-  }
-
-  pub fn sort_workspaces(&self, workspaces: &mut [Workspace]) {
-    workspaces.sort_by_key(|workspace| {
-      self.workspace_config_index(&workspace.config().name)
-    });
+    workspace: Option<&Workspace>,
+  ) -> HideMethod {
+    workspace
+      .and_then(|workspace| workspace.config().hide_method)
+      .unwrap_or_else(|| self.value.general.hide_method.clone())
   }
 
-  pub fn outer_gaps_for_workspace(
+  /// Gets the state for new windows in a workspace, taking the
+  /// workspace's initial window state override into account.
+  pub fn initial_window_state(
     &self,
     workspace: &Workspace,
-  ) -> &wm_common::RectDelta {
-    let is_single_window = workspace.tiling_children().nth(1).is_none();
-
-    if is_single_window {
-      self
-        .value
-        .gaps
-        .single_window_outer_gap
-        .as_ref()
-        .unwrap_or(&self.value.gaps.outer_gap)
-    } else {
-      &self.value.gaps.outer_gap
+  ) -> WindowState {
+    match workspace.config().initial_window_state {
+      Some(InitialWindowState::Tiling) => WindowState::Tiling,
+      Some(InitialWindowState::Floating) => WindowState::Floating(
+        self.value.window_behavior.state_defaults.floating.clone(),
+      ),
+      None => WindowState::default_from_config(&self.value),
     }
   }
 }
@@ -16,13 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use std::time::Instant;
+
 use anyhow::{bail, Context};
 use tokio::sync::mpsc::{self};
-use tracing::warn;
+use tracing::{debug_span, info, warn};
 use uuid::Uuid;
 use wm_common::{
-  FloatingStateConfig, FullscreenStateConfig, InvokeCommand, LengthValue,
-  RectDelta, TitleBarVisibility, WindowState, WmEvent,
+  ContainerDto, FloatingStateConfig, FullscreenStateConfig, GridCell,
+  GridPlacement, InvokeCommand, LengthValue, RectDelta, SizeConstraints,
+  StickyScope, WindowEffectOverrides, WindowState, WmEvent,
 };
 use wm_platform::PlatformEvent;
 
@@ -33,25 +36,36 @@ use crate::{
       toggle_tiling_direction,
     },
     general::{
-      cycle_focus, disable_binding_mode, enable_binding_mode,
-      platform_sync, reload_config, shell_exec, toggle_pause,
+      cycle_focus, disable_binding_mode, dump_diagnostics,
+      enable_binding_mode, platform_sync, reload_config, restore_layout,
+      shell_exec, toggle_pause,
     },
     monitor::focus_monitor,
     window::{
+      end_focus_cycle, focus_recent_window, focus_urgent_window,
       ignore_window, move_window_in_direction, move_window_to_workspace,
-      resize_window, set_window_position, set_window_size,
-      update_window_state, WindowPositionTarget,
+      resize_window, run_queued_window_rules, set_window_effects,
+      set_window_position, set_window_size, sync_sticky_windows,
+      transparency_override, update_window_state, RecentWindowTarget,
+      WindowPositionTarget,
+    },
+    workspace::{
+      arrange_floating_windows, create_workspace, delete_workspace,
+      focus_workspace, move_workspace_in_direction, rename_workspace,
+      reorder_workspace, run_workspace_commands, set_workspace_monocle,
+      sync_monocle_workspaces, FloatingArrangement,
     },
-    workspace::{focus_workspace, move_workspace_in_direction},
   },
   events::{
-    handle_display_settings_changed, handle_mouse_move,
-    handle_window_destroyed, handle_window_focused, handle_window_hidden,
+    handle_display_settings_changed, handle_modifiers_released,
+    handle_mouse_move, handle_window_destroyed, handle_window_flashed,
+    handle_window_focused, handle_window_hidden,
     handle_window_location_changed, handle_window_minimize_ended,
     handle_window_minimized, handle_window_moved_or_resized_end,
     handle_window_moved_or_resized_start, handle_window_shown,
     handle_window_title_changed,
   },
+  handoff::Handoff,
   models::{Container, WorkspaceTarget},
   traits::{CommonGetters, WindowGetters},
   user_config::UserConfig,
@@ -61,28 +75,120 @@ use crate::{
 pub struct WindowManager {
   pub event_rx: mpsc::UnboundedReceiver<WmEvent>,
   pub exit_rx: mpsc::UnboundedReceiver<()>,
+  pub size_constraints_rx:
+    mpsc::UnboundedReceiver<(Uuid, SizeConstraints)>,
   pub state: WmState,
 }
 
 impl WindowManager {
-  pub fn new(config: &mut UserConfig) -> anyhow::Result<Self> {
+  /// Creates the WM and populates its initial state, either from the
+  /// existing windows or from a previous instance that handed over via
+  /// `wm-restart`.
+  pub fn new(
+    config: &mut UserConfig,
+    handoff: Option<&Handoff>,
+  ) -> anyhow::Result<Self> {
     let (event_tx, event_rx) = mpsc::unbounded_channel();
     let (exit_tx, exit_rx) = mpsc::unbounded_channel();
+    let (size_constraints_tx, size_constraints_rx) =
+      mpsc::unbounded_channel();
+
+    let mut state = WmState::new(
+      event_tx.clone(),
+      exit_tx.clone(),
+      size_constraints_tx.clone(),
+    );
+
+    let Some(handoff) = handoff else {
+      state.populate(config)?;
+
+      return Ok(Self {
+        event_rx,
+        exit_rx,
+        size_constraints_rx,
+        state,
+      });
+    };
+
+    if let Err(err) = state.adopt(handoff, config) {
+      warn!("Failed to adopt state of previous instance: {:?}", err);
+
+      // Fall back to managing the windows anew, and then moving them back
+      // to where they were. Dropping the partially adopted state shows
+      // any windows that it hid.
+      drop(state);
+      state = WmState::new(event_tx, exit_tx, size_constraints_tx);
+      state.populate(config)?;
+
+      let mut wm = Self {
+        event_rx,
+        exit_rx,
+        size_constraints_rx,
+        state,
+      };
+
+      wm.restore_layout(&handoff.monitors, config)?;
+      return Ok(wm);
+    }
 
-    let mut state = WmState::new(event_tx, exit_tx);
-    state.populate(config)?;
+    info!(
+      "Adopted {} windows from previous instance.",
+      state.windows().len()
+    );
 
     Ok(Self {
       event_rx,
       exit_rx,
+      size_constraints_rx,
       state,
     })
   }
 
+  /// Restores the layout of a previous instance from a layout snapshot.
+  pub fn restore_layout(
+    &mut self,
+    monitors: &[ContainerDto],
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let state = &mut self.state;
+    restore_layout(monitors, state, config)?;
+
+    if state.pending_sync.has_changes() {
+      platform_sync(state, config)?;
+    }
+
+    Ok(())
+  }
+
   pub fn process_event(
     &mut self,
     event: PlatformEvent,
     config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let kind = event.kind();
+
+    let _span = debug_span!(
+      "platform_event",
+      kind,
+      window = event.window().map(|window| window.handle),
+    )
+    .entered();
+
+    let started_at = Instant::now();
+    let res = self.handle_event(event, config);
+
+    self
+      .state
+      .metrics
+      .record_platform_event(kind, started_at.elapsed());
+
+    res
+  }
+
+  fn handle_event(
+    &mut self,
+    event: PlatformEvent,
+    config: &mut UserConfig,
   ) -> anyhow::Result<()> {
     let state = &mut self.state;
 
@@ -96,17 +202,24 @@ impl WindowManager {
         // Return early since we don't want to redraw twice.
         return Ok(());
       }
+      PlatformEvent::ModifiersReleased => {
+        handle_modifiers_released(state);
+        Ok(())
+      }
       PlatformEvent::MouseMove(event) => {
         handle_mouse_move(&event, state, config)
       }
       PlatformEvent::WindowDestroyed(window) => {
-        handle_window_destroyed(&window, state)
+        handle_window_destroyed(&window, state, config)
+      }
+      PlatformEvent::WindowFlashed(window) => {
+        handle_window_flashed(&window, state, config)
       }
       PlatformEvent::WindowFocused(window) => {
         handle_window_focused(&window, state, config)
       }
       PlatformEvent::WindowHidden(window) => {
-        handle_window_hidden(&window, state)
+        handle_window_hidden(&window, state, config)
       }
       PlatformEvent::WindowLocationChanged(window) => {
         handle_window_location_changed(&window, state, config)
@@ -132,6 +245,63 @@ impl WindowManager {
       }
     }?;
 
+    run_queued_window_rules(state, config)?;
+    run_workspace_commands(state, config)?;
+    sync_sticky_windows(state)?;
+    sync_monocle_workspaces(state);
+
+    if !state.is_paused && state.pending_sync.has_changes() {
+      platform_sync(state, config)?;
+    }
+
+    Ok(())
+  }
+
+  /// Applies size constraints of a window that were retrieved in the
+  /// background. Constraints set via `set-size-constraints` take
+  /// precedence over the retrieved ones.
+  pub fn process_size_constraints(
+    &mut self,
+    window_id: Uuid,
+    size_constraints: &SizeConstraints,
+    config: &UserConfig,
+  ) -> anyhow::Result<()> {
+    let state = &mut self.state;
+
+    let window = state
+      .container_by_id(window_id)
+      .and_then(|container| container.as_window_container().ok())
+      .or_else(|| {
+        state
+          .swallowed_windows
+          .values()
+          .find(|window| window.id() == window_id)
+          .cloned()
+      });
+
+    // Window might have been unmanaged in the meantime.
+    let Some(window) = window else {
+      return Ok(());
+    };
+
+    window.set_size_constraints(
+      size_constraints.with_overrides(&window.size_constraints()),
+    );
+
+    if window.is_detached() {
+      return Ok(());
+    }
+
+    // Constraints of a tiling window can affect the layout of the whole
+    // workspace.
+    if window.state() == WindowState::Tiling {
+      state.pending_sync.queue_container_to_redraw(
+        window.workspace().context("No workspace.")?,
+      );
+    } else {
+      state.pending_sync.queue_container_to_redraw(window);
+    }
+
     if !state.is_paused && state.pending_sync.has_changes() {
       platform_sync(state, config)?;
     }
@@ -157,6 +327,8 @@ impl WindowManager {
         .context("No subject container for command.")?,
     };
 
+    let started_at = Instant::now();
+
     let new_subject_container_id = WindowManager::run_commands(
       commands,
       subject_container,
@@ -164,6 +336,15 @@ impl WindowManager {
       config,
     )?;
 
+    state
+      .metrics
+      .record_commands(commands.len(), started_at.elapsed());
+
+    run_queued_window_rules(state, config)?;
+    run_workspace_commands(state, config)?;
+    sync_sticky_windows(state)?;
+    sync_monocle_workspaces(state);
+
     if state.pending_sync.has_changes() {
       platform_sync(state, config)?;
     }
@@ -210,8 +391,16 @@ impl WindowManager {
     state: &mut WmState,
     config: &mut UserConfig,
   ) -> anyhow::Result<()> {
-    // No-op if WM is currently paused.
-    if state.is_paused && *command != InvokeCommand::WmTogglePause {
+    let _span = debug_span!("command", ?command).entered();
+
+    // No-op if WM is currently paused. Restarting is still allowed,
+    // since the new instance stays paused.
+    if state.is_paused
+      && !matches!(
+        command,
+        InvokeCommand::WmTogglePause | InvokeCommand::WmRestart
+      )
+    {
       return Ok(());
     }
 
@@ -239,6 +428,18 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::ArrangeFloating(args) => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        let arrangement = if args.cascade {
+          FloatingArrangement::Cascade
+        } else {
+          FloatingArrangement::Tile
+        };
+
+        arrange_floating_windows(&workspace, &arrangement, state)
+      }
       InvokeCommand::Close => {
         match subject_container.as_window_container() {
           Ok(window) => {
@@ -253,8 +454,19 @@ impl WindowManager {
         }
       }
       InvokeCommand::Focus(args) => {
+        // Any other focus command ends an in-progress cycle through
+        // recent windows.
+        if !args.cycle_recent {
+          end_focus_cycle(state);
+        }
+
         if let Some(direction) = &args.direction {
-          focus_in_direction(&subject_container, direction, state)?;
+          focus_in_direction(
+            &subject_container,
+            direction,
+            state,
+            config,
+          )?;
         }
 
         if let Some(direction) = &args.workspace_in_direction {
@@ -301,6 +513,30 @@ impl WindowManager {
           focus_workspace(WorkspaceTarget::Recent, state, config)?;
         }
 
+        if args.back {
+          focus_recent_window(&RecentWindowTarget::Back, state, config)?;
+        }
+
+        if args.forward {
+          focus_recent_window(
+            &RecentWindowTarget::Forward,
+            state,
+            config,
+          )?;
+        }
+
+        if args.urgent {
+          focus_urgent_window(state, config)?;
+        }
+
+        if args.cycle_recent {
+          focus_recent_window(
+            &RecentWindowTarget::CycleRecent,
+            state,
+            config,
+          )?;
+        }
+
         if args.next_active_workspace_on_monitor {
           focus_workspace(
             WorkspaceTarget::NextActiveInMonitor,
@@ -432,19 +668,27 @@ impl WindowManager {
       InvokeCommand::Position(args) => {
         match subject_container.as_window_container() {
           Ok(window) => {
-            if args.centered {
-              set_window_position(
-                window,
-                &WindowPositionTarget::Centered,
-                state,
-              )
+            let target = if args.centered {
+              WindowPositionTarget::Centered
+            } else if let Some(snap) = &args.snap {
+              WindowPositionTarget::Grid(snap.grid_placement())
+            } else if let Some(grid) = &args.grid {
+              WindowPositionTarget::Grid(GridPlacement {
+                size: grid.clone(),
+                cell: args
+                  .cell
+                  .clone()
+                  .unwrap_or(GridCell { column: 0, row: 0 }),
+                span: args
+                  .span
+                  .clone()
+                  .unwrap_or(GridCell { column: 1, row: 1 }),
+              })
             } else {
-              set_window_position(
-                window,
-                &WindowPositionTarget::Coordinates(args.x_pos, args.y_pos),
-                state,
-              )
-            }
+              WindowPositionTarget::Coordinates(args.x_pos, args.y_pos)
+            };
+
+            set_window_position(window, &target, state)
           }
           _ => Ok(()),
         }
@@ -460,6 +704,22 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::SetEffects(args) => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            let overrides = WindowEffectOverrides {
+              border_color: args.border_color.clone(),
+              corner_style: args.corner_style.clone(),
+              transparency: args.opacity.clone(),
+              title_bar: args.title_bar.clone(),
+            };
+
+            set_window_effects(&window, &overrides, args.reset, state);
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
       InvokeCommand::SetFloating {
         centered,
         shown_on_top,
@@ -554,6 +814,56 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::SetMonocle => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        set_workspace_monocle(&workspace, true, state)
+      }
+      InvokeCommand::SetSizeConstraints(args) => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            let overrides = SizeConstraints {
+              min_width: args.min_width,
+              min_height: args.min_height,
+              max_width: args.max_width,
+              max_height: args.max_height,
+            };
+
+            window.set_size_constraints(
+              window.size_constraints().with_overrides(&overrides),
+            );
+
+            // Constraints of a tiling window can affect the layout of the
+            // whole workspace.
+            if window.state() == WindowState::Tiling {
+              state.pending_sync.queue_container_to_redraw(
+                window.workspace().context("No workspace.")?,
+              );
+            } else {
+              state.pending_sync.queue_container_to_redraw(window);
+            }
+
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
+      InvokeCommand::SetSticky { all_monitors } => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            window.set_sticky(Some(if *all_monitors {
+              StickyScope::AllMonitors
+            } else {
+              StickyScope::Monitor
+            }));
+            state.pending_sync.queue_container_to_redraw(window);
+
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
       InvokeCommand::SetTiling => {
         match subject_container.as_window_container() {
           Ok(window) => {
@@ -572,9 +882,12 @@ impl WindowManager {
       InvokeCommand::SetTitleBarVisibility { visibility } => {
         match subject_container.as_window_container() {
           Ok(window) => {
-            _ = window.native().set_title_bar_visibility(
-              *visibility == TitleBarVisibility::Shown,
-            );
+            let overrides = WindowEffectOverrides {
+              title_bar: Some(visibility.clone()),
+              ..WindowEffectOverrides::default()
+            };
+
+            set_window_effects(&window, &overrides, false, state);
             Ok(())
           }
           _ => Ok(()),
@@ -583,14 +896,16 @@ impl WindowManager {
       InvokeCommand::SetTransparency(args) => {
         match subject_container.as_window_container() {
           Ok(window) => {
-            if let Some(opacity) = &args.opacity {
-              _ = window.native().set_transparency(opacity);
-            }
-
-            if let Some(opacity_delta) = &args.opacity_delta {
-              _ = window.native().adjust_transparency(opacity_delta);
-            }
-
+            let overrides = WindowEffectOverrides {
+              transparency: Some(transparency_override(
+                &window,
+                args.opacity.as_ref(),
+                args.opacity_delta.as_ref(),
+              )),
+              ..WindowEffectOverrides::default()
+            };
+
+            set_window_effects(&window, &overrides, false, state);
             Ok(())
           }
           _ => Ok(()),
@@ -611,6 +926,32 @@ impl WindowManager {
           _ => Ok(()),
         }
       }
+      InvokeCommand::ToggleMonocle => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        set_workspace_monocle(&workspace, !workspace.is_monocle(), state)
+      }
+      InvokeCommand::ToggleSticky { all_monitors } => {
+        match subject_container.as_window_container() {
+          Ok(window) => {
+            let sticky = match window.sticky() {
+              Some(_) => None,
+              None => Some(if *all_monitors {
+                StickyScope::AllMonitors
+              } else {
+                StickyScope::Monitor
+              }),
+            };
+
+            window.set_sticky(sticky);
+            state.pending_sync.queue_container_to_redraw(window);
+
+            Ok(())
+          }
+          _ => Ok(()),
+        }
+      }
       InvokeCommand::ToggleFloating {
         centered,
         shown_on_top,
@@ -734,6 +1075,9 @@ impl WindowManager {
         disable_binding_mode(name, state);
         Ok(())
       }
+      InvokeCommand::WmDumpDiagnostics { path } => {
+        dump_diagnostics(path.as_deref(), state, config)
+      }
       InvokeCommand::WmEnableBindingMode { name } => {
         enable_binding_mode(name, state, config)
       }
@@ -746,10 +1090,40 @@ impl WindowManager {
         Ok(())
       }
       InvokeCommand::WmReloadConfig => reload_config(state, config),
+      InvokeCommand::WmRestart => {
+        state.is_restarting = true;
+        state.emit_exit()
+      }
       InvokeCommand::WmTogglePause => {
         toggle_pause(state);
         Ok(())
       }
+      InvokeCommand::Workspace(args) => {
+        let workspace =
+          subject_container.workspace().context("No workspace.")?;
+
+        if let Some(name) = &args.create {
+          create_workspace(name, state, config)?;
+        }
+
+        if let Some(name) = &args.rename {
+          rename_workspace(&workspace, name, state, config)?;
+        }
+
+        if let Some(offset) = args.reorder {
+          reorder_workspace(&workspace, offset, state, config)?;
+        }
+
+        if let Some(name) = &args.delete {
+          delete_workspace(
+            &name.clone().unwrap_or_else(|| workspace.config().name),
+            state,
+            config,
+          )?;
+        }
+
+        Ok(())
+      }
     }
   }
 }
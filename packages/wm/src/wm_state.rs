@@ -16,15 +16,19 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, time::Instant};
+use std::{
+  collections::{HashMap, HashSet},
+  time::Instant,
+};
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use tokio::sync::mpsc::{self};
 use tracing::warn;
 use uuid::Uuid;
 use wm_common::{
   BindingModeConfig, Direction, FloatingStateConfig, LengthValue, Point,
-  Rect, RectDelta, WindowState, WmEvent,
+  Rect, RectDelta, SizeConstraints, WindowEffectOverrides,
+  WindowRuleEvent, WindowState, WmEvent, WorkspaceConfig,
 };
 use wm_platform::{NativeMonitor, NativeWindow, Platform};
 
@@ -35,17 +39,27 @@ use crate::{
     container::set_focused_descendant,
     general::platform_sync,
     monitor::{add_monitor, move_bounded_workspaces_to_new_monitor},
-    window::manage_window,
+    window::{manage_window, run_window_rules},
+    workspace::PendingWorkspaceCommands,
   },
+  focus_history::FocusHistory,
+  handoff::Handoff,
+  metrics::Metrics,
   models::{
     Container, Monitor, NonTilingWindow, RootContainer, WindowContainer,
     Workspace, WorkspaceTarget,
   },
   pending_sync::PendingSync,
+  remembered_placements::RememberedPlacements,
+  runtime_workspaces::{
+    merge_workspace_configs, read_persisted_workspace_configs,
+    PersistedWorkspace, RuntimeWorkspaceConfig,
+  },
   traits::{CommonGetters, PositionGetters, WindowGetters},
   user_config::UserConfig,
 };
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct WmState {
   /// Root node of the container tree. Monitors are the children of the
   /// root node, followed by workspaces, then split containers/windows.
@@ -85,12 +99,51 @@ pub struct WmState {
   /// Keyed by window handle (isize).
   pub fullscreen_cooldowns: HashMap<isize, Instant>,
 
+  /// Workspace commands (e.g. `on_activate`) to run once the current
+  /// event or command has been processed.
+  pub pending_workspace_commands: Vec<PendingWorkspaceCommands>,
+
+  /// Window rule events (e.g. `StateChange`) to run once the current
+  /// event or command has been processed. Keyed by window ID.
+  pub pending_window_rule_events: Vec<(Uuid, WindowRuleEvent)>,
+
+  /// Workspace configs in their runtime order.
+  ///
+  /// Initialized from the user config, and updated when workspaces are
+  /// created, renamed, reordered or deleted at runtime.
+  pub workspace_configs: Vec<RuntimeWorkspaceConfig>,
+
+  /// Workspaces that had windows when workspace commands were last run.
+  ///
+  /// Used to run `on_empty` commands when the last window leaves a
+  /// workspace.
+  pub occupied_workspace_ids: HashSet<Uuid>,
+
+  /// History of focused windows. Used for `focus --back`, `focus
+  /// --forward` and `focus --cycle-recent`.
+  pub focus_history: FocusHistory,
+
+  /// Floating placements restored by the `remember_per_app` placement
+  /// strategy.
+  pub remembered_placements: RememberedPlacements,
+
+  /// Windows that are hidden while a window they launched takes their
+  /// place in the tree. Keyed by the ID of the launched window.
+  pub swallowed_windows: HashMap<Uuid, WindowContainer>,
+
   /// Whether the WM is paused.
   pub is_paused: bool,
 
+  /// Whether the WM is exiting to hand over to a new instance via
+  /// `wm-restart`, in which case windows are left as they are on exit.
+  pub is_restarting: bool,
+
   /// Whether the OS focused window is the same as the WM focused window.
   pub is_focus_synced: bool,
 
+  /// Counters and latency histograms of the event loop.
+  pub metrics: Metrics,
+
   /// Whether the initial state has been populated.
   has_initialized: bool,
 
@@ -99,12 +152,17 @@ pub struct WmState {
 
   /// Sender for gracefully shutting down the WM.
   exit_tx: mpsc::UnboundedSender<()>,
+
+  /// Sender for size constraints of windows that were retrieved in the
+  /// background.
+  size_constraints_tx: mpsc::UnboundedSender<(Uuid, SizeConstraints)>,
 }
 
 impl WmState {
   pub fn new(
     event_tx: mpsc::UnboundedSender<WmEvent>,
     exit_tx: mpsc::UnboundedSender<()>,
+    size_constraints_tx: mpsc::UnboundedSender<(Uuid, SizeConstraints)>,
   ) -> Self {
     Self {
       root_container: RootContainer::new(),
@@ -115,11 +173,21 @@ impl WmState {
       fullscreen_cooldowns: HashMap::new(),
       binding_modes: Vec::new(),
       ignored_windows: Vec::new(),
+      pending_workspace_commands: Vec::new(),
+      pending_window_rule_events: Vec::new(),
+      workspace_configs: Vec::new(),
+      occupied_workspace_ids: HashSet::new(),
+      focus_history: FocusHistory::default(),
+      remembered_placements: RememberedPlacements::default(),
+      swallowed_windows: HashMap::new(),
       is_paused: false,
+      is_restarting: false,
       is_focus_synced: false,
+      metrics: Metrics::new(),
       has_initialized: false,
       event_tx,
       exit_tx,
+      size_constraints_tx,
     }
   }
 
@@ -136,6 +204,25 @@ impl WmState {
       warn!("Failed to recover orphaned windows: {:?}", err);
     }
 
+    let persisted_workspace_configs =
+      if config.value.general.persist_dynamic_workspaces {
+        read_persisted_workspace_configs(config).unwrap_or_else(|err| {
+          warn!("Failed to read persisted workspaces: {:?}", err);
+          Vec::new()
+        })
+      } else {
+        Vec::new()
+      };
+
+    self.workspace_configs =
+      merge_workspace_configs(&persisted_workspace_configs, config);
+
+    self.remembered_placements = RememberedPlacements::read(config)
+      .unwrap_or_else(|err| {
+        warn!("Failed to read remembered floating placements: {:?}", err);
+        RememberedPlacements::default()
+      });
+
     // Get the originally focused window when the WM was started.
     let foreground_window = Platform::foreground_window();
 
@@ -188,6 +275,132 @@ impl WmState {
       self.pending_sync.queue_workspace_to_reorder(workspace);
     }
 
+    // Run window rules for windows that were managed on startup.
+    for window in self.windows() {
+      run_window_rules(window, &WindowRuleEvent::Startup, self, config)?;
+    }
+
+    platform_sync(self, config)?;
+    self.has_initialized = true;
+
+    Ok(())
+  }
+
+  /// Populates the initial WM state from the state of a previous
+  /// instance, which handed over to this instance via `wm-restart`.
+  ///
+  /// Windows are adopted where they are instead of being managed anew,
+  /// so hidden windows stay hidden and no window rules are run for them.
+  /// Fails without changing the state if the monitors have changed since
+  /// the handoff.
+  pub fn adopt(
+    &mut self,
+    handoff: &Handoff,
+    config: &mut UserConfig,
+  ) -> anyhow::Result<()> {
+    let native_monitors = Platform::sorted_monitors()?;
+
+    if !handoff.has_same_monitors(&native_monitors) {
+      bail!("Monitors have changed since the handoff.");
+    }
+
+    let handoff_workspace_configs = handoff
+      .workspaces
+      .iter()
+      .map(PersistedWorkspace::to_config)
+      .collect::<Vec<_>>();
+
+    self.workspace_configs =
+      merge_workspace_configs(&handoff_workspace_configs, config);
+
+    self.remembered_placements = RememberedPlacements::read(config)
+      .unwrap_or_else(|err| {
+        warn!("Failed to read remembered floating placements: {:?}", err);
+        RememberedPlacements::default()
+      });
+
+    self.binding_modes = handoff
+      .binding_modes
+      .iter()
+      .filter_map(|name| {
+        config
+          .value
+          .binding_modes
+          .iter()
+          .find(|binding_mode| binding_mode.name == *name)
+          .cloned()
+      })
+      .collect();
+
+    self.is_paused = handoff.is_paused;
+    self
+      .recent_workspace_name
+      .clone_from(&handoff.recent_workspace_name);
+
+    self.focus_history =
+      FocusHistory::from_entries(handoff.focus_history.clone());
+
+    self.ignored_windows = handoff
+      .ignored_handles
+      .iter()
+      .map(|&handle| NativeWindow::new(handle))
+      .filter(NativeWindow::is_valid)
+      .collect();
+
+    let focused_container =
+      handoff.adopt_container_tree(native_monitors, self, config)?;
+
+    handoff.adopt_swallowed_windows(self, config);
+
+    // Manage windows that were opened during the restart.
+    let mut new_windows = Vec::new();
+
+    for native_window in Platform::manageable_windows()?.into_iter().rev()
+    {
+      if self.window_from_native(&native_window).is_some()
+        || self.ignored_windows.contains(&native_window)
+      {
+        continue;
+      }
+
+      let nearest_workspace = self
+        .nearest_monitor(&native_window)
+        .and_then(|m| m.displayed_workspace());
+
+      if let Some(workspace) = nearest_workspace {
+        manage_window(
+          native_window.clone(),
+          Some(workspace.into()),
+          self,
+          config,
+        )?;
+
+        new_windows.extend(self.window_from_native(&native_window));
+      }
+    }
+
+    let container_to_focus = focused_container
+      .or(self.windows().pop().map(Into::into))
+      .or(self.workspaces().pop().map(Into::into))
+      .context("Failed to get container to focus.")?;
+
+    set_focused_descendant(&container_to_focus, None);
+    self.is_focus_synced = true;
+
+    self
+      .pending_sync
+      .queue_container_to_redraw(self.root_container.clone())
+      .queue_focus_change()
+      .queue_all_effects_update();
+
+    for workspace in self.workspaces() {
+      self.pending_sync.queue_workspace_to_reorder(workspace);
+    }
+
+    for window in new_windows {
+      run_window_rules(window, &WindowRuleEvent::Startup, self, config)?;
+    }
+
     platform_sync(self, config)?;
     self.has_initialized = true;
 
@@ -206,13 +419,87 @@ impl WmState {
       .collect()
   }
 
-  /// Gets workspaces sorted by their position in the user config.
-  pub fn sorted_workspaces(&self, config: &UserConfig) -> Vec<Workspace> {
+  /// Gets workspaces sorted by their position in the workspace configs.
+  pub fn sorted_workspaces(&self) -> Vec<Workspace> {
     let mut workspaces = self.workspaces();
-    config.sort_workspaces(&mut workspaces);
+    self.sort_workspaces(&mut workspaces);
     workspaces
   }
 
+  pub fn workspace_config(
+    &self,
+    workspace_name: &str,
+  ) -> Option<&WorkspaceConfig> {
+    self
+      .workspace_configs
+      .iter()
+      .map(|workspace_config| &workspace_config.value)
+      .find(|workspace_config| workspace_config.name == workspace_name)
+  }
+
+  /// Gets workspace configs that don't have an active workspace.
+  pub fn inactive_workspace_configs(&self) -> Vec<&WorkspaceConfig> {
+    let active_workspaces = self.workspaces();
+
+    self
+      .workspace_configs
+      .iter()
+      .map(|workspace_config| &workspace_config.value)
+      .filter(|config| {
+        !active_workspaces
+          .iter()
+          .any(|workspace| workspace.config().name == config.name)
+      })
+      .collect()
+  }
+
+  pub fn workspace_config_for_monitor(
+    &self,
+    monitor: &Monitor,
+  ) -> Option<&WorkspaceConfig> {
+    self
+      .inactive_workspace_configs()
+      .into_iter()
+      .find(|&config| {
+        config
+          .bind_to_monitor
+          .as_ref()
+          .is_some_and(|monitor_index| {
+            monitor.index() == *monitor_index as usize
+          })
+      })
+  }
+
+  /// Gets the first inactive workspace config, prioritizing configs that
+  /// don't have a monitor binding.
+  pub fn next_inactive_workspace_config(
+    &self,
+  ) -> Option<&WorkspaceConfig> {
+    let inactive_configs = self.inactive_workspace_configs();
+
+    inactive_configs
+      .iter()
+      .find(|config| config.bind_to_monitor.is_none())
+      .or(inactive_configs.first())
+      .copied()
+  }
+
+  pub fn workspace_config_index(
+    &self,
+    workspace_name: &str,
+  ) -> Option<usize> {
+    self
+      .workspace_configs
+      .iter()
+      .position(|config| config.value.name == workspace_name)
+  }
+
+  pub fn sort_workspaces(&self, workspaces: &mut [Workspace]) {
+    workspaces.sort_by_key(|workspace| {
+      self.workspace_config_index(&workspace.config().name)
+    });
+  }
+
   pub fn windows(&self) -> Vec<WindowContainer> {
     self
       .root_container
@@ -221,6 +508,34 @@ impl WmState {
       .collect()
   }
 
+  /// Gets all windows, from most to least recently focused.
+  ///
+  /// Windows that aren't in the focus history come last, in the order
+  /// they were last focused within their workspace.
+  pub fn recent_windows(&self) -> Vec<WindowContainer> {
+    let mut windows = self
+      .focus_history
+      .most_recent()
+      .into_iter()
+      .filter_map(|id| self.container_by_id(id))
+      .filter_map(|container| container.as_window_container().ok())
+      .collect::<Vec<_>>();
+
+    let remaining_windows = self
+      .root_container
+      .descendant_focus_order()
+      .filter_map(|container| container.as_window_container().ok())
+      .collect::<Vec<_>>();
+
+    for window in remaining_windows {
+      if !windows.iter().any(|recent| recent.id() == window.id()) {
+        windows.push(window);
+      }
+    }
+
+    windows
+  }
+
   /// Gets the monitor that encompasses the largest portion of a given
   /// window.
   ///
@@ -292,6 +607,50 @@ impl WmState {
     Ok(closest_monitor)
   }
 
+  /// Gets the nearest container in a given direction out of all windows
+  /// on displayed workspaces, based on their positions. Empty displayed
+  /// workspaces are also included.
+  ///
+  /// Minimized windows are excluded.
+  pub fn container_in_direction(
+    &self,
+    origin_container: &Container,
+    direction: &Direction,
+  ) -> anyhow::Result<Option<Container>> {
+    let candidates = self
+      .monitors()
+      .into_iter()
+      .filter_map(|monitor| monitor.displayed_workspace())
+      .flat_map(|workspace| {
+        let windows = workspace
+          .descendants()
+          .filter(|descendant| match descendant.as_window_container() {
+            Ok(window) => window.state() != WindowState::Minimized,
+            Err(_) => false,
+          })
+          .collect::<Vec<_>>();
+
+        if windows.is_empty() {
+          vec![workspace.into()]
+        } else {
+          windows
+        }
+      })
+      .filter(|candidate| candidate.id() != origin_container.id())
+      .collect::<Vec<_>>();
+
+    let candidate_rects = candidates
+      .iter()
+      .map(PositionGetters::to_rect)
+      .try_collect::<Vec<_>>()?;
+
+    let nearest_index = origin_container
+      .to_rect()?
+      .nearest_in_direction(direction, &candidate_rects);
+
+    Ok(nearest_index.map(|index| candidates[index].clone()))
+  }
+
   /// Gets window that corresponds to the given `NativeWindow`.
   pub fn window_from_native(
     &self,
@@ -348,7 +707,7 @@ impl WmState {
           .and_then(|name| self.workspace_by_name(name)),
       ),
       WorkspaceTarget::NextActive => {
-        let active_workspaces = self.sorted_workspaces(config);
+        let active_workspaces = self.sorted_workspaces();
         let origin_index = active_workspaces
           .iter()
           .position(|workspace| workspace.id() == origin_workspace.id())
@@ -364,7 +723,7 @@ impl WmState {
         )
       }
       WorkspaceTarget::PreviousActive => {
-        let active_workspaces = self.sorted_workspaces(config);
+        let active_workspaces = self.sorted_workspaces();
         let origin_index = active_workspaces
           .iter()
           .position(|workspace| workspace.id() == origin_workspace.id())
@@ -387,7 +746,7 @@ impl WmState {
           .context("No monitor in workspace")?;
 
         let mut workspace_in_monitor = monitor.workspaces();
-        config.sort_workspaces(&mut workspace_in_monitor);
+        self.sort_workspaces(&mut workspace_in_monitor);
 
         let origin_index = workspace_in_monitor
           .iter()
@@ -410,7 +769,7 @@ impl WmState {
           .context("No monitor in workspace")?;
 
         let mut workspace_in_monitor = monitor.workspaces();
-        config.sort_workspaces(&mut workspace_in_monitor);
+        self.sort_workspaces(&mut workspace_in_monitor);
 
         let origin_index = workspace_in_monitor
           .iter()
@@ -430,11 +789,10 @@ impl WmState {
         )
       }
       WorkspaceTarget::Next => {
-        let workspaces = &config.value.workspaces;
+        let workspaces = &self.workspace_configs;
         let origin_name = origin_workspace.config().name.clone();
-        let origin_index = workspaces
-          .iter()
-          .position(|workspace| workspace.name == origin_name)
+        let origin_index = self
+          .workspace_config_index(&origin_name)
           .context("Failed to get index of given workspace.")?;
 
         let next_workspace_config = workspaces
@@ -442,7 +800,7 @@ impl WmState {
           .or_else(|| workspaces.first());
 
         let next_workspace_name =
-          next_workspace_config.map(|config| config.name.clone());
+          next_workspace_config.map(|config| config.value.name.clone());
 
         let next_workspace = next_workspace_name
           .as_ref()
@@ -451,19 +809,18 @@ impl WmState {
         (next_workspace_name, next_workspace)
       }
       WorkspaceTarget::Previous => {
-        let workspaces = &config.value.workspaces;
+        let workspaces = &self.workspace_configs;
         let origin_name = origin_workspace.config().name.clone();
-        let origin_index = workspaces
-          .iter()
-          .position(|workspace| workspace.name == origin_name)
+        let origin_index = self
+          .workspace_config_index(&origin_name)
           .context("Failed to get index of given workspace.")?;
 
         let previous_workspace_config = workspaces.get(
           origin_index.checked_sub(1).unwrap_or(workspaces.len() - 1),
         );
 
-        let previous_workspace_name =
-          previous_workspace_config.map(|config| config.name.clone());
+        let previous_workspace_name = previous_workspace_config
+          .map(|config| config.value.name.clone());
 
         let previous_workspace = previous_workspace_name
           .as_ref()
@@ -530,6 +887,30 @@ impl WmState {
     }
   }
 
+  /// Retrieves the size constraints of a window in the background, so
+  /// that windows which are slow to respond don't stall the WM. Once
+  /// retrieved, they're applied via
+  /// `WindowManager::process_size_constraints`.
+  pub fn query_size_constraints(
+    &self,
+    native_window: &NativeWindow,
+    window_id: Uuid,
+  ) {
+    let size_constraints_tx = self.size_constraints_tx.clone();
+
+    native_window.query_size_constraints(move |res| {
+      // Size constraints can be unavailable if the window doesn't respond
+      // (e.g. if it's hung), in which case it stays unconstrained.
+      if let Ok(size_constraints) = res {
+        if let Err(err) =
+          size_constraints_tx.send((window_id, size_constraints))
+        {
+          warn!("Failed to send size constraints: {}", err);
+        }
+      }
+    });
+  }
+
   /// Starts graceful shutdown via an MSPC channel.
   pub fn emit_exit(&self) -> anyhow::Result<()> {
     self.exit_tx.send(())?;
@@ -655,6 +1036,10 @@ impl WmState {
       false,
       Vec::new(),
       None,
+      None,
+      None,
+      SizeConstraints::default(),
+      WindowEffectOverrides::default(),
     );
 
     attach_container(
@@ -685,10 +1070,20 @@ impl WmState {
 
 impl Drop for WmState {
   fn drop(&mut self) {
+    if self.is_restarting {
+      return;
+    }
+
     let managed_windows = self
       .windows()
       .into_iter()
       .map(|window| window.native().clone())
+      .chain(
+        self
+          .swallowed_windows
+          .values()
+          .map(|window| window.native().clone()),
+      )
       .collect::<Vec<_>>();
 
     for window in managed_windows {